@@ -0,0 +1,29 @@
+#!/bin/sh
+# Fires concurrent `GET /api/v1/digital_publications` requests at a running
+# backend and prints the wall time against the sum of the request times.
+# Requests that serialize on the workers make both numbers about equal;
+# with the queries off the workers the wall time is several times lower.
+#
+# Usage: ./load_test.sh [URL] [REQUESTS] [CONCURRENCY]
+set -e
+
+URL=${1:-http://localhost:8081/api/v1/digital_publications}
+REQUESTS=${2:-200}
+CONCURRENCY=${3:-32}
+
+start=$(date +%s.%N)
+times=$(seq "$REQUESTS" | xargs -P "$CONCURRENCY" -I{} \
+    curl -s -o /dev/null -w "%{http_code} %{time_total}\n" "$URL")
+end=$(date +%s.%N)
+
+echo "$times" | awk -v start="$start" -v end="$end" '
+    $1 != 200 { failed++ }
+    { total += $2; if ($2 > slowest) slowest = $2 }
+    END {
+        wall = end - start
+        printf "requests:      %d (%d failed)\n", NR, failed
+        printf "wall time:     %.2fs\n", wall
+        printf "sum of times:  %.2fs\n", total
+        printf "slowest:       %.3fs\n", slowest
+        printf "concurrency:   %.1fx\n", total / wall
+    }'
@@ -0,0 +1,5 @@
+DROP INDEX digital_publications_source_idx;
+DROP INDEX digital_publications_name_idx;
+DROP INDEX digital_publications_created_at_idx;
+
+ALTER TABLE digital_publications DROP COLUMN created_at;
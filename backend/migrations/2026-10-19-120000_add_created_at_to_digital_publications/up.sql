@@ -0,0 +1,6 @@
+ALTER TABLE digital_publications
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+CREATE INDEX digital_publications_created_at_idx ON digital_publications (created_at);
+CREATE INDEX digital_publications_name_idx ON digital_publications (lower(name) varchar_pattern_ops);
+CREATE INDEX digital_publications_source_idx ON digital_publications (source);
@@ -0,0 +1,3 @@
+DROP INDEX digital_publications_name_trgm_idx;
+DROP INDEX digital_publications_search_idx;
+DROP FUNCTION publication_search_vector(VARCHAR, VARCHAR);
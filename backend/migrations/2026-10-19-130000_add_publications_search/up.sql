@@ -0,0 +1,21 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Russian and English words of the name (weight A) and description (weight B).
+-- Cyrillic is only split into words in a database with a UTF-8 LC_CTYPE.
+-- An expression index on it is kept up to date by PostgreSQL itself.
+CREATE FUNCTION publication_search_vector(name VARCHAR, description VARCHAR)
+    RETURNS tsvector
+    LANGUAGE SQL IMMUTABLE PARALLEL SAFE
+AS $$
+    SELECT setweight(to_tsvector('russian', name), 'A')
+        || setweight(to_tsvector('english', name), 'A')
+        || setweight(to_tsvector('russian', description), 'B')
+        || setweight(to_tsvector('english', description), 'B')
+$$;
+
+CREATE INDEX digital_publications_search_idx ON digital_publications
+    USING GIN (publication_search_vector(name, description));
+
+-- Fuzzy search by the name when the words have typos
+CREATE INDEX digital_publications_name_trgm_idx ON digital_publications
+    USING GIN (name gin_trgm_ops);
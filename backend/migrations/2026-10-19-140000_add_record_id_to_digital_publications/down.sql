@@ -0,0 +1,3 @@
+DROP INDEX digital_publications_source_record_id_idx;
+
+ALTER TABLE digital_publications DROP COLUMN record_id;
@@ -0,0 +1,6 @@
+-- Identifier (001) of the RUSMARC record the publication was imported from,
+-- unique within the source so that repeated imports update it.
+ALTER TABLE digital_publications ADD COLUMN record_id VARCHAR NULL;
+
+CREATE UNIQUE INDEX digital_publications_source_record_id_idx
+    ON digital_publications (source, record_id);
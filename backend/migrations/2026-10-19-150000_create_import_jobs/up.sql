@@ -0,0 +1,35 @@
+CREATE TABLE import_jobs (
+    uuid       UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    source     UUID NOT NULL REFERENCES sources(uuid) ON DELETE CASCADE,
+
+    -- Uploaded dump, removed when the job is done
+    path       VARCHAR NOT NULL,
+    format     VARCHAR NOT NULL,
+    status     VARCHAR NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'done', 'failed', 'cancelled')),
+    -- Why the job failed as a whole
+    error      VARCHAR NULL,
+
+    -- Records in the dump, counted when the job starts
+    total      INTEGER NULL,
+    -- Records from the start of the dump that are done, where a resumed job continues
+    processed  INTEGER NOT NULL DEFAULT 0,
+    created    INTEGER NOT NULL DEFAULT 0,
+    updated    INTEGER NOT NULL DEFAULT 0,
+    failed     INTEGER NOT NULL DEFAULT 0,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX import_jobs_status_idx ON import_jobs (status, created_at);
+
+CREATE TABLE import_job_errors (
+    id        SERIAL PRIMARY KEY,
+    job       UUID NOT NULL REFERENCES import_jobs(uuid) ON DELETE CASCADE,
+    position  INTEGER NOT NULL,
+    record_id VARCHAR NULL,
+    error     VARCHAR NOT NULL
+);
+
+CREATE INDEX import_job_errors_job_idx ON import_job_errors (job, position);
@@ -0,0 +1,14 @@
+-- Vendor record a publication was imported from, as it was in the dump
+CREATE TABLE raw_records (
+    publication UUID PRIMARY KEY REFERENCES digital_publications(uuid) ON DELETE CASCADE,
+    source      UUID NOT NULL REFERENCES sources(uuid) ON DELETE CASCADE,
+
+    -- 001
+    record_id   VARCHAR NOT NULL,
+    -- 005 as in the record, `yyyymmddhhmmss.f`
+    version     VARCHAR NULL,
+    -- Array of fields, as in the JSON Lines format of rusmarc_raw
+    fields      JSONB NOT NULL,
+
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,10 @@
+DROP TABLE publication_authors;
+DROP TABLE authors;
+
+ALTER TABLE digital_publications
+    DROP COLUMN isbns,
+    DROP COLUMN year,
+    DROP COLUMN publisher,
+    DROP COLUMN language,
+    DROP COLUMN edition,
+    DROP COLUMN pages;
@@ -0,0 +1,32 @@
+ALTER TABLE digital_publications
+    ADD COLUMN isbns     TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN year      INTEGER NULL,
+    ADD COLUMN publisher VARCHAR NULL,
+    -- ISO 639-2 code of the text, `rus`
+    ADD COLUMN language  VARCHAR NULL,
+    -- `2-е изд., перераб.`
+    ADD COLUMN edition   VARCHAR NULL,
+    ADD COLUMN pages     INTEGER NULL CHECK (pages > 0);
+
+-- People and organizations, shared between their publications
+CREATE TABLE authors (
+    uuid         UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    -- `Иванов, И. И.` for people, as names are sorted in catalogues
+    name         VARCHAR NOT NULL,
+    organization BOOLEAN NOT NULL DEFAULT false,
+
+    UNIQUE (name, organization)
+);
+
+CREATE TABLE publication_authors (
+    publication UUID NOT NULL REFERENCES digital_publications(uuid) ON DELETE CASCADE,
+    author      UUID NOT NULL REFERENCES authors(uuid) ON DELETE CASCADE,
+    role        VARCHAR NOT NULL DEFAULT 'author'
+        CHECK (role IN ('author', 'compiler', 'editor', 'illustrator', 'translator', 'contributor')),
+    -- Order of the authors in the publication, from 0
+    position    INTEGER NOT NULL,
+
+    PRIMARY KEY (publication, author, role)
+);
+
+CREATE INDEX publication_authors_author_idx ON publication_authors (author);
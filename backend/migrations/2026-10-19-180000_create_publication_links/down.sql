@@ -0,0 +1,14 @@
+ALTER TABLE digital_publications ADD COLUMN links TEXT[] NOT NULL DEFAULT '{}';
+
+UPDATE digital_publications p
+SET links = l.urls
+FROM (
+    SELECT publication, array_agg(url ORDER BY position) AS urls
+    FROM publication_links
+    GROUP BY publication
+) l
+WHERE l.publication = p.uuid;
+
+ALTER TABLE digital_publications ALTER COLUMN links DROP DEFAULT;
+
+DROP TABLE publication_links;
@@ -0,0 +1,34 @@
+CREATE TABLE publication_links (
+    uuid        UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    publication UUID NOT NULL REFERENCES digital_publications(uuid) ON DELETE CASCADE,
+    url         VARCHAR NOT NULL,
+    kind        VARCHAR NOT NULL DEFAULT 'read_online'
+        CHECK (kind IN ('read_online', 'download', 'vendor_page', 'doi')),
+    -- Shown instead of the URL, `PDF, 12 МБ`
+    label       VARCHAR NULL,
+    access      VARCHAR NOT NULL DEFAULT 'subscription'
+        CHECK (access IN ('open', 'subscription', 'restricted')),
+    -- Order of the links in the publication, from 0
+    position    INTEGER NOT NULL,
+
+    -- Last check: the HTTP status, or the error when there was no response
+    checked_at  TIMESTAMPTZ NULL,
+    status      INTEGER NULL,
+    error       VARCHAR NULL,
+
+    UNIQUE (publication, url)
+);
+
+-- For the checker, which takes the links checked longest ago
+CREATE INDEX publication_links_checked_at_idx ON publication_links (checked_at NULLS FIRST);
+
+INSERT INTO publication_links (publication, url, kind, position)
+SELECT p.uuid,
+       l.url,
+       CASE WHEN l.url ~ '^https?://(dx\.)?doi\.org/' THEN 'doi' ELSE 'read_online' END,
+       l.position - 1
+FROM digital_publications p, unnest(p.links) WITH ORDINALITY AS l(url, position)
+WHERE l.url IS NOT NULL AND l.url <> ''
+ON CONFLICT (publication, url) DO NOTHING;
+
+ALTER TABLE digital_publications DROP COLUMN links;
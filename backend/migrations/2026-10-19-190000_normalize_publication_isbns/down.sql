@@ -0,0 +1,2 @@
+-- The ISBNs stay compact, there is no telling how they were written
+ALTER TABLE digital_publications DROP CONSTRAINT digital_publications_isbns_check;
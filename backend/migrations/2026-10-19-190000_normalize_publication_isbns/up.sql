@@ -0,0 +1,53 @@
+-- The compact ISBN-13 of `rusmarc_raw::identifiers::Isbn`, or NULL for text
+-- that isn't a valid ISBN. Only for the rows saved before the backend did it.
+CREATE FUNCTION normalize_isbn(raw TEXT) RETURNS TEXT LANGUAGE plpgsql IMMUTABLE AS $$
+DECLARE
+    digits      TEXT := upper(translate(
+        substring(raw FROM '[0-9][0-9 -]{8,16}[0-9XxХх]'), 'Хх -', 'XX'
+    ));
+    total       INTEGER := 0;
+    check_digit INTEGER;
+BEGIN
+    IF digits ~ '^[0-9]{9}[0-9X]$' THEN
+        FOR i IN 1..9 LOOP
+            total := total + (11 - i) * substr(digits, i, 1)::INTEGER;
+        END LOOP;
+        check_digit := (11 - total % 11) % 11;
+        IF right(digits, 1) <> (CASE check_digit WHEN 10 THEN 'X' ELSE check_digit::TEXT END) THEN
+            RETURN NULL;
+        END IF;
+        -- The ISBN-13 check digit is added below
+        digits := '978' || left(digits, 9) || '?';
+    ELSIF digits IS NULL OR digits !~ '^97[89][0-9]{10}$' OR digits ~ '^9790' THEN
+        RETURN NULL;
+    END IF;
+
+    total := 0;
+    FOR i IN 1..12 LOOP
+        total := total + substr(digits, i, 1)::INTEGER * CASE i % 2 WHEN 1 THEN 1 ELSE 3 END;
+    END LOOP;
+    check_digit := (10 - total % 10) % 10;
+    IF right(digits, 1) NOT IN ('?', check_digit::TEXT) THEN
+        RETURN NULL;
+    END IF;
+    RETURN left(digits, 12) || check_digit;
+END
+$$;
+
+-- Valid ISBNs in their order, without repeats
+UPDATE digital_publications
+SET isbns = ARRAY(
+    SELECT normalize_isbn(i.raw)
+    FROM unnest(isbns) WITH ORDINALITY AS i(raw, position)
+    WHERE normalize_isbn(i.raw) IS NOT NULL
+    GROUP BY 1
+    ORDER BY min(i.position)
+)
+WHERE isbns <> '{}';
+
+DROP FUNCTION normalize_isbn;
+
+-- Compact ISBN-13s only, so that they can be compared as text
+ALTER TABLE digital_publications
+    ADD CONSTRAINT digital_publications_isbns_check
+    CHECK (array_to_string(isbns, ',', '-') ~ '^([0-9]{13}(,|$))*$');
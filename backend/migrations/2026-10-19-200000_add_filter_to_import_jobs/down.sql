@@ -0,0 +1,3 @@
+ALTER TABLE import_jobs
+    DROP COLUMN filter,
+    DROP COLUMN skipped;
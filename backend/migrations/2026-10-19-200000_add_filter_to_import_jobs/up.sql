@@ -0,0 +1,5 @@
+ALTER TABLE import_jobs
+    -- Query (rusmarc_raw::query) of the records to import, all of them if NULL
+    ADD COLUMN filter  VARCHAR NULL,
+    -- Records that don't match the filter
+    ADD COLUMN skipped INTEGER NOT NULL DEFAULT 0;
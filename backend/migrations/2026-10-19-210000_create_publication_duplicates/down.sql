@@ -0,0 +1,3 @@
+DROP INDEX digital_publications_isbns_idx;
+
+DROP TABLE publication_duplicates;
@@ -0,0 +1,22 @@
+-- Pairs of publications that are probably the same document, e.g. a book
+-- sent by two vendors, found on import by `rusmarc_raw::dedup` for review
+CREATE TABLE publication_duplicates (
+    -- The one imported later
+    publication  UUID NOT NULL REFERENCES digital_publications(uuid) ON DELETE CASCADE,
+    duplicate_of UUID NOT NULL REFERENCES digital_publications(uuid) ON DELETE CASCADE,
+    reason       VARCHAR NOT NULL CHECK (reason IN ('isbn', 'metadata')),
+    confidence   REAL NOT NULL,
+    found_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+    PRIMARY KEY (publication, duplicate_of),
+    CHECK (publication <> duplicate_of)
+);
+
+-- A pair is kept once, whichever of the two was imported again
+CREATE UNIQUE INDEX publication_duplicates_pair_idx ON publication_duplicates (
+    least(publication, duplicate_of), greatest(publication, duplicate_of)
+);
+CREATE INDEX publication_duplicates_duplicate_of_idx ON publication_duplicates (duplicate_of);
+
+-- For the publications that share an ISBN with the imported ones
+CREATE INDEX digital_publications_isbns_idx ON digital_publications USING GIN (isbns);
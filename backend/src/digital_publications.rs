@@ -1,9 +1,16 @@
-
 use crate::db::DbPool;
-use crate::models::{DigitalPublication, NewDigitalPublication};
-use crate::schema::digital_publications::dsl::*;
-use diesel::prelude::*;
-use actix_web::{web, HttpResponse, Responder};
+use crate::error::{ApiError, ErrorKind};
+use crate::export::{self, ExportFormat};
+use crate::models::NewDigitalPublication;
+use crate::repository::digital_publications::{self as repository, Filter};
+use crate::repository::{publication_duplicates, raw_records, Page};
+use actix_web::http::header::ContentDisposition;
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use rusmarc_raw::description::CitationStyle;
+use rusmarc_raw::field::Field;
+use rusmarc_raw::formats::{record_writer, Format};
+use serde::Deserialize;
 
 use ::uuid::Uuid;
 
@@ -13,73 +20,210 @@ pub fn config(cfg: &mut web::ServiceConfig, base_path: &str) {
             .route(web::post().to(create))
             .route(web::get().to(get_all)),
     )
+    // Before `{id}`, which would take `search`, `export` and `duplicates` for ids
+    .service(
+        web::resource(format!("{}{}", base_path, "digital_publications/search"))
+            .route(web::get().to(search)),
+    )
+    .service(
+        web::resource(format!("{}{}", base_path, "digital_publications/duplicates"))
+            .route(web::get().to(duplicates)),
+    )
+    .service(
+        web::resource(format!("{}{}", base_path, "digital_publications/export"))
+            .route(web::get().to(export)),
+    )
     .service(
         web::resource(format!("{}{}", base_path, "digital_publications/{id}"))
             .route(web::get().to(get))
             .route(web::put().to(update))
             .route(web::delete().to(delete)),
     )
+    .service(
+        web::resource(format!("{}{}", base_path, "digital_publications/{id}/citation"))
+            .route(web::get().to(citation)),
+    )
+    .service(
+        web::resource(format!("{}{}", base_path, "digital_publications/{id}/marc"))
+            .route(web::get().to(marc)),
+    )
     ;
 }
 
 
+/// `?limit=&offset=` with [`Filter`]; the number of all matching publications is in `X-Total-Count`.
 async fn get_all(
-    pool: web::Data<DbPool>
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+    pool: web::Data<DbPool>,
+    page: web::Query<Page>,
+    filter: web::Query<Filter>,
+) -> Result<HttpResponse, ApiError> {
+    let (pubs, total) = repository::list(&pool, page.into_inner(), filter.into_inner()).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("X-Total-Count", total))
+        .json(pubs))
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+}
+
+/// `?q=&limit=&offset=`, hits ranked best first; the number of all hits is in `X-Total-Count`.
+async fn search(
+    pool: web::Data<DbPool>,
+    query: web::Query<SearchQuery>,
+    page: web::Query<Page>,
+) -> Result<HttpResponse, ApiError> {
+    let query = query.into_inner().q.trim().to_string();
+    if query.is_empty() {
+        return Err(ApiError::bad_request("Search query `q` is empty"));
+    }
+
+    let (hits, total) = repository::search(&pool, query, page.into_inner()).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("X-Total-Count", total))
+        .json(hits))
+}
+
+/// `?limit=&offset=`, pairs of publications that are probably the same
+/// document, found on import, the latest first; the number of all pairs is in
+/// `X-Total-Count`.
+async fn duplicates(
+    pool: web::Data<DbPool>,
+    page: web::Query<Page>,
+) -> Result<HttpResponse, ApiError> {
+    let (pairs, total) = publication_duplicates::list(&pool, page.into_inner()).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("X-Total-Count", total))
+        .json(pairs))
+}
 
-    let pubs = digital_publications
-        .select((
-            uuid,
-            source,
-            name,
-            description, 
-            cover_url, 
-            links
-        ))
-        .load::<DigitalPublication>(&mut conn)
-        .expect("Error loading digital publications");
+#[derive(Deserialize)]
+struct ExportQuery {
+    format: ExportFormat,
+    /// Comma-separated UUIDs of the selected publications
+    ids: Option<String>,
+}
 
-    HttpResponse::Ok().json(pubs)
+/// `?format=csv|json|xlsx|rusmarc|marcxml` with [`Filter`], and `&ids=` to
+/// export only the selected ones of the publications; streamed as a file download.
+async fn export(
+    pool: web::Data<DbPool>,
+    query: web::Query<ExportQuery>,
+    filter: web::Query<Filter>,
+) -> Result<HttpResponse, ApiError> {
+    let ExportQuery { format, ids } = query.into_inner();
+    let ids = ids
+        .map(|ids| {
+            ids.split(',')
+                .map(|id| id.trim().parse::<Uuid>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| ApiError::bad_request(format!("Invalid `ids`: {err}")))
+        })
+        .transpose()?;
+
+    let body = export::stream(&pool, format, filter.into_inner(), ids).await?;
+    let file_name = format!(
+        "publications-{}.{}",
+        Utc::now().format("%Y-%m-%d"),
+        format.extension()
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.media_type())
+        .insert_header(ContentDisposition::attachment(file_name))
+        .streaming(body))
 }
 
 async fn create(
     pool: web::Data<DbPool>,
     publication_data: web::Json<NewDigitalPublication>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+) -> Result<HttpResponse, ApiError> {
+    repository::insert(&pool, publication_data.into_inner()).await?;
 
-    let new_publication = NewDigitalPublication {
-        name:        publication_data.name.clone(),
-        source:      publication_data.source.clone(),
-        description: publication_data.description.clone(),
-        cover_url:   publication_data.cover_url.clone(),
-        links:       publication_data.links.clone(),
-    };
+    Ok(HttpResponse::Created().finish())
+}
+
+async fn get(
+    pool: web::Data<DbPool>,
+    pub_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let publication = repository::find(&pool, pub_id.into_inner())
+        .await?
+        .ok_or_else(|| ApiError::not_found("Digital publication not found"))?;
 
-    diesel::insert_into(digital_publications)
-        .values(&new_publication)
-        .execute(&mut conn)
-        .expect("Error saving new digital publication");
+    Ok(HttpResponse::Ok().json(publication))
+}
 
-    HttpResponse::Created().finish()
+#[derive(Deserialize)]
+struct CitationQuery {
+    /// gost, apa, bibtex, ris or csl-json; gost by default
+    style: Option<String>,
 }
 
-async fn get(
+async fn citation(
+    pool: web::Data<DbPool>,
+    pub_id: web::Path<Uuid>,
+    query: web::Query<CitationQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let style = query.style.as_deref().unwrap_or("gost")
+        .parse::<CitationStyle>()
+        .map_err(ApiError::bad_request)?;
+
+    let publication = repository::find(&pool, pub_id.into_inner())
+        .await?
+        .ok_or_else(|| ApiError::not_found("Digital publication not found"))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(style.media_type())
+        .body(publication.description().cite(style)))
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum MarcFormat {
+    /// The stored record: its 001, 005, source and fields
+    #[default]
+    Json,
+    /// Fields in the text format of the vendors
+    Text,
+}
+
+#[derive(Deserialize)]
+struct MarcQuery {
+    #[serde(default)]
+    format: MarcFormat,
+}
+
+/// Vendor record the publication was imported from, `?format=json|text`.
+async fn marc(
     pool: web::Data<DbPool>,
     pub_id: web::Path<Uuid>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-
-    let publication = digital_publications
-        .filter(uuid.eq(pub_id.into_inner()))
-        .first::<DigitalPublication>(&mut conn)
-        .optional()
-        .expect("Error loading digital publication");
-
-    match publication {
-        Some(publication) => HttpResponse::Ok().json(publication),
-        None => HttpResponse::NotFound().finish(),
+    query: web::Query<MarcQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let record = raw_records::find(&pool, pub_id.into_inner())
+        .await?
+        .ok_or_else(|| ApiError::not_found("Publication has no imported record"))?;
+
+    match query.format {
+        MarcFormat::Json => Ok(HttpResponse::Ok().json(record)),
+        MarcFormat::Text => {
+            let fields = serde_json::from_value::<Vec<Field>>(record.fields).map_err(|err| {
+                ApiError::new(ErrorKind::Internal, format!("Stored record is broken: {err}"))
+            })?;
+            let mut text = Vec::new();
+            let mut writer = record_writer(&mut text, Format::Text);
+            writer.write_record(&fields)?;
+            writer.finish()?;
+            drop(writer);
+
+            Ok(HttpResponse::Ok()
+                .content_type("text/plain; charset=utf-8")
+                .body(text))
+        }
     }
 }
 
@@ -87,35 +231,19 @@ async fn update(
     pool: web::Data<DbPool>,
     pub_id: web::Path<Uuid>,
     publication_data: web::Json<NewDigitalPublication>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-
-    diesel::update(digital_publications
-        .filter(uuid.eq(pub_id.into_inner())))
-        .set((
-            name.eq(&publication_data.name),
-            source.eq(&publication_data.source),
-            description.eq(&publication_data.description),
-            cover_url.eq(&publication_data.cover_url),
-            links.eq(&publication_data.links),
-        ))
-        .execute(&mut conn)
-        .expect("Error updating digital publication");
-
-    HttpResponse::Ok().finish()
+) -> Result<HttpResponse, ApiError> {
+    if !repository::update(&pool, pub_id.into_inner(), publication_data.into_inner()).await? {
+        return Err(ApiError::not_found("Digital publication not found"));
+    }
+    Ok(HttpResponse::Ok().finish())
 }
 
 async fn delete(
     pool: web::Data<DbPool>,
     pub_id: web::Path<Uuid>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-
-    diesel::delete(
-        digital_publications
-            .filter(uuid.eq(pub_id.into_inner()))
-    ).execute(&mut conn)
-        .expect("Error deleting digital publication");
-
-    HttpResponse::NoContent().finish()
+) -> Result<HttpResponse, ApiError> {
+    if !repository::delete(&pool, pub_id.into_inner()).await? {
+        return Err(ApiError::not_found("Digital publication not found"));
+    }
+    Ok(HttpResponse::NoContent().finish())
 }
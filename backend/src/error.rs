@@ -0,0 +1,175 @@
+use std::{fmt, io};
+
+use actix_web::error::BlockingError;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+use diesel::r2d2::PoolError;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use log::error;
+use serde_json::{json, Value};
+
+/// Error of an API handler, answered as
+/// `{"code": "not_found", "message": "...", "details": null}`.
+#[derive(Debug)]
+pub struct ApiError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// Anything that helps the client to fix the request, e.g. the violated constraint.
+    pub details: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 400, the request is malformed or breaks a constraint of the data
+    BadRequest,
+    /// 404
+    NotFound,
+    /// 409, the request clashes with existing data
+    Conflict,
+    /// 503, the database cannot be reached, worth retrying
+    Unavailable,
+    /// 500
+    Internal,
+}
+
+impl ErrorKind {
+    /// Machine-readable `code` of the response.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::BadRequest => "bad_request",
+            Self::NotFound => "not_found",
+            Self::Conflict => "conflict",
+            Self::Unavailable => "unavailable",
+            Self::Internal => "internal",
+        }
+    }
+
+    pub fn status(self) -> StatusCode {
+        match self {
+            Self::BadRequest => StatusCode::BAD_REQUEST,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Conflict => StatusCode::CONFLICT,
+            Self::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl ApiError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::BadRequest, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Conflict, message)
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind.code(), self.message)
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.kind.status()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(json!({
+            "code": self.kind.code(),
+            "message": self.message,
+            "details": self.details,
+        }))
+    }
+}
+
+/// Answers malformed JSON bodies, paths and query strings with [`ApiError`] too.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.app_data(
+        web::JsonConfig::default()
+            .error_handler(|err, _| ApiError::bad_request(err.to_string()).into()),
+    )
+    .app_data(
+        web::PathConfig::default()
+            .error_handler(|err, _| ApiError::bad_request(err.to_string()).into()),
+    )
+    .app_data(
+        web::QueryConfig::default()
+            .error_handler(|err, _| ApiError::bad_request(err.to_string()).into()),
+    );
+}
+
+impl From<DieselError> for ApiError {
+    fn from(err: DieselError) -> Self {
+        match err {
+            DieselError::NotFound => Self::not_found("Not found"),
+            DieselError::DatabaseError(kind, info) => {
+                let kind = match kind {
+                    DatabaseErrorKind::UniqueViolation
+                    | DatabaseErrorKind::ForeignKeyViolation
+                    | DatabaseErrorKind::RestrictViolation
+                    | DatabaseErrorKind::ExclusionViolation
+                    | DatabaseErrorKind::SerializationFailure => ErrorKind::Conflict,
+                    DatabaseErrorKind::NotNullViolation | DatabaseErrorKind::CheckViolation => {
+                        ErrorKind::BadRequest
+                    }
+                    DatabaseErrorKind::ClosedConnection => ErrorKind::Unavailable,
+                    _ => ErrorKind::Internal,
+                };
+                if kind == ErrorKind::Internal {
+                    error!("Database error: {}", info.message());
+                    return Self::new(kind, "Database error");
+                }
+
+                Self::new(kind, info.message()).with_details(json!({
+                    "table": info.table_name(),
+                    "column": info.column_name(),
+                    "constraint": info.constraint_name(),
+                }))
+            }
+            err => {
+                error!("Database error: {err}");
+                Self::new(ErrorKind::Internal, "Database error")
+            }
+        }
+    }
+}
+
+impl From<PoolError> for ApiError {
+    fn from(err: PoolError) -> Self {
+        error!("Couldn't get db connection from pool: {err}");
+        Self::new(ErrorKind::Unavailable, "Database is unavailable")
+    }
+}
+
+impl From<BlockingError> for ApiError {
+    fn from(_: BlockingError) -> Self {
+        Self::new(ErrorKind::Internal, "Blocking task was cancelled")
+    }
+}
+
+impl From<io::Error> for ApiError {
+    fn from(err: io::Error) -> Self {
+        error!("I/O error: {err}");
+        Self::new(ErrorKind::Internal, "Couldn't save or read a file")
+    }
+}
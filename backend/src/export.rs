@@ -0,0 +1,546 @@
+//! Publications written as spreadsheets, JSON or RUSMARC records for
+//! `GET /digital_publications/export`.
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+use actix_web::rt::task;
+use actix_web::web::Bytes;
+use futures_util::Stream;
+use log::{error, warn};
+use rusmarc_raw::codes::LanguageCode;
+use rusmarc_raw::description::{Name, Role};
+use rusmarc_raw::field::{Field, FieldData, Subfield};
+use rusmarc_raw::formats::{record_writer, Format, RecordWriter};
+use rusmarc_raw::identifiers::Isbn;
+use rusmarc_raw::typed_record::{
+    CorporateName, Field001RecordId, Field010Isbn, Field101Language, Field200Header,
+    Field205Edition, Field210PublicationDistribution, Field215PhysicalDescription, Field330Summary,
+    Field700PersonPrimary, Field701PersonAlternative, Field702PersonSecondary,
+    Field710OrganizationPrimary, Field711OrganizationAlternative, Field712OrganizationSecondary,
+    PersonalName, TypedRecord, TypedRecordBuilder,
+};
+use rust_xlsxwriter::{Format as CellFormat, Workbook, Worksheet, XlsxError};
+use serde::Deserialize;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::{ApiError, ErrorKind};
+use crate::models::{author_role, Author, DigitalPublication};
+use crate::repository::digital_publications::{self as repository, Filter};
+
+/// Size of the chunks of the response body.
+const CHUNK: usize = 64 * 1024;
+/// Chunks written ahead of the client before the export waits for it.
+const CHUNKS_AHEAD: usize = 4;
+/// Longest text Excel keeps in a cell.
+const MAX_CELL_CHARS: usize = 32_767;
+/// Longest text of one 330, in bytes: an ISO 2709 field takes at most 9999,
+/// with its indicators, subfield marker and terminator.
+const MAX_SUMMARY_BYTES: usize = 9_000;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// UTF-8 with a byte order mark, so that Excel reads Cyrillic right
+    Csv,
+    /// Array of publications as `GET /digital_publications` returns them
+    Json,
+    Xlsx,
+    /// ISO 2709 records
+    Rusmarc,
+    MarcXml,
+}
+
+impl ExportFormat {
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Json => "application/json",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Rusmarc => "application/marc",
+            Self::MarcXml => "application/marcxml+xml",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Xlsx => "xlsx",
+            Self::Rusmarc => "iso",
+            Self::MarcXml => "xml",
+        }
+    }
+}
+
+/// Destination for publications in some format, like [`RecordWriter`] for records.
+pub trait PublicationWriter {
+    fn write(&mut self, publication: &DigitalPublication) -> io::Result<()>;
+
+    /// Writes whatever the format needs after the last publication and flushes the output.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+pub fn publication_writer<'a>(
+    output: impl Write + 'a,
+    format: ExportFormat,
+) -> io::Result<Box<dyn PublicationWriter + 'a>> {
+    Ok(match format {
+        ExportFormat::Csv => Box::new(CsvWriter::new(output)?),
+        ExportFormat::Json => Box::new(JsonWriter::new(output)),
+        ExportFormat::Xlsx => Box::new(XlsxWriter::new(output).map_err(io::Error::other)?),
+        ExportFormat::Rusmarc => Box::new(MarcWriter(record_writer(output, Format::Iso2709))),
+        ExportFormat::MarcXml => Box::new(MarcWriter(record_writer(output, Format::MarcXml))),
+    })
+}
+
+const COLUMNS: [&str; 15] = [
+    "uuid",
+    "source",
+    "record_id",
+    "name",
+    "authors",
+    "description",
+    "isbns",
+    "year",
+    "publisher",
+    "language",
+    "edition",
+    "pages",
+    "cover_url",
+    "links",
+    "created_at",
+];
+
+fn columns(publication: &DigitalPublication) -> [String; 15] {
+    [
+        publication.uuid.to_string(),
+        publication
+            .source
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        publication.record_id.clone().unwrap_or_default(),
+        publication.name.clone(),
+        publication
+            .authors
+            .iter()
+            .map(|author| author.name.as_str())
+            .collect::<Vec<_>>()
+            .join("; "),
+        publication.description.clone(),
+        publication
+            .isbns
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" "),
+        publication
+            .year
+            .map(|year| year.to_string())
+            .unwrap_or_default(),
+        publication.publisher.clone().unwrap_or_default(),
+        publication.language.clone().unwrap_or_default(),
+        publication.edition.clone().unwrap_or_default(),
+        publication
+            .pages
+            .map(|pages| pages.to_string())
+            .unwrap_or_default(),
+        publication.cover_url.clone(),
+        publication
+            .links
+            .iter()
+            .map(|link| link.url.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        publication.created_at.to_rfc3339(),
+    ]
+}
+
+struct CsvWriter<W: Write> {
+    output: csv::Writer<W>,
+}
+
+impl<W: Write> CsvWriter<W> {
+    fn new(mut output: W) -> io::Result<Self> {
+        output.write_all("\u{feff}".as_bytes())?;
+        let mut output = csv::Writer::from_writer(output);
+        output.write_record(COLUMNS)?;
+        Ok(Self { output })
+    }
+}
+
+impl<W: Write> PublicationWriter for CsvWriter<W> {
+    fn write(&mut self, publication: &DigitalPublication) -> io::Result<()> {
+        Ok(self.output.write_record(columns(publication))?)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+struct JsonWriter<W: Write> {
+    output: W,
+    empty: bool,
+}
+
+impl<W: Write> JsonWriter<W> {
+    fn new(output: W) -> Self {
+        Self {
+            output,
+            empty: true,
+        }
+    }
+}
+
+impl<W: Write> PublicationWriter for JsonWriter<W> {
+    fn write(&mut self, publication: &DigitalPublication) -> io::Result<()> {
+        self.output
+            .write_all(if self.empty { b"[" } else { b"," })?;
+        self.empty = false;
+        serde_json::to_writer(&mut self.output, publication)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.output
+            .write_all(if self.empty { b"[]" } else { b"]" })?;
+        self.output.flush()
+    }
+}
+
+/// Rows are kept in temporary files until the workbook is assembled, also in
+/// a temporary file, and copied to the output.
+struct XlsxWriter<W: Write> {
+    workbook: Workbook,
+    worksheet: Worksheet,
+    row: u32,
+    output: W,
+}
+
+impl<W: Write> XlsxWriter<W> {
+    fn new(output: W) -> Result<Self, XlsxError> {
+        let mut workbook = Workbook::new();
+        let mut worksheet = workbook.new_worksheet_with_constant_memory();
+        worksheet.set_name("Publications")?;
+        worksheet.write_row_with_format(0, 0, COLUMNS, &CellFormat::new().set_bold())?;
+        Ok(Self {
+            workbook,
+            worksheet,
+            row: 1,
+            output,
+        })
+    }
+}
+
+impl<W: Write> PublicationWriter for XlsxWriter<W> {
+    fn write(&mut self, publication: &DigitalPublication) -> io::Result<()> {
+        let cells =
+            columns(publication).map(|cell| match cell.char_indices().nth(MAX_CELL_CHARS) {
+                Some((end, _)) => cell[..end].to_string(),
+                None => cell,
+            });
+        self.worksheet
+            .write_row(self.row, 0, cells)
+            .map_err(io::Error::other)?;
+        self.row += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let worksheet = std::mem::take(&mut self.worksheet);
+        self.workbook.push_worksheet(worksheet);
+
+        let mut file = tempfile::tempfile()?;
+        self.workbook
+            .save_to_writer(&mut file)
+            .map_err(io::Error::other)?;
+        file.seek(SeekFrom::Start(0))?;
+        io::copy(&mut file, &mut self.output)?;
+        self.output.flush()
+    }
+}
+
+struct MarcWriter<'a>(Box<dyn RecordWriter + 'a>);
+
+impl PublicationWriter for MarcWriter<'_> {
+    fn write(&mut self, publication: &DigitalPublication) -> io::Result<()> {
+        match self.0.write_record(&record(publication)) {
+            // Nothing of the record is written then, the export goes on without it
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                warn!("Publication {} is not exported: {err}", publication.uuid);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.0.finish()
+    }
+}
+
+/// The reverse of [`crate::import::publication`]: 001, 010, 101, 200 $a,
+/// 205, 210, 215, 330 and 7xx, 856 $u for the links and the vendor 953 $a
+/// for the cover, so that an exported dump imports back into the same
+/// publications.
+pub fn record(publication: &DigitalPublication) -> Vec<Field> {
+    let id = publication
+        .record_id
+        .clone()
+        .unwrap_or_else(|| publication.uuid.to_string());
+    let mut record = TypedRecord::builder()
+        .field(Field001RecordId::from(id))
+        .field(Field200Header {
+            main_title: Some(publication.name.clone()),
+            ..Default::default()
+        });
+    // Hyphenated, as vendors write them
+    let isbns = publication.isbns.iter().flatten();
+    for isbn in isbns.filter_map(|isbn| isbn.parse::<Isbn>().ok()) {
+        record = record.field(Field010Isbn {
+            isbn: isbn.to_string(),
+            ..Default::default()
+        });
+    }
+    if let Some(language) = &publication.language {
+        record = record.field(Field101Language {
+            language_text: vec![LanguageCode::from(language.clone())],
+            ..Default::default()
+        });
+    }
+    if publication.edition.is_some() {
+        record = record.field(Field205Edition {
+            edition: publication.edition.clone(),
+            ..Default::default()
+        });
+    }
+    if publication.publisher.is_some() || publication.year.is_some() {
+        record = record.field(Field210PublicationDistribution {
+            publisher: publication.publisher.iter().cloned().collect(),
+            date: publication.year.map(|year| year.to_string()),
+            ..Default::default()
+        });
+    }
+    if let Some(pages) = publication.pages {
+        record = record.field(Field215PhysicalDescription {
+            extent: vec![format!("{pages} с.")],
+            ..Default::default()
+        });
+    }
+    for text in split_summary(&publication.description, MAX_SUMMARY_BYTES) {
+        record = record.field(Field330Summary {
+            text: text.to_string(),
+        });
+    }
+    record = add_authors(record, &publication.authors);
+    let mut record = record.build().to_record();
+
+    let url_field = |number, marker, url: &str| Field {
+        number,
+        data: FieldData::Subfields {
+            subfields: vec![Subfield {
+                marker,
+                text: url.to_string(),
+            }],
+        },
+    };
+    record.extend(
+        publication
+            .links
+            .iter()
+            .map(|link| url_field(856, 'U', &link.url)),
+    );
+    if !publication.cover_url.is_empty() {
+        record.push(url_field(953, 'A', &publication.cover_url));
+    }
+    record
+}
+
+/// Parts of `summary` of at most `max_bytes` each, for repeated 330s, split
+/// between words where there are any. Nothing for an empty summary.
+fn split_summary(summary: &str, max_bytes: usize) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = summary.trim();
+    while rest.len() > max_bytes {
+        let mut end = max_bytes;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (part, _) = rest.split_at(end);
+        let end = match part.rfind(char::is_whitespace) {
+            Some(space) if space > 0 => space,
+            _ => end,
+        };
+        let (part, tail) = rest.split_at(end);
+        parts.push(part.trim_end());
+        rest = tail.trim_start();
+    }
+    if !rest.is_empty() {
+        parts.push(rest);
+    }
+    parts
+}
+
+/// 700 or 710 for the first author, 701 or 711 for the other authors, and 702
+/// or 712 with the relator code for everyone else.
+fn add_authors(mut record: TypedRecordBuilder, authors: &[Author]) -> TypedRecordBuilder {
+    let mut first = true;
+    for author in authors {
+        let role = author_role::to_role(&author.role);
+        let relator_codes = role
+            .relator_code()
+            .filter(|_| role != Role::Author)
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+        let primary = role == Role::Author && std::mem::take(&mut first);
+
+        record = match author.to_name() {
+            Name::Person { family, given } => {
+                let name = PersonalName {
+                    entry_element: family,
+                    rest_of_name: given,
+                    relator_codes,
+                    ..Default::default()
+                };
+                match role {
+                    Role::Author if primary => record.field(Field700PersonPrimary { name }),
+                    Role::Author => record.field(Field701PersonAlternative { name }),
+                    _ => record.field(Field702PersonSecondary { name }),
+                }
+            }
+            Name::Organization(entry_element) => {
+                let name = CorporateName {
+                    entry_element,
+                    relator_codes,
+                    ..Default::default()
+                };
+                match role {
+                    Role::Author if primary => record.field(Field710OrganizationPrimary { name }),
+                    Role::Author => record.field(Field711OrganizationAlternative { name }),
+                    _ => record.field(Field712OrganizationSecondary { name }),
+                }
+            }
+        };
+    }
+    record
+}
+
+/// Body of the response, sent as [`stream`] writes it.
+struct ChunkWriter {
+    buffer: Vec<u8>,
+    chunks: mpsc::Sender<io::Result<Bytes>>,
+}
+
+impl ChunkWriter {
+    fn send(&mut self, chunk: io::Result<Bytes>) -> io::Result<()> {
+        self.chunks
+            .blocking_send(chunk)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Client is gone"))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(data);
+        if self.buffer.len() >= CHUNK {
+            self.flush()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = Bytes::from(std::mem::take(&mut self.buffer));
+        self.send(Ok(chunk))
+    }
+}
+
+/// Publications that pass the filter, or the ones in `ids` among them, as the
+/// body of a response. Fails before the first byte if the query does;
+/// later errors break off the body, so the client sees it incomplete.
+pub async fn stream(
+    pool: &DbPool,
+    format: ExportFormat,
+    filter: Filter,
+    ids: Option<Vec<Uuid>>,
+) -> Result<impl Stream<Item = io::Result<Bytes>>, ApiError> {
+    let pool = pool.clone();
+    let (chunks, mut body) = mpsc::channel(CHUNKS_AHEAD);
+    let (started, start) = oneshot::channel::<Result<(), ApiError>>();
+
+    // Runs on its own as long as the client reads
+    task::spawn_blocking(move || {
+        let mut output = ChunkWriter {
+            buffer: Vec::with_capacity(CHUNK),
+            chunks,
+        };
+        let mut conn = match pool.get() {
+            Ok(conn) => conn,
+            Err(err) => {
+                let _ = started.send(Err(err.into()));
+                return;
+            }
+        };
+        let publications = match repository::export(&mut conn, &filter, ids) {
+            Ok(publications) => publications,
+            Err(err) => {
+                let _ = started.send(Err(err.into()));
+                return;
+            }
+        };
+        let _ = started.send(Ok(()));
+
+        let result = publication_writer(&mut output, format).and_then(|mut writer| {
+            for publication in publications {
+                writer.write(&publication.map_err(io::Error::other)?)?;
+            }
+            writer.finish()
+        });
+        let result = result.and_then(|()| output.flush());
+        // Nothing to report if the client went away
+        if let Err(err) = result {
+            if !output.chunks.is_closed() {
+                error!("Export failed: {err}");
+                let _ = output.send(Err(err));
+            }
+        }
+    });
+
+    start
+        .await
+        .map_err(|_| ApiError::new(ErrorKind::Internal, "Export was cancelled"))??;
+    Ok(futures_util::stream::poll_fn(move |cx| body.poll_recv(cx)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_summary;
+
+    #[test]
+    fn split_summary_between_words() {
+        let summary = "Краткое содержание учебника";
+        let parts = split_summary(summary, 20);
+        assert_eq!(parts, ["Краткое", "содержание", "учебника"]);
+        assert_eq!(parts.join(" "), summary);
+    }
+
+    #[test]
+    fn split_summary_without_spaces() {
+        // Letters are two bytes, the parts end before the one that doesn't fit
+        assert_eq!(split_summary("Краткое", 5), ["Кр", "ат", "ко", "е"]);
+        assert_eq!(split_summary("abcdefg", 3), ["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn split_summary_empty() {
+        assert!(split_summary("", 10).is_empty());
+        assert!(split_summary("   ", 10).is_empty());
+        assert_eq!(split_summary("Кратко", 100), ["Кратко"]);
+    }
+}
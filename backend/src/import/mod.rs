@@ -0,0 +1,115 @@
+//! Publications from the RUSMARC dumps of the vendors, imported in the
+//! background by [`worker`].
+
+pub mod worker;
+
+use rusmarc_raw::description::Description;
+use rusmarc_raw::field::Field;
+use rusmarc_raw::formats::Format;
+use rusmarc_raw::typed_record::{Field330Summary, TypedRecord};
+use uuid::Uuid;
+
+use crate::models::{Author, NewDigitalPublication, NewPublicationLink, NewRawRecord};
+
+/// Vendor fields with the cover image, `(field, subfield)`, in order of preference.
+const COVER_SUBFIELDS: &[(u128, char)] = &[(953, 'a'), (953, 'i')];
+/// Fields with the pages of the document: 856 and the vendor 951.
+const LINK_SUBFIELDS: &[(u128, char)] = &[(856, 'u'), (951, 'i')];
+
+/// Format of a dump by its first character.
+pub fn guess_format(data: &[u8]) -> Format {
+    match data.iter().find(|byte| !byte.is_ascii_whitespace()) {
+        Some(b'<') => Format::MarcXml,
+        Some(b'[') => Format::Json,
+        Some(byte) if byte.is_ascii_digit() => Format::Iso2709,
+        _ => Format::Text,
+    }
+}
+
+/// 200 $a : $e as the name, the 330s as the description, vendor 9xx for
+/// the cover and links, the rest of [`Description`] for the bibliographic
+/// fields and the authors. Fails without 001 or 200 $a, with the record ID
+/// if known.
+pub fn publication(
+    record: &[Field],
+    source: Uuid,
+) -> Result<NewDigitalPublication, (Option<String>, String)> {
+    let urls = |subfields: &[(u128, char)]| -> Vec<String> {
+        subfields
+            .iter()
+            .flat_map(|&(number, marker)| {
+                record
+                    .iter()
+                    .filter(move |field| field.number == number)
+                    .flat_map(move |field| field.data.get_subfields(marker))
+            })
+            .map(str::trim)
+            .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+            .map(str::to_string)
+            .collect()
+    };
+    let cover_url = urls(COVER_SUBFIELDS).into_iter().next().unwrap_or_default();
+    let mut links = urls(LINK_SUBFIELDS);
+    links.dedup();
+    let links = links
+        .into_iter()
+        .map(NewPublicationLink::from_url)
+        .collect();
+
+    let (typed, _errors) = TypedRecord::parse(record.iter().cloned());
+    let description = Description::from(&typed);
+
+    let record_id = description.id.clone().filter(|id| !id.trim().is_empty());
+    let Some(record_id) = record_id else {
+        return Err((None, "No record ID (001)".to_string()));
+    };
+    let Some(name) = description.full_title() else {
+        return Err((Some(record_id), "No title (200 $a)".to_string()));
+    };
+
+    Ok(NewDigitalPublication {
+        name,
+        source,
+        // Long summaries are split into several, as by the export
+        description: typed
+            .get_fields::<Field330Summary>()
+            .map(|summary| summary.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        cover_url,
+        links,
+        record_id: Some(record_id),
+        isbns: description
+            .isbns
+            .iter()
+            .map(|isbn| isbn.compact().to_string())
+            .collect(),
+        year: description.year(),
+        publisher: description.publisher.clone(),
+        language: description.languages.first().cloned(),
+        edition: description.edition.clone(),
+        pages: description
+            .page_count()
+            .and_then(|pages| i32::try_from(pages).ok())
+            .filter(|&pages| pages > 0),
+        authors: description
+            .creators
+            .iter()
+            .map(|creator| Author::from_name(&creator.name, creator.role))
+            .collect(),
+    })
+}
+
+/// The whole record to keep with the publication, with 005 as it is.
+pub fn raw_record(record: &[Field]) -> NewRawRecord {
+    let version = record
+        .iter()
+        .find(|field| field.number == 5)
+        .and_then(|field| field.data.as_singular_text())
+        .map(|version| version.trim().to_string());
+
+    NewRawRecord {
+        version,
+        fields: serde_json::to_value(record).expect("Fields are always serializable"),
+    }
+}
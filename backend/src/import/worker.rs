@@ -0,0 +1,193 @@
+//! Thread that imports the uploaded dumps, one job at a time, in batches.
+
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use log::{error, info, warn};
+use rusmarc_raw::formats::{read_records, Format};
+use rusmarc_raw::query::Query;
+use rusmarc_raw::record::{record_remove_errors, ErrorlessRecord};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::models::{ImportJob, NewImportJobError};
+use crate::repository::import_jobs as repository;
+
+/// Records saved in one transaction. Cancellation takes effect between batches.
+const BATCH: usize = 500;
+/// How long the worker sleeps without a wake-up before it looks at the queue again.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Where the uploads wait for the worker, and how to wake it up.
+#[derive(Clone)]
+pub struct ImportQueue {
+    dir: PathBuf,
+    wake: Sender<()>,
+}
+
+impl ImportQueue {
+    pub fn dump_path(&self, job: Uuid) -> PathBuf {
+        self.dir.join(job.to_string())
+    }
+
+    /// Tells the worker that a job was queued.
+    pub fn notify(&self) {
+        // Only fails if the worker is gone, and then there is no one to tell
+        let _ = self.wake.send(());
+    }
+}
+
+/// Starts the worker. Uploads are kept in `IMPORT_DIR`, `./imports` by default.
+pub fn spawn(pool: DbPool) -> ImportQueue {
+    let dir = PathBuf::from(env::var("IMPORT_DIR").unwrap_or_else(|_| "imports".to_string()));
+    fs::create_dir_all(&dir).expect("Failed to create the import directory");
+
+    let (wake, wake_receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("import-worker".to_string())
+        .spawn(move || run(pool, wake_receiver))
+        .expect("Failed to start the import worker");
+
+    ImportQueue { dir, wake }
+}
+
+type WorkerError = Box<dyn Error + Send + Sync>;
+
+fn run(pool: DbPool, wake: Receiver<()>) {
+    match pool
+        .get()
+        .map_err(WorkerError::from)
+        .and_then(|mut conn| Ok(repository::requeue_running(&mut conn)?))
+    {
+        Ok(0) => {}
+        Ok(count) => info!("Resuming {count} import jobs"),
+        Err(err) => error!("Couldn't resume import jobs: {err}"),
+    }
+
+    loop {
+        let job = pool
+            .get()
+            .map_err(WorkerError::from)
+            .and_then(|mut conn| Ok(repository::claim_next(&mut conn)?));
+        match job {
+            Ok(Some(job)) => {
+                let id = job.uuid;
+                info!("Import job {id} started");
+                let result = process(&pool, &job);
+                if let Err(err) = &result {
+                    error!("Import job {id} failed: {err}");
+                }
+                let finished = pool.get().map_err(WorkerError::from).and_then(|mut conn| {
+                    Ok(repository::finish(
+                        &mut conn,
+                        id,
+                        result.err().map(|err| err.to_string()),
+                    )?)
+                });
+                if let Err(err) = finished {
+                    error!("Couldn't finish import job {id}: {err}");
+                }
+            }
+            Ok(None) => {
+                if let Err(RecvTimeoutError::Disconnected) = wake.recv_timeout(POLL_INTERVAL) {
+                    return;
+                }
+            }
+            Err(err) => {
+                error!("Couldn't take the next import job: {err}");
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Imports the records of the job after the processed ones until the dump
+/// ends or the job is cancelled, skipping the ones that don't match its
+/// filter. The dump is removed when all of it is done.
+fn process(pool: &DbPool, job: &ImportJob) -> Result<(), WorkerError> {
+    let mut conn = pool.get()?;
+    let format = job.format.parse::<Format>()?;
+    let filter = job.filter.as_deref().map(Query::parse).transpose()?;
+    let wanted =
+        |record: &ErrorlessRecord| filter.as_ref().is_none_or(|filter| filter.matches(record));
+    let read =
+        || -> io::Result<_> { Ok(read_records(BufReader::new(File::open(&job.path)?), format)) };
+
+    if job.total.is_none() {
+        let total = read()?.count();
+        repository::set_total(&mut conn, job.uuid, total as i32)?;
+    }
+
+    let mut records = read()?.enumerate().skip(job.processed as usize);
+    loop {
+        let mut publications = Vec::new();
+        let mut errors = Vec::new();
+        let mut processed = 0;
+        let mut skipped = 0;
+        // Error that ends the dump, raised once the records before it are saved
+        let mut broken = None;
+
+        for (i, record) in records.by_ref().take(BATCH) {
+            let position = i as i32 + 1;
+            let mut fail = |record_id, error| {
+                errors.push(NewImportJobError {
+                    job: job.uuid,
+                    position,
+                    record_id,
+                    error,
+                })
+            };
+            match record {
+                Ok(record) => match record_remove_errors(record) {
+                    record if !wanted(&record) => skipped += 1,
+                    record => match super::publication(&record, job.source) {
+                        Ok(publication) => {
+                            publications.push((position, publication, super::raw_record(&record)))
+                        }
+                        Err((record_id, error)) => fail(record_id, error),
+                    },
+                },
+                // Broken record, the next one is fine
+                Err(err) if err.kind() == io::ErrorKind::InvalidData => fail(None, err.to_string()),
+                Err(err) => {
+                    broken = Some(err);
+                    break;
+                }
+            }
+            processed += 1;
+        }
+
+        if processed > 0 {
+            let saved = repository::save_batch(
+                &mut conn,
+                job.uuid,
+                processed,
+                skipped,
+                &publications,
+                &errors,
+            )?;
+            if !saved {
+                info!("Import job {} cancelled", job.uuid);
+                return Ok(());
+            }
+        }
+        if let Some(err) = broken {
+            return Err(err.into());
+        }
+        if processed == 0 {
+            break;
+        }
+    }
+
+    info!("Import job {} done", job.uuid);
+    if let Err(err) = fs::remove_file(&job.path) {
+        warn!("Couldn't remove the dump of import job {}: {err}", job.uuid);
+    }
+    Ok(())
+}
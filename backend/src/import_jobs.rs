@@ -0,0 +1,88 @@
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::import::worker::ImportQueue;
+use crate::models::{ImportJob, ImportJobError};
+use crate::repository::import_jobs::{self as repository, CANCELLABLE, RESUMABLE};
+use crate::models::job_status::{CANCELLED, QUEUED};
+use actix_web::{web, HttpResponse};
+use serde::Serialize;
+
+use ::uuid::Uuid;
+
+pub fn config(cfg: &mut web::ServiceConfig, base_path: &str) {
+    cfg.service(
+        web::resource(format!("{}{}", base_path, "import_jobs/{id}"))
+            .route(web::get().to(get)),
+    )
+    .service(
+        web::resource(format!("{}{}", base_path, "import_jobs/{id}/cancel"))
+            .route(web::post().to(cancel)),
+    )
+    .service(
+        web::resource(format!("{}{}", base_path, "import_jobs/{id}/resume"))
+            .route(web::post().to(resume)),
+    )
+    ;
+}
+
+#[derive(Serialize)]
+struct JobReport {
+    #[serde(flatten)]
+    job: ImportJob,
+    errors: Vec<ImportJobError>,
+}
+
+/// Status, progress and the records that failed.
+async fn get(
+    pool: web::Data<DbPool>,
+    job_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let job_id = job_id.into_inner();
+    let job = repository::find(&pool, job_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Import job not found"))?;
+    let errors = repository::errors(&pool, job_id).await?;
+
+    Ok(HttpResponse::Ok().json(JobReport { job, errors }))
+}
+
+/// Stops a queued or running job after the current batch. Publications
+/// imported so far are kept.
+async fn cancel(
+    pool: web::Data<DbPool>,
+    job_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let job = transition(&pool, job_id.into_inner(), CANCELLABLE, CANCELLED).await?;
+
+    Ok(HttpResponse::Ok().json(job))
+}
+
+/// Queues a cancelled or failed job again, to continue after its processed records.
+async fn resume(
+    pool: web::Data<DbPool>,
+    queue: web::Data<ImportQueue>,
+    job_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let job = transition(&pool, job_id.into_inner(), RESUMABLE, QUEUED).await?;
+    queue.notify();
+
+    Ok(HttpResponse::Ok().json(job))
+}
+
+async fn transition(
+    pool: &DbPool,
+    job_id: Uuid,
+    from: &'static [&'static str],
+    to: &'static str,
+) -> Result<ImportJob, ApiError> {
+    let job = repository::find(pool, job_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Import job not found"))?;
+    if !repository::transition(pool, job_id, from, to).await? {
+        return Err(ApiError::conflict(format!("Import job is {}", job.status)));
+    }
+
+    repository::find(pool, job_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Import job not found"))
+}
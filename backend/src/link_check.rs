@@ -0,0 +1,230 @@
+//! Whether the links of the publications still work: rechecked in the
+//! background by the thread of [`spawn`], and checked on request with
+//! [`check_now`].
+
+use std::env;
+use std::error::Error;
+use std::io;
+use std::net::IpAddr;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+use actix_web::web;
+use chrono::{TimeDelta, Utc};
+use log::{error, info};
+use ureq::config::Config;
+use ureq::unversioned::resolver::{DefaultResolver, ResolvedSocketAddrs, Resolver};
+use ureq::unversioned::transport::{DefaultConnector, NextTimeout};
+use ureq::Agent;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::models::{LinkCheck, PublicationLink};
+use crate::repository::{publication_links as repository, run};
+
+/// Links checked between two looks at what is due.
+const BATCH: i64 = 100;
+/// How long the checker sleeps when no link is due.
+const POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// For the whole check, redirects included.
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+fn agent() -> &'static Agent {
+    static AGENT: OnceLock<Agent> = OnceLock::new();
+    AGENT.get_or_init(|| {
+        let config = Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            // Statuses are the result, not errors
+            .http_status_as_error(false)
+            .user_agent(concat!("bookify/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Agent::with_parts(
+            config,
+            DefaultConnector::default(),
+            PublicResolver::default(),
+        )
+    })
+}
+
+/// Resolves hosts to their public addresses only, so that links, or the
+/// redirects of their servers, can't make the checker request the server's
+/// own network.
+#[derive(Debug, Default)]
+struct PublicResolver(DefaultResolver);
+
+impl Resolver for PublicResolver {
+    fn resolve(
+        &self,
+        uri: &ureq::http::Uri,
+        config: &Config,
+        timeout: NextTimeout,
+    ) -> Result<ResolvedSocketAddrs, ureq::Error> {
+        let mut public = self.empty();
+        for addr in self.0.resolve(uri, config, timeout)?.iter() {
+            if is_public(addr.ip()) {
+                public.push(*addr);
+            }
+        }
+        if public.is_empty() {
+            return Err(ureq::Error::Io(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "host is not on a public address",
+            )));
+        }
+        Ok(public)
+    }
+}
+
+/// Not loopback, private, link-local or otherwise reserved.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                // Shared address space of carrier-grade NAT, 100.64.0.0/10
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_unspecified()
+                    || ip.is_loopback()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Requests the URL, with `HEAD` unless the server only answers `GET`. Blocks
+/// for up to [`TIMEOUT`].
+pub fn check(url: &str) -> LinkCheck {
+    let agent = agent();
+    let response = match agent.head(url).call() {
+        Ok(response) if matches!(response.status().as_u16(), 405 | 501) => agent.get(url).call(),
+        response => response,
+    };
+    let (status, error) = match response {
+        Ok(response) => (Some(i32::from(response.status().as_u16())), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+    LinkCheck {
+        checked_at: Utc::now(),
+        status,
+        error,
+    }
+}
+
+/// Checks the link of the publication and saves the result, or returns
+/// `None` without the link.
+pub async fn check_now(
+    pool: &DbPool,
+    publication: Uuid,
+    id: Uuid,
+) -> Result<Option<PublicationLink>, ApiError> {
+    let Some(link) = repository::find(pool, publication, id).await? else {
+        return Ok(None);
+    };
+    let result = web::block(move || check(&link.url)).await?;
+    run(pool, move |conn| {
+        Ok(repository::save_check(conn, id, &result)?)
+    })
+    .await
+}
+
+/// Starts the checker, which rechecks every link `LINK_RECHECK_DAYS` days
+/// after the last check, 7 by default. `0` turns it off.
+pub fn spawn(pool: DbPool) {
+    let days = env::var("LINK_RECHECK_DAYS")
+        .ok()
+        .map(|days| {
+            days.parse::<i64>()
+                .expect("LINK_RECHECK_DAYS must be a number")
+        })
+        .unwrap_or(7);
+    if days <= 0 {
+        info!("Link checker is off");
+        return;
+    }
+
+    thread::Builder::new()
+        .name("link-checker".to_string())
+        .spawn(move || run_checker(pool, TimeDelta::days(days)))
+        .expect("Failed to start the link checker");
+}
+
+type CheckerError = Box<dyn Error + Send + Sync>;
+
+fn run_checker(pool: DbPool, recheck_after: TimeDelta) {
+    loop {
+        match check_due(&pool, recheck_after) {
+            Ok(0) => thread::sleep(POLL_INTERVAL),
+            Ok(_) => {}
+            Err(err) => {
+                error!("Couldn't check the links: {err}");
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Checks a batch of the links due, one by one, returns how many there were.
+/// The connection is only taken to save a result, not while a server answers.
+fn check_due(pool: &DbPool, recheck_after: TimeDelta) -> Result<usize, CheckerError> {
+    let due = repository::due(&mut *pool.get()?, Utc::now() - recheck_after, BATCH)?;
+    for (id, url) in &due {
+        let result = check(url);
+        repository::save_check(&mut *pool.get()?, *id, &result)?;
+    }
+    Ok(due.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_public;
+
+    fn public(ip: &str) -> bool {
+        is_public(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn internal_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "100.64.0.1",
+            "100.127.255.254",
+            "169.254.169.254",
+            "0.0.0.0",
+            "::1",
+            "::ffff:127.0.0.1",
+            "fc00::1",
+            "fd12:3456::1",
+            "fe80::1",
+        ] {
+            assert!(!public(ip), "{ip}");
+        }
+    }
+
+    #[test]
+    fn public_addresses_are_public() {
+        for ip in [
+            "93.184.215.14",
+            "100.128.0.1",
+            "::ffff:93.184.215.14",
+            "2a00:1450:4010::65",
+        ] {
+            assert!(public(ip), "{ip}");
+        }
+    }
+}
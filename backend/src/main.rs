@@ -1,8 +1,15 @@
 pub mod schema;
 pub mod models;
 pub mod db;
+pub mod error;
+pub mod export;
+pub mod import;
+pub mod link_check;
+pub mod repository;
 
 pub mod digital_publications;
+pub mod import_jobs;
+pub mod publication_links;
 pub mod sources;
 
 use actix_web::middleware::Logger;
@@ -20,11 +27,17 @@ async fn main() -> std::io::Result<()> {
     info!("Program starts.");
 
     let pool = db::establish_connection();
+    let import_queue = import::worker::spawn(pool.clone());
+    link_check::spawn(pool.clone());
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(import_queue.clone()))
+            .configure(error::config)
             .configure(|cfg| digital_publications::config(cfg, "/api/v1/"))
+            .configure(|cfg| publication_links::config(cfg, "/api/v1/"))
             .configure(|cfg| sources::config(cfg, "/api/v1/"))
+            .configure(|cfg| import_jobs::config(cfg, "/api/v1/"))
             .service(actix_files::Files::new("/", "./static").show_files_listing())
             .wrap(Logger::default())
     })
@@ -1,8 +1,24 @@
-use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserializer, Serialize, Deserialize};
+use serde_json::Value;
 use uuid::Uuid;
 
-use diesel::{Insertable, Queryable};
+use diesel::{AsChangeset, Insertable, Queryable, QueryableByName, Selectable};
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
+use diesel::pg::{Pg, PgValue};
+use diesel::sql_types::{Float, Jsonb, Text};
+use rusmarc_raw::description::{Description, Name, Role};
+use rusmarc_raw::identifiers::Isbn;
+use ureq::http::Uri;
 use crate::schema::digital_publications;
+use crate::schema::import_job_errors;
+use crate::schema::import_jobs;
+use crate::schema::publication_duplicates;
+use crate::schema::publication_links;
+use crate::schema::raw_records;
 use crate::schema::sources;
 
 
@@ -19,7 +35,8 @@ pub struct NewSource {
 }
 
 
-#[derive(Queryable, Serialize)]
+#[derive(Queryable, QueryableByName, Selectable, Serialize)]
+#[diesel(table_name = digital_publications, check_for_backend(Pg))]
 pub struct DigitalPublication {
     pub uuid: Uuid,
     pub source: Option<Uuid>,
@@ -27,15 +44,459 @@ pub struct DigitalPublication {
     pub name: String,
     pub description: String,
     pub cover_url: String,
-    pub links: Vec<Option<String>>,
+    pub created_at: DateTime<Utc>,
+    /// Identifier (001) of the record it was imported from
+    pub record_id: Option<String>,
+
+    /// Compact ISBN-13s, `9785811412341`
+    pub isbns: Vec<Option<String>>,
+    pub year: Option<i32>,
+    pub publisher: Option<String>,
+    /// ISO 639-2 code of the text, `rus`
+    pub language: Option<String>,
+    pub edition: Option<String>,
+    pub pages: Option<i32>,
+    /// In the order of the publication
+    #[diesel(
+        sql_type = Jsonb,
+        deserialize_as = JsonAgg<Author>,
+        select_expression = sql::<Jsonb>(AUTHORS),
+        select_expression_type = SqlLiteral<Jsonb>,
+    )]
+    pub authors: Vec<Author>,
+    /// In the order of the publication
+    #[diesel(
+        sql_type = Jsonb,
+        deserialize_as = JsonAgg<PublicationLink>,
+        select_expression = sql::<Jsonb>(LINKS),
+        select_expression_type = SqlLiteral<Jsonb>,
+    )]
+    pub links: Vec<PublicationLink>,
 }
 
-#[derive(Insertable, Deserialize)]
-#[diesel(table_name = digital_publications)]
+/// Authors of the publication in the row, `[{"name", "organization", "role"}]`.
+/// Queries with an alias for `digital_publications` select it themselves.
+pub const AUTHORS: &str = "(
+    SELECT coalesce(jsonb_agg(jsonb_build_object(
+        'name', a.name, 'organization', a.organization, 'role', pa.role
+    ) ORDER BY pa.position), '[]')
+    FROM publication_authors pa JOIN authors a ON a.uuid = pa.author
+    WHERE pa.publication = digital_publications.uuid
+)";
+
+/// Links of the publication in the row, like [`AUTHORS`].
+pub const LINKS: &str = "(
+    SELECT coalesce(jsonb_agg(to_jsonb(l) ORDER BY l.position), '[]')
+    FROM publication_links l
+    WHERE l.publication = digital_publications.uuid
+)";
+
+/// Rows of another table as aggregated by [`AUTHORS`] or [`LINKS`].
+#[derive(FromSqlRow)]
+pub struct JsonAgg<T>(Vec<T>);
+
+impl<T: DeserializeOwned> FromSql<Jsonb, Pg> for JsonAgg<T> {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <Value as FromSql<Jsonb, Pg>>::from_sql(bytes)?;
+        Ok(Self(serde_json::from_value(value)?))
+    }
+}
+
+impl<T> From<JsonAgg<T>> for Vec<T> {
+    fn from(rows: JsonAgg<T>) -> Self {
+        rows.0
+    }
+}
+
+impl DigitalPublication {
+    /// Bibliographic description for citations and metadata exports.
+    pub fn description(&self) -> Description {
+        Description {
+            id: Some(self.uuid.to_string()),
+            title: Some(self.name.clone()),
+            creators: self
+                .authors
+                .iter()
+                .map(|author| author.to_name().with_role(author_role::to_role(&author.role)))
+                .collect(),
+            edition: self.edition.clone(),
+            publisher: self.publisher.clone(),
+            date: self.year.map(|year| year.to_string()),
+            extent: self.pages.map(|pages| format!("{pages} с.")),
+            isbns: self.isbns.iter().flatten().filter_map(|isbn| isbn.parse().ok()).collect(),
+            summary: Some(self.description.clone()).filter(|text| !text.is_empty()),
+            languages: self.language.iter().cloned().collect(),
+            urls: self.links.iter().map(|link| link.url.clone()).collect(),
+            image: Some(self.cover_url.clone()).filter(|url| !url.is_empty()),
+            ..Description::default()
+        }
+    }
+}
+
+/// Roles of an [`Author`] in a publication.
+pub mod author_role {
+    use rusmarc_raw::description::Role;
+
+    pub const AUTHOR: &str = "author";
+    pub const COMPILER: &str = "compiler";
+    pub const EDITOR: &str = "editor";
+    pub const ILLUSTRATOR: &str = "illustrator";
+    pub const TRANSLATOR: &str = "translator";
+    pub const CONTRIBUTOR: &str = "contributor";
+
+    pub fn from_role(role: Role) -> &'static str {
+        match role {
+            Role::Author => AUTHOR,
+            Role::Compiler => COMPILER,
+            Role::Editor => EDITOR,
+            Role::Illustrator => ILLUSTRATOR,
+            Role::Translator => TRANSLATOR,
+            Role::Contributor => CONTRIBUTOR,
+        }
+    }
+
+    /// Contributor for unknown roles.
+    pub fn to_role(role: &str) -> Role {
+        match role {
+            AUTHOR => Role::Author,
+            COMPILER => Role::Compiler,
+            EDITOR => Role::Editor,
+            ILLUSTRATOR => Role::Illustrator,
+            TRANSLATOR => Role::Translator,
+            _ => Role::Contributor,
+        }
+    }
+}
+
+/// Person or organization responsible for a publication.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Author {
+    /// `Иванов, И. И.` for people, as names are sorted in catalogues
+    pub name: String,
+    #[serde(default)]
+    pub organization: bool,
+    /// One of [`author_role`], `author` by default
+    #[serde(default = "Author::default_role")]
+    pub role: String,
+}
+
+impl Author {
+    fn default_role() -> String {
+        author_role::AUTHOR.to_string()
+    }
+
+    pub fn from_name(name: &Name, role: Role) -> Self {
+        Self {
+            name: name.inverted(),
+            organization: matches!(name, Name::Organization(_)),
+            role: author_role::from_role(role).to_string(),
+        }
+    }
+
+    pub fn to_name(&self) -> Name {
+        if self.organization {
+            return Name::Organization(self.name.clone());
+        }
+        match self.name.split_once(", ") {
+            Some((family, given)) => Name::Person {
+                family: family.to_string(),
+                given: Some(given.to_string()),
+            },
+            None => Name::Person {
+                family: self.name.clone(),
+                given: None,
+            },
+        }
+    }
+}
+
+/// Kinds of a [`PublicationLink`].
+pub mod link_kind {
+    /// Reader of the vendor
+    pub const READ_ONLINE: &str = "read_online";
+    /// The file itself
+    pub const DOWNLOAD: &str = "download";
+    /// Description of the publication on the site of the vendor
+    pub const VENDOR_PAGE: &str = "vendor_page";
+    /// `https://doi.org/...`
+    pub const DOI: &str = "doi";
+
+    /// DOI for `doi.org` URLs, reading online otherwise.
+    pub fn of_url(url: &str) -> &'static str {
+        let host = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        match host {
+            "doi.org" | "dx.doi.org" => DOI,
+            _ => READ_ONLINE,
+        }
+    }
+}
+
+/// Who can follow a [`PublicationLink`].
+pub mod link_access {
+    pub const OPEN: &str = "open";
+    /// Readers of the library, through its subscription to the vendor
+    pub const SUBSCRIPTION: &str = "subscription";
+    /// Only in the library, or on request
+    pub const RESTRICTED: &str = "restricted";
+}
+
+/// Where a publication can be read, downloaded or looked up, with the result
+/// of the last check that it still works.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = publication_links, check_for_backend(Pg))]
+pub struct PublicationLink {
+    pub uuid: Uuid,
+    pub url: String,
+    /// One of [`link_kind`]
+    pub kind: String,
+    pub label: Option<String>,
+    /// One of [`link_access`]
+    pub access: String,
+    pub position: i32,
+
+    /// Never checked when empty
+    pub checked_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last check, after redirects
+    pub status: Option<i32>,
+    /// Why the last check got no response
+    pub error: Option<String>,
+}
+
+/// Link to add to a publication, or to replace one with. A new URL hasn't
+/// been checked, whatever the old one was.
+#[derive(Debug, Clone, Insertable, AsChangeset, Deserialize)]
+#[diesel(table_name = publication_links, treat_none_as_null = true)]
+pub struct NewPublicationLink {
+    /// `http` or `https` only
+    #[serde(deserialize_with = "deserialize_link_url")]
+    pub url: String,
+    /// One of [`link_kind`], `read_online` by default
+    #[serde(default = "NewPublicationLink::default_kind")]
+    pub kind: String,
+    pub label: Option<String>,
+    /// One of [`link_access`], `subscription` by default
+    #[serde(default = "NewPublicationLink::default_access")]
+    pub access: String,
+}
+
+impl NewPublicationLink {
+    fn default_kind() -> String {
+        link_kind::READ_ONLINE.to_string()
+    }
+
+    fn default_access() -> String {
+        link_access::SUBSCRIPTION.to_string()
+    }
+
+    /// Imported link, of the kind its URL suggests.
+    pub fn from_url(url: String) -> Self {
+        Self {
+            kind: link_kind::of_url(&url).to_string(),
+            url,
+            label: None,
+            access: Self::default_access(),
+        }
+    }
+}
+
+/// Absolute `http` or `https` URL with a host, which the checker can request.
+fn deserialize_link_url<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let url = String::deserialize(deserializer)?;
+    let uri = url
+        .parse::<Uri>()
+        .map_err(|err| D::Error::custom(format!("invalid link URL `{url}`: {err}")))?;
+    let web = matches!(uri.scheme_str(), Some("http" | "https"));
+    if !web || uri.host().is_none_or(str::is_empty) {
+        return Err(D::Error::custom(format!("link URL `{url}` is not an http(s) URL")));
+    }
+    Ok(url)
+}
+
+/// Result of checking a [`PublicationLink`].
+#[derive(Debug, AsChangeset)]
+#[diesel(table_name = publication_links, treat_none_as_null = true)]
+pub struct LinkCheck {
+    pub checked_at: DateTime<Utc>,
+    pub status: Option<i32>,
+    pub error: Option<String>,
+}
+
+/// Two publications that are probably the same document, see
+/// [`publication_duplicates`](crate::repository::publication_duplicates).
+#[derive(Debug, Queryable, Selectable, Serialize)]
+#[diesel(table_name = publication_duplicates, check_for_backend(Pg))]
+pub struct PublicationDuplicate {
+    /// The one imported later
+    pub publication: Uuid,
+    pub duplicate_of: Uuid,
+    /// `isbn` or `metadata`, as `rusmarc_raw::dedup::MatchReason`
+    pub reason: String,
+    pub confidence: f32,
+    pub found_at: DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = publication_duplicates)]
+pub struct NewPublicationDuplicate {
+    pub publication: Uuid,
+    pub duplicate_of: Uuid,
+    pub reason: String,
+    pub confidence: f32,
+}
+
+/// Publication found by `GET /digital_publications/search`.
+#[derive(QueryableByName, Serialize)]
+pub struct SearchHit {
+    #[diesel(embed)]
+    pub publication: DigitalPublication,
+    /// Higher is better, comparable within one search only
+    #[diesel(sql_type = Float)]
+    pub rank: f32,
+    /// Name with the matched words in `<mark>`
+    #[diesel(sql_type = Text)]
+    pub highlighted_name: String,
+    /// Fragments of the description around the matched words, in `<mark>`
+    #[diesel(sql_type = Text)]
+    pub snippet: String,
+    /// `full_text`, or `fuzzy` when no words matched and the name is similar to the query
+    #[diesel(sql_type = Text)]
+    pub matched_by: String,
+}
+
+/// Publication to create, or to replace the fields of an existing one with;
+/// the record ID stays as it was imported.
+#[derive(Insertable, AsChangeset, Deserialize)]
+#[diesel(table_name = digital_publications, treat_none_as_null = true)]
 pub struct NewDigitalPublication {
     pub name: String,
     pub source: Uuid,
     pub description: String,
     pub cover_url: String,
-    pub links: Vec<String>,
-}
\ No newline at end of file
+    #[serde(default)]
+    #[diesel(skip_update)]
+    pub record_id: Option<String>,
+
+    /// Any way ISBNs are written, saved as compact ISBN-13s
+    #[serde(default, deserialize_with = "deserialize_isbns")]
+    pub isbns: Vec<String>,
+    pub year: Option<i32>,
+    pub publisher: Option<String>,
+    pub language: Option<String>,
+    pub edition: Option<String>,
+    pub pages: Option<i32>,
+    #[serde(default)]
+    #[diesel(skip_insertion, skip_update)]
+    pub authors: Vec<Author>,
+    #[serde(default)]
+    #[diesel(skip_insertion, skip_update)]
+    pub links: Vec<NewPublicationLink>,
+}
+
+/// ISBNs in the compact ISBN-13 form they are kept in, without repeats;
+/// one that doesn't parse fails the request.
+fn deserialize_isbns<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let mut isbns = Vec::new();
+    for text in Vec::<String>::deserialize(deserializer)? {
+        let isbn = Isbn::parse(&text)
+            .map_err(|err| D::Error::custom(format!("invalid ISBN `{text}`: {err}")))?;
+        let isbn = isbn.compact().to_string();
+        if !isbns.contains(&isbn) {
+            isbns.push(isbn);
+        }
+    }
+    Ok(isbns)
+}
+
+/// Vendor record a publication was imported from.
+#[derive(Queryable, Serialize)]
+pub struct RawRecord {
+    pub publication: Uuid,
+    pub source: Uuid,
+    /// 001
+    pub record_id: String,
+    /// 005, `yyyymmddhhmmss.f`
+    pub version: Option<String>,
+    /// Array of [`rusmarc_raw::field::Field`]s
+    pub fields: Value,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Record of a publication being imported, saved along with it.
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = raw_records)]
+pub struct NewRawRecord {
+    pub version: Option<String>,
+    pub fields: Value,
+}
+
+
+/// Statuses of an [`ImportJob`].
+pub mod job_status {
+    pub const QUEUED: &str = "queued";
+    pub const RUNNING: &str = "running";
+    pub const DONE: &str = "done";
+    pub const FAILED: &str = "failed";
+    pub const CANCELLED: &str = "cancelled";
+}
+
+#[derive(Queryable, Serialize)]
+pub struct ImportJob {
+    pub uuid: Uuid,
+    pub source: Uuid,
+    #[serde(skip)]
+    pub path: String,
+    pub format: String,
+    pub status: String,
+    pub error: Option<String>,
+
+    pub total: Option<i32>,
+    pub processed: i32,
+    pub created: i32,
+    pub updated: i32,
+    pub failed: i32,
+
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    /// Query the records must match, see `rusmarc_raw::query`
+    pub filter: Option<String>,
+    /// Records that don't match the filter
+    pub skipped: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = import_jobs)]
+pub struct NewImportJob {
+    pub uuid: Uuid,
+    pub source: Uuid,
+    pub path: String,
+    pub format: String,
+    pub filter: Option<String>,
+}
+
+/// Record of an import that could not be read or has no identifier or title.
+#[derive(Queryable, Serialize)]
+pub struct ImportJobError {
+    #[serde(skip)]
+    pub id: i32,
+    #[serde(skip)]
+    pub job: Uuid,
+    /// Position of the record in the dump, from 1
+    pub position: i32,
+    pub record_id: Option<String>,
+    pub error: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = import_job_errors)]
+pub struct NewImportJobError {
+    pub job: Uuid,
+    pub position: i32,
+    pub record_id: Option<String>,
+    pub error: String,
+}
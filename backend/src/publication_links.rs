@@ -0,0 +1,108 @@
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::link_check;
+use crate::models::NewPublicationLink;
+use crate::repository::publication_links as repository;
+use actix_web::{web, HttpResponse};
+
+use ::uuid::Uuid;
+
+pub fn config(cfg: &mut web::ServiceConfig, base_path: &str) {
+    cfg.service(
+        web::resource(format!("{}{}", base_path, "digital_publications/{id}/links"))
+            .route(web::get().to(get_all))
+            .route(web::post().to(create)),
+    )
+    .service(
+        web::resource(format!("{}{}", base_path, "digital_publications/{id}/links/{link_id}"))
+            .route(web::get().to(get))
+            .route(web::put().to(update))
+            .route(web::delete().to(delete)),
+    )
+    .service(
+        web::resource(format!("{}{}", base_path, "digital_publications/{id}/links/{link_id}/check"))
+            .route(web::post().to(check)),
+    )
+    ;
+}
+
+
+/// Links of the publication in their order, with the results of their last checks.
+async fn get_all(
+    pool: web::Data<DbPool>,
+    pub_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let links = repository::list(&pool, pub_id.into_inner())
+        .await?
+        .ok_or_else(|| ApiError::not_found("Digital publication not found"))?;
+
+    Ok(HttpResponse::Ok().json(links))
+}
+
+/// Adds the link after the others; it is checked by the next round of the checker.
+async fn create(
+    pool: web::Data<DbPool>,
+    pub_id: web::Path<Uuid>,
+    link_data: web::Json<NewPublicationLink>,
+) -> Result<HttpResponse, ApiError> {
+    let pub_id = pub_id.into_inner();
+    let link = repository::insert(&pool, pub_id, link_data.into_inner())
+        .await?
+        .ok_or_else(|| ApiError::not_found("Digital publication not found"))?;
+
+    Ok(HttpResponse::Created()
+        .insert_header((
+            "Location",
+            format!("/api/v1/digital_publications/{}/links/{}", pub_id, link.uuid),
+        ))
+        .json(link))
+}
+
+async fn get(
+    pool: web::Data<DbPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (pub_id, link_id) = path.into_inner();
+    let link = repository::find(&pool, pub_id, link_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Link not found"))?;
+
+    Ok(HttpResponse::Ok().json(link))
+}
+
+async fn update(
+    pool: web::Data<DbPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    link_data: web::Json<NewPublicationLink>,
+) -> Result<HttpResponse, ApiError> {
+    let (pub_id, link_id) = path.into_inner();
+    let link = repository::update(&pool, pub_id, link_id, link_data.into_inner())
+        .await?
+        .ok_or_else(|| ApiError::not_found("Link not found"))?;
+
+    Ok(HttpResponse::Ok().json(link))
+}
+
+async fn delete(
+    pool: web::Data<DbPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (pub_id, link_id) = path.into_inner();
+    if !repository::delete(&pool, pub_id, link_id).await? {
+        return Err(ApiError::not_found("Link not found"));
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Checks the link right away instead of waiting for the checker.
+async fn check(
+    pool: web::Data<DbPool>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (pub_id, link_id) = path.into_inner();
+    let link = link_check::check_now(&pool, pub_id, link_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Link not found"))?;
+
+    Ok(HttpResponse::Ok().json(link))
+}
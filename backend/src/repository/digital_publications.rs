@@ -0,0 +1,301 @@
+use diesel::pg::{Pg, PgConnection, PgRowByRowLoadingMode};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel::sql_types::{BigInt, Text};
+use serde::Deserialize;
+use ::uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::models::{
+    Author, DigitalPublication, NewDigitalPublication, NewRawRecord, SearchHit, AUTHORS, LINKS,
+};
+use crate::schema::digital_publications::dsl::*;
+use crate::schema::{authors, publication_authors};
+use crate::schema::digital_publications::BoxedQuery;
+
+use super::{escape_like, publication_links, raw_records, run, Page};
+
+define_sql_function!(fn lower(text: Text) -> Text);
+
+/// `?source=...&name_prefix=...&has_cover=true&sort=-created_at`
+#[derive(Debug, Default, Deserialize)]
+pub struct Filter {
+    pub source: Option<Uuid>,
+    /// Case-insensitive beginning of the name
+    pub name_prefix: Option<String>,
+    pub has_cover: Option<bool>,
+    #[serde(default)]
+    pub sort: Sort,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub enum Sort {
+    #[serde(rename = "name")]
+    Name,
+    #[serde(rename = "-name")]
+    NameDesc,
+    #[default]
+    #[serde(rename = "created_at")]
+    CreatedAt,
+    #[serde(rename = "-created_at")]
+    CreatedAtDesc,
+}
+
+fn filtered(filter: &Filter) -> BoxedQuery<'static, Pg> {
+    let mut query = digital_publications.into_boxed();
+    if let Some(id) = filter.source {
+        query = query.filter(source.eq(id));
+    }
+    if let Some(prefix) = &filter.name_prefix {
+        query = query.filter(lower(name).like(format!("{}%", escape_like(&prefix.to_lowercase()))));
+    }
+    match filter.has_cover {
+        Some(true) => query.filter(cover_url.ne("")),
+        Some(false) => query.filter(cover_url.eq("")),
+        None => query,
+    }
+}
+
+fn sorted(query: BoxedQuery<'static, Pg>, sort: Sort) -> BoxedQuery<'static, Pg> {
+    let query = match sort {
+        Sort::Name => query.order(name.asc()),
+        Sort::NameDesc => query.order(name.desc()),
+        Sort::CreatedAt => query.order(created_at.asc()),
+        Sort::CreatedAtDesc => query.order(created_at.desc()),
+    };
+    // Stable pages among equal names and dates
+    query.then_order_by(uuid)
+}
+
+/// Page of the publications that pass the filter, and their total number.
+pub async fn list(
+    pool: &DbPool,
+    page: Page,
+    filter: Filter,
+) -> Result<(Vec<DigitalPublication>, i64), ApiError> {
+    run(pool, move |conn| {
+        let total = filtered(&filter).count().get_result::<i64>(conn)?;
+
+        let pubs = sorted(filtered(&filter), filter.sort)
+            .limit(page.limit())
+            .offset(page.offset())
+            .select(DigitalPublication::as_select())
+            .load::<DigitalPublication>(conn)?;
+
+        Ok((pubs, total))
+    })
+    .await
+}
+
+/// All the publications that pass the filter, or only those of them in `ids`,
+/// in the order of the filter. Rows are fetched one by one while the iterator
+/// is consumed, so the result doesn't have to fit in memory.
+pub fn export<'conn>(
+    conn: &'conn mut PgConnection,
+    filter: &Filter,
+    ids: Option<Vec<Uuid>>,
+) -> QueryResult<impl Iterator<Item = QueryResult<DigitalPublication>> + 'conn> {
+    let mut query = filtered(filter);
+    if let Some(ids) = ids {
+        query = query.filter(uuid.eq_any(ids));
+    }
+    sorted(query, filter.sort)
+        .select(DigitalPublication::as_select())
+        .load_iter::<DigitalPublication, PgRowByRowLoadingMode>(conn)
+}
+
+/// `websearch_to_tsquery` syntax: words, `"phrases"`, `or`, `-excluded`.
+const FULL_TEXT_QUERY: &str =
+    "websearch_to_tsquery('russian', $1) || websearch_to_tsquery('english', $1)";
+
+/// Publications ranked by how well the name and description match `query`,
+/// and the number of all of them. When no words match, names similar to
+/// `query` are returned instead, so typos still find something.
+pub async fn search(
+    pool: &DbPool,
+    query: String,
+    page: Page,
+) -> Result<(Vec<SearchHit>, i64), ApiError> {
+    #[derive(QueryableByName)]
+    struct Count {
+        #[diesel(sql_type = BigInt)]
+        count: i64,
+    }
+
+    run(pool, move |conn| {
+        let mut count = |matches: &str| {
+            diesel::sql_query(format!("SELECT COUNT(*) AS count {matches}"))
+                .bind::<Text, _>(&query)
+                .get_result::<Count>(conn)
+                .map(|found| found.count)
+        };
+        let full_text_matches = format!(
+            "FROM digital_publications p, (SELECT {FULL_TEXT_QUERY} AS query) q
+            WHERE publication_search_vector(p.name, p.description) @@ q.query"
+        );
+        let fuzzy_matches = "FROM digital_publications p WHERE $1 <% p.name";
+
+        let mut total = count(&full_text_matches)?;
+        let full_text = total > 0;
+        if !full_text {
+            total = count(fuzzy_matches)?;
+        }
+
+        let authors = AUTHORS.replace("digital_publications.uuid", "p.uuid");
+        let links = LINKS.replace("digital_publications.uuid", "p.uuid");
+        let sql = if full_text {
+            format!(
+                "SELECT p.*, {authors} AS authors, {links} AS links,
+                    ts_rank(publication_search_vector(p.name, p.description), q.query) AS rank,
+                    ts_headline('russian', p.name, q.query,
+                        'HighlightAll=true, StartSel=<mark>, StopSel=</mark>') AS highlighted_name,
+                    ts_headline('russian', p.description, q.query,
+                        'MaxFragments=2, MinWords=5, MaxWords=20, StartSel=<mark>, StopSel=</mark>') AS snippet,
+                    'full_text'::text AS matched_by
+                {full_text_matches}
+                ORDER BY rank DESC, p.uuid
+                LIMIT $2 OFFSET $3"
+            )
+        } else {
+            format!(
+                "SELECT p.*, {authors} AS authors, {links} AS links,
+                word_similarity($1, p.name) AS rank,
+                p.name AS highlighted_name,
+                left(p.description, 200) AS snippet,
+                'fuzzy'::text AS matched_by
+            {fuzzy_matches}
+            ORDER BY rank DESC, p.uuid
+            LIMIT $2 OFFSET $3"
+            )
+        };
+
+        let hits = diesel::sql_query(sql)
+            .bind::<Text, _>(&query)
+            .bind::<BigInt, _>(page.limit())
+            .bind::<BigInt, _>(page.offset())
+            .load::<SearchHit>(conn)?;
+
+        Ok((hits, total))
+    })
+    .await
+}
+
+pub async fn find(pool: &DbPool, id: Uuid) -> Result<Option<DigitalPublication>, ApiError> {
+    run(pool, move |conn| {
+        Ok(digital_publications
+            .filter(uuid.eq(id))
+            .select(DigitalPublication::as_select())
+            .first(conn)
+            .optional()?)
+    })
+    .await
+}
+
+pub async fn insert(pool: &DbPool, publication: NewDigitalPublication) -> Result<(), ApiError> {
+    run(pool, move |conn| {
+        conn.transaction(|conn| {
+            let id = diesel::insert_into(digital_publications)
+                .values(&publication)
+                .returning(uuid)
+                .get_result::<Uuid>(conn)?;
+            set_authors(conn, id, &publication.authors)?;
+            publication_links::replace(conn, id, &publication.links)
+        })?;
+        Ok(())
+    })
+    .await
+}
+
+/// Replaces the authors of the publication, adding the new ones to `authors`.
+fn set_authors(conn: &mut PgConnection, id: Uuid, list: &[Author]) -> QueryResult<()> {
+    diesel::delete(publication_authors::table.filter(publication_authors::publication.eq(id)))
+        .execute(conn)?;
+
+    for (position, author) in list.iter().enumerate() {
+        let author_id = diesel::insert_into(authors::table)
+            .values((
+                authors::name.eq(&author.name),
+                authors::organization.eq(author.organization),
+            ))
+            .on_conflict((authors::name, authors::organization))
+            // Not `do_nothing`, which returns no row for an existing author
+            .do_update()
+            .set(authors::name.eq(excluded(authors::name)))
+            .returning(authors::uuid)
+            .get_result::<Uuid>(conn)?;
+
+        diesel::insert_into(publication_authors::table)
+            .values((
+                publication_authors::publication.eq(id),
+                publication_authors::author.eq(author_id),
+                publication_authors::role.eq(&author.role),
+                publication_authors::position.eq(position as i32),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+/// Updates the publication of the same source with the same record ID, or
+/// inserts it, with its record, in the transaction of the caller. Returns
+/// its ID and whether it was created.
+pub fn upsert_by_record_id(
+    conn: &mut PgConnection,
+    publication: &NewDigitalPublication,
+    record: &NewRawRecord,
+) -> QueryResult<(Uuid, bool)> {
+    let existing = digital_publications
+        .filter(source.eq(publication.source))
+        .filter(record_id.eq(&publication.record_id));
+    let id = diesel::update(existing)
+        .set(publication)
+        .returning(uuid)
+        .get_result::<Uuid>(conn)
+        .optional()?;
+
+    let created = id.is_none();
+    let id = match id {
+        Some(id) => id,
+        None => diesel::insert_into(digital_publications)
+            .values(publication)
+            .returning(uuid)
+            .get_result::<Uuid>(conn)?,
+    };
+    set_authors(conn, id, &publication.authors)?;
+    publication_links::replace_imported(conn, id, &publication.links)?;
+    raw_records::save(conn, id, publication, record)?;
+    Ok((id, created))
+}
+
+/// Whether the publication existed.
+pub async fn update(
+    pool: &DbPool,
+    id: Uuid,
+    publication: NewDigitalPublication,
+) -> Result<bool, ApiError> {
+    run(pool, move |conn| {
+        let updated = conn.transaction(|conn| {
+            let updated = diesel::update(digital_publications.filter(uuid.eq(id)))
+                .set(&publication)
+                .execute(conn)?;
+            if updated > 0 {
+                set_authors(conn, id, &publication.authors)?;
+                publication_links::replace(conn, id, &publication.links)?;
+            }
+            QueryResult::Ok(updated)
+        })?;
+        Ok(updated > 0)
+    })
+    .await
+}
+
+/// Whether the publication existed.
+pub async fn delete(pool: &DbPool, id: Uuid) -> Result<bool, ApiError> {
+    run(pool, move |conn| {
+        let deleted = diesel::delete(digital_publications.filter(uuid.eq(id))).execute(conn)?;
+        Ok(deleted > 0)
+    })
+    .await
+}
@@ -0,0 +1,218 @@
+//! Jobs are created and watched by the handlers, and taken and advanced by
+//! the [worker](crate::import::worker) on its own thread with its own
+//! connection, hence the synchronous functions.
+
+use ::uuid::Uuid;
+use diesel::dsl::now;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::models::job_status::{CANCELLED, DONE, FAILED, QUEUED, RUNNING};
+use crate::models::{
+    ImportJob, ImportJobError, NewDigitalPublication, NewImportJob, NewImportJobError, NewRawRecord,
+};
+use crate::schema::{import_job_errors, import_jobs};
+
+use super::{digital_publications, publication_duplicates, run};
+
+pub async fn insert(pool: &DbPool, job: NewImportJob) -> Result<ImportJob, ApiError> {
+    run(pool, move |conn| {
+        Ok(diesel::insert_into(import_jobs::table)
+            .values(&job)
+            .get_result::<ImportJob>(conn)?)
+    })
+    .await
+}
+
+pub async fn find(pool: &DbPool, id: Uuid) -> Result<Option<ImportJob>, ApiError> {
+    run(pool, move |conn| {
+        Ok(import_jobs::table
+            .find(id)
+            .first::<ImportJob>(conn)
+            .optional()?)
+    })
+    .await
+}
+
+/// Records of the job that failed, in the order of the dump.
+pub async fn errors(pool: &DbPool, id: Uuid) -> Result<Vec<ImportJobError>, ApiError> {
+    run(pool, move |conn| {
+        Ok(import_job_errors::table
+            .filter(import_job_errors::job.eq(id))
+            .order(import_job_errors::position)
+            .load::<ImportJobError>(conn)?)
+    })
+    .await
+}
+
+/// Moves the job to the status `to` if it is in one of `from`, returns
+/// whether it was.
+pub async fn transition(
+    pool: &DbPool,
+    id: Uuid,
+    from: &'static [&'static str],
+    to: &'static str,
+) -> Result<bool, ApiError> {
+    run(pool, move |conn| {
+        let changed = diesel::update(
+            import_jobs::table
+                .find(id)
+                .filter(import_jobs::status.eq_any(from)),
+        )
+        .set((
+            import_jobs::status.eq(to),
+            import_jobs::error.eq(None::<String>),
+            import_jobs::updated_at.eq(now),
+        ))
+        .execute(conn)?;
+        Ok(changed > 0)
+    })
+    .await
+}
+
+/// Jobs that were running when the server stopped, so that they are taken
+/// again and continue after their processed records.
+pub fn requeue_running(conn: &mut PgConnection) -> QueryResult<usize> {
+    diesel::update(import_jobs::table.filter(import_jobs::status.eq(RUNNING)))
+        .set(import_jobs::status.eq(QUEUED))
+        .execute(conn)
+}
+
+/// Oldest queued job, marked as running.
+pub fn claim_next(conn: &mut PgConnection) -> QueryResult<Option<ImportJob>> {
+    conn.transaction(|conn| {
+        let job = import_jobs::table
+            .filter(import_jobs::status.eq(QUEUED))
+            .order(import_jobs::created_at)
+            .for_update()
+            .skip_locked()
+            .first::<ImportJob>(conn)
+            .optional()?;
+
+        let Some(mut job) = job else {
+            return Ok(None);
+        };
+        diesel::update(import_jobs::table.find(job.uuid))
+            .set((
+                import_jobs::status.eq(RUNNING),
+                import_jobs::updated_at.eq(now),
+            ))
+            .execute(conn)?;
+        job.status = RUNNING.to_string();
+        Ok(Some(job))
+    })
+}
+
+pub fn set_total(conn: &mut PgConnection, id: Uuid, total: i32) -> QueryResult<()> {
+    diesel::update(import_jobs::table.find(id))
+        .set((
+            import_jobs::total.eq(total),
+            import_jobs::updated_at.eq(now),
+        ))
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Saves the publications with their records and positions in the dump, and
+/// the errors, of the next `processed` records, `skipped` of which don't match
+/// the filter, with the progress of the job, in one transaction so that a
+/// resumed job neither skips nor repeats records. A publication the database rejects, e.g. for a NUL in its text,
+/// is rolled back to its savepoint and saved as an error of its record.
+/// Duplicates of the saved publications are looked for in the same
+/// transaction.
+/// `false`, with nothing saved, if the job was cancelled in the meantime.
+pub fn save_batch(
+    conn: &mut PgConnection,
+    id: Uuid,
+    processed: i32,
+    skipped: i32,
+    publications: &[(i32, NewDigitalPublication, NewRawRecord)],
+    errors: &[NewImportJobError],
+) -> QueryResult<bool> {
+    conn.transaction(|conn| {
+        // Locked until the batch is saved, so a cancellation waits for it
+        let status = import_jobs::table
+            .find(id)
+            .select(import_jobs::status)
+            .for_update()
+            .first::<String>(conn)?;
+        if status != RUNNING {
+            return Ok(false);
+        }
+
+        let (mut created, mut updated) = (0, 0);
+        let mut imported = Vec::new();
+        let mut rejected = Vec::new();
+        for (position, publication, record) in publications {
+            let saved = conn.transaction(|conn| {
+                digital_publications::upsert_by_record_id(conn, publication, record)
+            });
+            match saved {
+                Ok((publication_id, true)) => {
+                    imported.push(publication_id);
+                    created += 1;
+                }
+                Ok((publication_id, false)) => {
+                    imported.push(publication_id);
+                    updated += 1;
+                }
+                Err(DieselError::DatabaseError(kind, info))
+                    if !matches!(kind, DatabaseErrorKind::ClosedConnection) =>
+                {
+                    rejected.push(NewImportJobError {
+                        job: id,
+                        position: *position,
+                        record_id: publication.record_id.clone(),
+                        error: info.message().to_string(),
+                    })
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        publication_duplicates::find(conn, &imported)?;
+
+        let failed = errors.len() + rejected.len();
+        if failed > 0 {
+            diesel::insert_into(import_job_errors::table)
+                .values(errors.iter().chain(&rejected).collect::<Vec<_>>())
+                .execute(conn)?;
+        }
+
+        diesel::update(import_jobs::table.find(id))
+            .set((
+                import_jobs::processed.eq(import_jobs::processed + processed),
+                import_jobs::created.eq(import_jobs::created + created),
+                import_jobs::updated.eq(import_jobs::updated + updated),
+                import_jobs::failed.eq(import_jobs::failed + failed as i32),
+                import_jobs::skipped.eq(import_jobs::skipped + skipped),
+                import_jobs::updated_at.eq(now),
+            ))
+            .execute(conn)?;
+        Ok(true)
+    })
+}
+
+/// Marks a running job as done, or as failed with the reason.
+pub fn finish(conn: &mut PgConnection, id: Uuid, error: Option<String>) -> QueryResult<()> {
+    let status = if error.is_some() { FAILED } else { DONE };
+    diesel::update(
+        import_jobs::table
+            .find(id)
+            .filter(import_jobs::status.eq(RUNNING)),
+    )
+    .set((
+        import_jobs::status.eq(status),
+        import_jobs::error.eq(error),
+        import_jobs::updated_at.eq(now),
+    ))
+    .execute(conn)?;
+    Ok(())
+}
+
+/// Statuses a job can be cancelled in.
+pub const CANCELLABLE: &[&str] = &[QUEUED, RUNNING];
+/// Statuses a job can be resumed from.
+pub const RESUMABLE: &[&str] = &[CANCELLED, FAILED];
@@ -0,0 +1,57 @@
+//! Database access for the handlers. Diesel is synchronous, so every query
+//! runs on actix's blocking thread pool instead of a worker thread.
+
+pub mod digital_publications;
+pub mod import_jobs;
+pub mod publication_duplicates;
+pub mod publication_links;
+pub mod raw_records;
+pub mod sources;
+
+use actix_web::web;
+use diesel::pg::PgConnection;
+use serde::Deserialize;
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+
+/// Runs `query` with a connection from the pool, off the async runtime.
+pub async fn run<T, F>(pool: &DbPool, query: F) -> Result<T, ApiError>
+where
+    F: FnOnce(&mut PgConnection) -> Result<T, ApiError> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = pool.clone();
+    web::block(move || {
+        let mut conn = pool.get()?;
+        query(&mut conn)
+    })
+    .await?
+}
+
+/// Part of a list to load, `?limit=50&offset=100`.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct Page {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl Page {
+    pub const DEFAULT_LIMIT: i64 = 50;
+    pub const MAX_LIMIT: i64 = 500;
+
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(Self::DEFAULT_LIMIT).clamp(0, Self::MAX_LIMIT)
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+}
+
+/// Escapes `%`, `_` and `\` for a `LIKE` pattern.
+pub fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
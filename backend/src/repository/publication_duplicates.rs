@@ -0,0 +1,102 @@
+//! Publications that are probably the same document, e.g. a book sent by two
+//! vendors under their own record IDs. They are found by
+//! [`rusmarc_raw::dedup`] as dumps are imported, and kept for someone to
+//! review; nothing is merged.
+
+use std::collections::HashSet;
+
+use ::uuid::Uuid;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use rusmarc_raw::dedup::{Deduplicator, Fingerprint, MatchReason};
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::models::{DigitalPublication, NewPublicationDuplicate, PublicationDuplicate};
+use crate::schema::{digital_publications, publication_duplicates};
+
+use super::{run, Page};
+
+/// Pairs found so far, the latest first, and the number of all of them.
+pub async fn list(pool: &DbPool, page: Page) -> Result<(Vec<PublicationDuplicate>, i64), ApiError> {
+    run(pool, move |conn| {
+        let total = publication_duplicates::table
+            .count()
+            .get_result::<i64>(conn)?;
+        let pairs = publication_duplicates::table
+            .order((
+                publication_duplicates::found_at.desc(),
+                publication_duplicates::publication,
+                publication_duplicates::duplicate_of,
+            ))
+            .limit(page.limit())
+            .offset(page.offset())
+            .select(PublicationDuplicate::as_select())
+            .load(conn)?;
+        Ok((pairs, total))
+    })
+    .await
+}
+
+/// Compares the imported publications, in the order of `ids`, with the
+/// publications that share an ISBN with them and with each other, and saves
+/// the pairs that match. Pairs found before are kept. Returns the number of
+/// new pairs.
+pub fn find(conn: &mut PgConnection, ids: &[Uuid]) -> QueryResult<usize> {
+    let mut seen = HashSet::new();
+    let ids: Vec<Uuid> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+    let mut imported = digital_publications::table
+        .filter(digital_publications::uuid.eq_any(&ids))
+        .select(DigitalPublication::as_select())
+        .load::<DigitalPublication>(conn)?;
+    imported.sort_by_key(|publication| ids.iter().position(|&id| id == publication.uuid));
+
+    let isbns: Vec<String> = imported
+        .iter()
+        .flat_map(|publication| publication.isbns.iter().flatten().cloned())
+        .collect();
+    let others = if isbns.is_empty() {
+        Vec::new()
+    } else {
+        digital_publications::table
+            .filter(digital_publications::isbns.overlaps_with(isbns))
+            .filter(digital_publications::uuid.ne_all(&ids))
+            .select(DigitalPublication::as_select())
+            .load::<DigitalPublication>(conn)?
+    };
+
+    // Publications of the deduplicator by their positions in it
+    let mut added = Vec::new();
+    let mut dedup = Deduplicator::default();
+    for publication in &others {
+        dedup.add(Fingerprint::from(&publication.description()));
+        added.push(publication.uuid);
+    }
+    let mut pairs = Vec::new();
+    for publication in &imported {
+        let fingerprint = Fingerprint::from(&publication.description());
+        pairs.extend(dedup.matches(&fingerprint).into_iter().map(|(i, found)| {
+            NewPublicationDuplicate {
+                publication: publication.uuid,
+                duplicate_of: added[i],
+                reason: match found.reason {
+                    MatchReason::Isbn => "isbn",
+                    MatchReason::Metadata => "metadata",
+                }
+                .to_string(),
+                confidence: found.confidence,
+            }
+        }));
+        dedup.add(fingerprint);
+        added.push(publication.uuid);
+    }
+
+    if pairs.is_empty() {
+        return Ok(0);
+    }
+    // Pairs found before, either way round, are left as they are
+    diesel::insert_into(publication_duplicates::table)
+        .values(&pairs)
+        .on_conflict_do_nothing()
+        .execute(conn)
+}
@@ -0,0 +1,224 @@
+//! Links are edited one by one by the handlers, replaced all at once with
+//! their publication, and checked by the [checker](crate::link_check) on its
+//! own thread, hence the synchronous functions.
+
+use ::uuid::Uuid;
+use chrono::{DateTime, Utc};
+use diesel::dsl::{exists, max, select};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::models::{LinkCheck, NewPublicationLink, PublicationLink};
+use crate::schema::{digital_publications, publication_links};
+
+use super::run;
+
+fn publication_exists(conn: &mut PgConnection, publication: Uuid) -> QueryResult<bool> {
+    select(exists(digital_publications::table.find(publication))).get_result(conn)
+}
+
+/// Links of the publication in their order, or `None` without the publication.
+pub async fn list(
+    pool: &DbPool,
+    publication: Uuid,
+) -> Result<Option<Vec<PublicationLink>>, ApiError> {
+    run(pool, move |conn| {
+        if !publication_exists(conn, publication)? {
+            return Ok(None);
+        }
+        Ok(Some(
+            publication_links::table
+                .filter(publication_links::publication.eq(publication))
+                .order(publication_links::position)
+                .select(PublicationLink::as_select())
+                .load(conn)?,
+        ))
+    })
+    .await
+}
+
+pub async fn find(
+    pool: &DbPool,
+    publication: Uuid,
+    id: Uuid,
+) -> Result<Option<PublicationLink>, ApiError> {
+    run(pool, move |conn| {
+        Ok(publication_links::table
+            .filter(publication_links::publication.eq(publication))
+            .filter(publication_links::uuid.eq(id))
+            .select(PublicationLink::as_select())
+            .first(conn)
+            .optional()?)
+    })
+    .await
+}
+
+/// Adds the link after the others, or returns `None` without the publication.
+pub async fn insert(
+    pool: &DbPool,
+    publication: Uuid,
+    link: NewPublicationLink,
+) -> Result<Option<PublicationLink>, ApiError> {
+    run(pool, move |conn| {
+        let link = conn.transaction(|conn| {
+            if !publication_exists(conn, publication)? {
+                return Ok(None);
+            }
+            let last = publication_links::table
+                .filter(publication_links::publication.eq(publication))
+                .select(max(publication_links::position))
+                .get_result::<Option<i32>>(conn)?;
+
+            diesel::insert_into(publication_links::table)
+                .values((
+                    publication_links::publication.eq(publication),
+                    publication_links::position.eq(last.map_or(0, |last| last + 1)),
+                    &link,
+                ))
+                .returning(PublicationLink::as_returning())
+                .get_result(conn)
+                .map(Some)
+        })?;
+        Ok(link)
+    })
+    .await
+}
+
+/// Replaces the URL, kind, label and access of the link, or returns `None`
+/// without it.
+pub async fn update(
+    pool: &DbPool,
+    publication: Uuid,
+    id: Uuid,
+    link: NewPublicationLink,
+) -> Result<Option<PublicationLink>, ApiError> {
+    run(pool, move |conn| {
+        let link = conn.transaction(|conn| {
+            let target = publication_links::table
+                .filter(publication_links::publication.eq(publication))
+                .filter(publication_links::uuid.eq(id));
+
+            // The last check was of the old URL
+            diesel::update(target.filter(publication_links::url.ne(&link.url)))
+                .set((
+                    publication_links::checked_at.eq(None::<DateTime<Utc>>),
+                    publication_links::status.eq(None::<i32>),
+                    publication_links::error.eq(None::<String>),
+                ))
+                .execute(conn)?;
+
+            diesel::update(target)
+                .set(&link)
+                .returning(PublicationLink::as_returning())
+                .get_result(conn)
+                .optional()
+        })?;
+        Ok(link)
+    })
+    .await
+}
+
+/// Whether the link existed.
+pub async fn delete(pool: &DbPool, publication: Uuid, id: Uuid) -> Result<bool, ApiError> {
+    run(pool, move |conn| {
+        let deleted = diesel::delete(
+            publication_links::table
+                .filter(publication_links::publication.eq(publication))
+                .filter(publication_links::uuid.eq(id)),
+        )
+        .execute(conn)?;
+        Ok(deleted > 0)
+    })
+    .await
+}
+
+/// Makes `links` the links of the publication, in their order, in the
+/// transaction of the caller. Links with the same URLs as before keep the
+/// results of their checks.
+pub fn replace(
+    conn: &mut PgConnection,
+    publication: Uuid,
+    links: &[NewPublicationLink],
+) -> QueryResult<()> {
+    replace_with(conn, publication, links, true)
+}
+
+/// Like [`replace`] for the links of an imported record, which only has the
+/// URLs: links that were there keep the kind, label and access set for them.
+pub fn replace_imported(
+    conn: &mut PgConnection,
+    publication: Uuid,
+    links: &[NewPublicationLink],
+) -> QueryResult<()> {
+    replace_with(conn, publication, links, false)
+}
+
+fn replace_with(
+    conn: &mut PgConnection,
+    publication: Uuid,
+    links: &[NewPublicationLink],
+    overwrite: bool,
+) -> QueryResult<()> {
+    let urls = links
+        .iter()
+        .map(|link| link.url.as_str())
+        .collect::<Vec<_>>();
+    diesel::delete(
+        publication_links::table
+            .filter(publication_links::publication.eq(publication))
+            .filter(publication_links::url.ne_all(&urls)),
+    )
+    .execute(conn)?;
+
+    for (position, link) in links.iter().enumerate() {
+        let position = publication_links::position.eq(position as i32);
+        let insert = diesel::insert_into(publication_links::table)
+            .values((
+                publication_links::publication.eq(publication),
+                position,
+                link,
+            ))
+            .on_conflict((publication_links::publication, publication_links::url))
+            .do_update();
+        if overwrite {
+            insert.set((link, position)).execute(conn)?;
+        } else {
+            insert.set(position).execute(conn)?;
+        }
+    }
+    Ok(())
+}
+
+/// Up to `limit` links never checked or last checked before `before`, the
+/// oldest first, as `(id, url)`.
+pub fn due(
+    conn: &mut PgConnection,
+    before: DateTime<Utc>,
+    limit: i64,
+) -> QueryResult<Vec<(Uuid, String)>> {
+    publication_links::table
+        .filter(
+            publication_links::checked_at
+                .is_null()
+                .or(publication_links::checked_at.lt(before)),
+        )
+        .order(publication_links::checked_at.asc().nulls_first())
+        .limit(limit)
+        .select((publication_links::uuid, publication_links::url))
+        .load(conn)
+}
+
+/// Records the check of the link, returns it unless it was deleted meanwhile.
+pub fn save_check(
+    conn: &mut PgConnection,
+    id: Uuid,
+    check: &LinkCheck,
+) -> QueryResult<Option<PublicationLink>> {
+    diesel::update(publication_links::table.find(id))
+        .set(check)
+        .returning(PublicationLink::as_returning())
+        .get_result(conn)
+        .optional()
+}
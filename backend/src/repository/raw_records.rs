@@ -0,0 +1,50 @@
+use ::uuid::Uuid;
+use diesel::dsl::now;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::models::{NewDigitalPublication, NewRawRecord, RawRecord};
+use crate::schema::raw_records;
+
+use super::run;
+
+pub async fn find(pool: &DbPool, publication: Uuid) -> Result<Option<RawRecord>, ApiError> {
+    run(pool, move |conn| {
+        Ok(raw_records::table
+            .find(publication)
+            .first::<RawRecord>(conn)
+            .optional()?)
+    })
+    .await
+}
+
+/// Keeps the record the publication `id` was just imported from, replacing
+/// the one of an earlier import.
+pub fn save(
+    conn: &mut PgConnection,
+    id: Uuid,
+    publication: &NewDigitalPublication,
+    record: &NewRawRecord,
+) -> QueryResult<()> {
+    // Publications without a record ID aren't imported
+    let record_id = publication.record_id.clone().unwrap_or_default();
+    diesel::insert_into(raw_records::table)
+        .values((
+            raw_records::publication.eq(id),
+            raw_records::source.eq(publication.source),
+            raw_records::record_id.eq(&record_id),
+            record,
+        ))
+        .on_conflict(raw_records::publication)
+        .do_update()
+        .set((
+            raw_records::source.eq(publication.source),
+            raw_records::record_id.eq(&record_id),
+            record,
+            raw_records::updated_at.eq(now),
+        ))
+        .execute(conn)?;
+    Ok(())
+}
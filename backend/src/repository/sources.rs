@@ -0,0 +1,63 @@
+use diesel::prelude::*;
+use ::uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error::ApiError;
+use crate::models::{NewSource, Source};
+use crate::schema::sources::dsl::*;
+
+use super::{run, Page};
+
+/// Page of the sources by name, and their total number.
+pub async fn list(pool: &DbPool, page: Page) -> Result<(Vec<Source>, i64), ApiError> {
+    run(pool, move |conn| {
+        let total = sources.count().get_result::<i64>(conn)?;
+        let list = sources
+            .select((uuid, name))
+            .order((name, uuid))
+            .limit(page.limit())
+            .offset(page.offset())
+            .load::<Source>(conn)?;
+
+        Ok((list, total))
+    })
+    .await
+}
+
+pub async fn find(pool: &DbPool, id: Uuid) -> Result<Option<Source>, ApiError> {
+    run(pool, move |conn| {
+        Ok(sources
+            .filter(uuid.eq(id))
+            .first::<Source>(conn)
+            .optional()?)
+    })
+    .await
+}
+
+pub async fn insert(pool: &DbPool, new_source: NewSource) -> Result<(), ApiError> {
+    run(pool, move |conn| {
+        diesel::insert_into(sources).values(&new_source).execute(conn)?;
+        Ok(())
+    })
+    .await
+}
+
+/// Whether the source existed.
+pub async fn update(pool: &DbPool, id: Uuid, new_source: NewSource) -> Result<bool, ApiError> {
+    run(pool, move |conn| {
+        let updated = diesel::update(sources.filter(uuid.eq(id)))
+            .set(name.eq(&new_source.name))
+            .execute(conn)?;
+        Ok(updated > 0)
+    })
+    .await
+}
+
+/// Whether the source existed.
+pub async fn delete(pool: &DbPool, id: Uuid) -> Result<bool, ApiError> {
+    run(pool, move |conn| {
+        let deleted = diesel::delete(sources.filter(uuid.eq(id))).execute(conn)?;
+        Ok(deleted > 0)
+    })
+    .await
+}
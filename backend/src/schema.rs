@@ -1,5 +1,13 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    authors (uuid) {
+        uuid -> Uuid,
+        name -> Varchar,
+        organization -> Bool,
+    }
+}
+
 diesel::table! {
     digital_publications (uuid) {
         uuid -> Uuid,
@@ -7,7 +15,89 @@ diesel::table! {
         name -> Varchar,
         description -> Varchar,
         cover_url -> Varchar,
-        links -> Array<Nullable<Text>>,
+        created_at -> Timestamptz,
+        record_id -> Nullable<Varchar>,
+        isbns -> Array<Nullable<Text>>,
+        year -> Nullable<Int4>,
+        publisher -> Nullable<Varchar>,
+        language -> Nullable<Varchar>,
+        edition -> Nullable<Varchar>,
+        pages -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    import_job_errors (id) {
+        id -> Int4,
+        job -> Uuid,
+        position -> Int4,
+        record_id -> Nullable<Varchar>,
+        error -> Varchar,
+    }
+}
+
+diesel::table! {
+    import_jobs (uuid) {
+        uuid -> Uuid,
+        source -> Uuid,
+        path -> Varchar,
+        format -> Varchar,
+        status -> Varchar,
+        error -> Nullable<Varchar>,
+        total -> Nullable<Int4>,
+        processed -> Int4,
+        created -> Int4,
+        updated -> Int4,
+        failed -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        filter -> Nullable<Varchar>,
+        skipped -> Int4,
+    }
+}
+
+diesel::table! {
+    publication_authors (publication, author, role) {
+        publication -> Uuid,
+        author -> Uuid,
+        role -> Varchar,
+        position -> Int4,
+    }
+}
+
+diesel::table! {
+    publication_duplicates (publication, duplicate_of) {
+        publication -> Uuid,
+        duplicate_of -> Uuid,
+        reason -> Varchar,
+        confidence -> Float4,
+        found_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    publication_links (uuid) {
+        uuid -> Uuid,
+        publication -> Uuid,
+        url -> Varchar,
+        kind -> Varchar,
+        label -> Nullable<Varchar>,
+        access -> Varchar,
+        position -> Int4,
+        checked_at -> Nullable<Timestamptz>,
+        status -> Nullable<Int4>,
+        error -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    raw_records (publication) {
+        publication -> Uuid,
+        source -> Uuid,
+        record_id -> Varchar,
+        version -> Nullable<Varchar>,
+        fields -> Jsonb,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -19,8 +109,22 @@ diesel::table! {
 }
 
 diesel::joinable!(digital_publications -> sources (source));
+diesel::joinable!(import_job_errors -> import_jobs (job));
+diesel::joinable!(import_jobs -> sources (source));
+diesel::joinable!(publication_authors -> authors (author));
+diesel::joinable!(publication_authors -> digital_publications (publication));
+diesel::joinable!(publication_links -> digital_publications (publication));
+diesel::joinable!(raw_records -> digital_publications (publication));
+diesel::joinable!(raw_records -> sources (source));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    authors,
     digital_publications,
+    import_job_errors,
+    import_jobs,
+    publication_authors,
+    publication_duplicates,
+    publication_links,
+    raw_records,
     sources,
 );
@@ -1,11 +1,23 @@
-
 use crate::{db::DbPool, models::NewSource};
-use crate::models::Source;
-use crate::schema::sources::dsl::*;
-use diesel::prelude::*;
-use actix_web::{web, HttpResponse, Responder};
+use crate::error::ApiError;
+use crate::import::{self, worker::ImportQueue};
+use crate::models::NewImportJob;
+use crate::repository::import_jobs;
+use crate::repository::sources as repository;
+use crate::repository::Page;
+use actix_web::{web, HttpResponse};
+use futures_util::StreamExt;
+use rusmarc_raw::formats::Format;
+use rusmarc_raw::query::Query;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
 use ::uuid;
 
+/// Bytes of an upload the format is guessed by.
+const FORMAT_PREFIX: usize = 1024;
+
 pub fn config(cfg: &mut web::ServiceConfig, base_path: &str) {
     cfg.service(
         web::resource(format!("{}{}", base_path, "sources"))
@@ -18,85 +30,140 @@ pub fn config(cfg: &mut web::ServiceConfig, base_path: &str) {
             .route(web::put().to(update))
             .route(web::delete().to(delete)),
     )
+    .service(
+        web::resource(format!("{}{}", base_path, "sources/{id}/import"))
+            .route(web::post().to(import)),
+    )
     ;
 }
 
 
+/// `?limit=&offset=`; the number of all sources is in `X-Total-Count`.
 async fn get_all(
-    pool: web::Data<DbPool>
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-
-    let list = sources
-        .select((uuid, name))
-        .load::<Source>(&mut conn)
-        .expect("Error loading sources list");
+    pool: web::Data<DbPool>,
+    page: web::Query<Page>,
+) -> Result<HttpResponse, ApiError> {
+    let (list, total) = repository::list(&pool, page.into_inner()).await?;
 
-    HttpResponse::Ok().json(list)
+    Ok(HttpResponse::Ok()
+        .insert_header(("X-Total-Count", total))
+        .json(list))
 }
 
 async fn create(
     pool: web::Data<DbPool>,
     publication_data: web::Json<NewSource>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+) -> Result<HttpResponse, ApiError> {
+    repository::insert(&pool, publication_data.into_inner()).await?;
 
-    let new_publication = NewSource {
-        name: publication_data.name.clone(),
-    };
-
-    diesel::insert_into(sources)
-        .values(&new_publication)
-        .execute(&mut conn)
-        .expect("Error saving new source");
-
-    HttpResponse::Created().finish()
+    Ok(HttpResponse::Created().finish())
 }
 
 async fn get(
     pool: web::Data<DbPool>,
     pub_id: web::Path<uuid::Uuid>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-
-    let publication = sources
-        .filter(uuid.eq(pub_id.into_inner()))
-        .first::<Source>(&mut conn)
-        .optional()
-        .expect("Error loading source");
-
-    match publication {
-        Some(publication) => HttpResponse::Ok().json(publication),
-        None => HttpResponse::NotFound().finish(),
-    }
+) -> Result<HttpResponse, ApiError> {
+    let publication = repository::find(&pool, pub_id.into_inner())
+        .await?
+        .ok_or_else(|| ApiError::not_found("Source not found"))?;
+
+    Ok(HttpResponse::Ok().json(publication))
 }
 
 async fn update(
     pool: web::Data<DbPool>,
     pub_id: web::Path<uuid::Uuid>,
     publication_data: web::Json<NewSource>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
-
-    diesel::update(sources.filter(uuid.eq(pub_id.into_inner())))
-        .set((
-            name.eq(&publication_data.name),
-        ))
-        .execute(&mut conn)
-        .expect("Error updating source");
-
-    HttpResponse::Ok().finish()
+) -> Result<HttpResponse, ApiError> {
+    if !repository::update(&pool, pub_id.into_inner(), publication_data.into_inner()).await? {
+        return Err(ApiError::not_found("Source not found"));
+    }
+    Ok(HttpResponse::Ok().finish())
 }
 
 async fn delete(
     pool: web::Data<DbPool>,
     pub_id: web::Path<uuid::Uuid>,
-) -> impl Responder {
-    let mut conn = pool.get().expect("couldn't get db connection from pool");
+) -> Result<HttpResponse, ApiError> {
+    if !repository::delete(&pool, pub_id.into_inner()).await? {
+        return Err(ApiError::not_found("Source not found"));
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
 
-    diesel::delete(sources.filter(uuid.eq(pub_id.into_inner())))
-        .execute(&mut conn)
-        .expect("Error deleting source");
+#[derive(Deserialize)]
+struct ImportQuery {
+    /// text, iso2709, marcxml or json; guessed by the content if missing
+    format: Option<String>,
+    /// Only the records that match the query are imported, e.g. `101$a = rus`,
+    /// see `rusmarc_raw::query`; the others are counted as skipped
+    filter: Option<String>,
+}
+
+/// Saves the RUSMARC dump in the body and queues a job that creates or
+/// updates, by record ID, the publications of the source. Answers 202 with
+/// the job, see `GET /import_jobs/{id}`.
+async fn import(
+    pool: web::Data<DbPool>,
+    queue: web::Data<ImportQueue>,
+    source_id: web::Path<uuid::Uuid>,
+    query: web::Query<ImportQuery>,
+    payload: web::Payload,
+) -> Result<HttpResponse, ApiError> {
+    let source_id = source_id.into_inner();
+    repository::find(&pool, source_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Source not found"))?;
+    let format = query
+        .format
+        .as_deref()
+        .map(str::parse::<Format>)
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+    if let Some(filter) = &query.filter {
+        Query::parse(filter)
+            .map_err(|err| ApiError::bad_request(format!("Invalid filter: {err}")))?;
+    }
+
+    let job_id = uuid::Uuid::new_v4();
+    let path = queue.dump_path(job_id);
+    let prefix = match save_upload(payload, path.clone()).await {
+        Ok(prefix) if !prefix.is_empty() => prefix,
+        result => {
+            let _ = web::block(move || std::fs::remove_file(path)).await;
+            result?;
+            return Err(ApiError::bad_request("Dump is empty"));
+        }
+    };
+
+    let job = import_jobs::insert(&pool, NewImportJob {
+        uuid: job_id,
+        source: source_id,
+        path: path.to_string_lossy().into_owned(),
+        format: format.unwrap_or_else(|| import::guess_format(&prefix)).to_string(),
+        filter: query.into_inner().filter,
+    }).await?;
+    queue.notify();
+
+    Ok(HttpResponse::Accepted()
+        .insert_header(("Location", format!("/api/v1/import_jobs/{}", job.uuid)))
+        .json(job))
+}
+
+/// Writes the body to `path` as it arrives, returns its first bytes.
+async fn save_upload(mut payload: web::Payload, path: PathBuf) -> Result<Vec<u8>, ApiError> {
+    let mut file = web::block(move || File::create(path)).await??;
+    let mut prefix = Vec::new();
+
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|err| ApiError::bad_request(err.to_string()))?;
+        if prefix.len() < FORMAT_PREFIX {
+            let wanted = (FORMAT_PREFIX - prefix.len()).min(chunk.len());
+            prefix.extend_from_slice(&chunk[..wanted]);
+        }
+        file = web::block(move || file.write_all(&chunk).map(|()| file)).await??;
+    }
 
-    HttpResponse::NoContent().finish()
+    web::block(move || file.sync_all()).await??;
+    Ok(prefix)
 }
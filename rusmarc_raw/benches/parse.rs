@@ -0,0 +1,74 @@
+use std::io::Cursor;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use rusmarc_raw::parallel::typed_records;
+use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+use rusmarc_raw::typed_record::TypedRecord;
+
+const RECORDS: usize = 20_000;
+
+/// Dump shaped like the Lan export.
+fn dump() -> String {
+    (0..RECORDS)
+        .map(|i| {
+            format!(
+                "#1: lan-{i}\n\
+                 #5: 20240115093000.0\n\
+                 #10: ^A978-5-8114-{:04}-0^DБ.ц.\n\
+                 #100: ^A20240115d2023    k  y0rusy50      ca\n\
+                 #101: ^Arus\n\
+                 #102: ^ARU\n\
+                 #200: ^AОсновы программирования. Часть {i}^FИванов И. И.^GПетров П. П.\n\
+                 #210: ^AСанкт-Петербург^CЛань^D2023\n\
+                 #215: ^A{} с.\n\
+                 #330: ^AУчебное пособие содержит теоретический материал и практические задания.\n\
+                 #606: ^AПрограммирование^XУчебные издания\n\
+                 #700: ^AИванов^BИ. И.\n\
+                 #856: ^Uhttps://e.lanbook.com/book/{i}\n\
+                 *****\n",
+                i % 10_000,
+                100 + i % 400,
+            )
+        })
+        .collect()
+}
+
+fn parse(c: &mut Criterion) {
+    let data = dump();
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.sample_size(10);
+
+    group.bench_function("read", |b| {
+        b.iter(|| {
+            for record in RecordsReader::new(Cursor::new(&data)) {
+                black_box(record.unwrap());
+            }
+        })
+    });
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            for record in RecordsReader::new(Cursor::new(&data)) {
+                black_box(TypedRecord::parse(
+                    record_remove_errors(record.unwrap()).into_iter(),
+                ));
+            }
+        })
+    });
+
+    group.bench_function("pipeline", |b| {
+        b.iter(|| {
+            for record in typed_records(Cursor::new(&data)) {
+                black_box(record.unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
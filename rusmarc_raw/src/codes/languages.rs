@@ -0,0 +1,518 @@
+//! ISO 639-2/B language codes.
+
+/// `(code, russian name, english name)`, ordered by code.
+#[rustfmt::skip]
+pub(super) const LANGUAGES: &[(&str, &str, &str)] = &[
+    ("aar", "Афарский", "Afar"),
+    ("abk", "Абхазский", "Abkhazian"),
+    ("ace", "Ачехский", "Achinese"),
+    ("ach", "Ачоли", "Acoli"),
+    ("ada", "Адангме", "Adangme"),
+    ("ady", "Адыгейский", "Adyghe"),
+    ("afa", "Афразийские языки", "Afro-Asiatic languages"),
+    ("afh", "Африхили", "Afrihili"),
+    ("afr", "Африкаанс", "Afrikaans"),
+    ("ain", "Айнский", "Ainu"),
+    ("aka", "Акан", "Akan"),
+    ("akk", "Аккадский", "Akkadian"),
+    ("alb", "Албанский", "Albanian"),
+    ("ale", "Алеутский", "Aleut"),
+    ("alg", "Алгонкинские языки", "Algonquian languages"),
+    ("alt", "Южноалтайский", "Southern Altai"),
+    ("amh", "Амхарский", "Amharic"),
+    ("ang", "Древнеанглийский", "English, Old (ca.450-1100)"),
+    ("anp", "Ангика", "Angika"),
+    ("apa", "Апачские языки", "Apache languages"),
+    ("ara", "Арабский", "Arabic"),
+    ("arc", "Арамейский", "Official Aramaic (700-300 BCE)"),
+    ("arg", "Арагонский", "Aragonese"),
+    ("arm", "Армянский", "Armenian"),
+    ("arn", "Мапудунгун", "Mapudungun"),
+    ("arp", "Арапахо", "Arapaho"),
+    ("art", "Искусственные языки", "Artificial languages"),
+    ("arw", "Аравакский", "Arawak"),
+    ("asm", "Ассамский", "Assamese"),
+    ("ast", "Астурийский", "Asturian"),
+    ("ath", "Атапаскские языки", "Athapascan languages"),
+    ("aus", "Австралийские языки", "Australian languages"),
+    ("ava", "Аварский", "Avaric"),
+    ("ave", "Авестийский", "Avestan"),
+    ("awa", "Авадхи", "Awadhi"),
+    ("aym", "Аймара", "Aymara"),
+    ("aze", "Азербайджанский", "Azerbaijani"),
+    ("bad", "Банда", "Banda languages"),
+    ("bai", "Бамилеке", "Bamileke languages"),
+    ("bak", "Башкирский", "Bashkir"),
+    ("bal", "Белуджский", "Baluchi"),
+    ("bam", "Бамбара", "Bambara"),
+    ("ban", "Балийский", "Balinese"),
+    ("baq", "Баскский", "Basque"),
+    ("bas", "Баса", "Basa"),
+    ("bat", "Балтийские языки", "Baltic languages"),
+    ("bej", "Беджа", "Beja"),
+    ("bel", "Белорусский", "Belarusian"),
+    ("bem", "Бемба", "Bemba"),
+    ("ben", "Бенгальский", "Bengali"),
+    ("ber", "Берберские языки", "Berber languages"),
+    ("bho", "Бходжпури", "Bhojpuri"),
+    ("bih", "Бихарские языки", "Bihari languages"),
+    ("bik", "Бикольский", "Bikol"),
+    ("bin", "Бини", "Bini"),
+    ("bis", "Бислама", "Bislama"),
+    ("bla", "Сиксика", "Siksika"),
+    ("bnt", "Языки банту", "Bantu languages"),
+    ("bos", "Боснийский", "Bosnian"),
+    ("bra", "Брадж", "Braj"),
+    ("bre", "Бретонский", "Breton"),
+    ("btk", "Батакские языки", "Batak languages"),
+    ("bua", "Бурятский", "Buriat"),
+    ("bug", "Бугийский", "Buginese"),
+    ("bul", "Болгарский", "Bulgarian"),
+    ("bur", "Бирманский", "Burmese"),
+    ("byn", "Билин", "Blin"),
+    ("cad", "Каддо", "Caddo"),
+    ("cai", "Языки индейцев Центральной Америки", "Central American Indian languages"),
+    ("car", "Карибский", "Galibi Carib"),
+    ("cat", "Каталанский", "Catalan"),
+    ("cau", "Кавказские языки", "Caucasian languages"),
+    ("ceb", "Себуано", "Cebuano"),
+    ("cel", "Кельтские языки", "Celtic languages"),
+    ("cha", "Чаморро", "Chamorro"),
+    ("chb", "Чибча", "Chibcha"),
+    ("che", "Чеченский", "Chechen"),
+    ("chg", "Чагатайский", "Chagatai"),
+    ("chi", "Китайский", "Chinese"),
+    ("chk", "Трукский", "Chuukese"),
+    ("chm", "Марийский", "Mari"),
+    ("chn", "Чинукский жаргон", "Chinook jargon"),
+    ("cho", "Чокто", "Choctaw"),
+    ("chp", "Чипевьян", "Chipewyan"),
+    ("chr", "Чероки", "Cherokee"),
+    ("chu", "Церковнославянский", "Church Slavic"),
+    ("chv", "Чувашский", "Chuvash"),
+    ("chy", "Шайенский", "Cheyenne"),
+    ("cmc", "Чамские языки", "Chamic languages"),
+    ("cnr", "Черногорский", "Montenegrin"),
+    ("cop", "Коптский", "Coptic"),
+    ("cor", "Корнский", "Cornish"),
+    ("cos", "Корсиканский", "Corsican"),
+    ("cpe", "Креольские языки и пиджины на основе английского", "Creoles and pidgins, English based"),
+    ("cpf", "Креольские языки и пиджины на основе французского", "Creoles and pidgins, French-based"),
+    ("cpp", "Креольские языки и пиджины на основе португальского", "Creoles and pidgins, Portuguese-based"),
+    ("cre", "Кри", "Cree"),
+    ("crh", "Крымскотатарский", "Crimean Tatar"),
+    ("crp", "Креольские языки и пиджины", "Creoles and pidgins"),
+    ("csb", "Кашубский", "Kashubian"),
+    ("cus", "Кушитские языки", "Cushitic languages"),
+    ("cze", "Чешский", "Czech"),
+    ("dak", "Дакота", "Dakota"),
+    ("dan", "Датский", "Danish"),
+    ("dar", "Даргинский", "Dargwa"),
+    ("day", "Даякские языки", "Land Dayak languages"),
+    ("del", "Делаварский", "Delaware"),
+    ("den", "Слейви", "Slave (Athapascan)"),
+    ("dgr", "Догриб", "Tlicho; Dogrib"),
+    ("din", "Динка", "Dinka"),
+    ("div", "Мальдивский", "Divehi"),
+    ("doi", "Догри", "Dogri"),
+    ("dra", "Дравидийские языки", "Dravidian languages"),
+    ("dsb", "Нижнелужицкий", "Lower Sorbian"),
+    ("dua", "Дуала", "Duala"),
+    ("dum", "Средненидерландский", "Dutch, Middle (ca.1050-1350)"),
+    ("dut", "Нидерландский", "Dutch"),
+    ("dyu", "Дьюла", "Dyula"),
+    ("dzo", "Дзонг-кэ", "Dzongkha"),
+    ("efi", "Эфик", "Efik"),
+    ("egy", "Древнеегипетский", "Egyptian (Ancient)"),
+    ("eka", "Экаджук", "Ekajuk"),
+    ("elx", "Эламский", "Elamite"),
+    ("eng", "Английский", "English"),
+    ("enm", "Среднеанглийский", "English, Middle (1100-1500)"),
+    ("epo", "Эсперанто", "Esperanto"),
+    ("est", "Эстонский", "Estonian"),
+    ("ewe", "Эве", "Ewe"),
+    ("ewo", "Эвондо", "Ewondo"),
+    ("fan", "Фанг", "Fang"),
+    ("fao", "Фарерский", "Faroese"),
+    ("fat", "Фанти", "Fanti"),
+    ("fij", "Фиджийский", "Fijian"),
+    ("fil", "Филиппинский", "Filipino"),
+    ("fin", "Финский", "Finnish"),
+    ("fiu", "Финно-угорские языки", "Finno-Ugrian languages"),
+    ("fon", "Фон", "Fon"),
+    ("fre", "Французский", "French"),
+    ("frm", "Среднефранцузский", "French, Middle (ca.1400-1600)"),
+    ("fro", "Старофранцузский", "French, Old (842-ca.1400)"),
+    ("frr", "Северофризский", "Northern Frisian"),
+    ("frs", "Восточнофризский", "Eastern Frisian"),
+    ("fry", "Западнофризский", "Western Frisian"),
+    ("ful", "Фула", "Fulah"),
+    ("fur", "Фриульский", "Friulian"),
+    ("gaa", "Га", "Ga"),
+    ("gay", "Гайо", "Gayo"),
+    ("gba", "Гбайя", "Gbaya"),
+    ("gem", "Германские языки", "Germanic languages"),
+    ("geo", "Грузинский", "Georgian"),
+    ("ger", "Немецкий", "German"),
+    ("gez", "Геэз", "Geez"),
+    ("gil", "Кирибати", "Gilbertese"),
+    ("gla", "Гэльский", "Gaelic"),
+    ("gle", "Ирландский", "Irish"),
+    ("glg", "Галисийский", "Galician"),
+    ("glv", "Мэнский", "Manx"),
+    ("gmh", "Средневерхненемецкий", "German, Middle High (ca.1050-1500)"),
+    ("goh", "Древневерхненемецкий", "German, Old High (ca.750-1050)"),
+    ("gon", "Гонди", "Gondi"),
+    ("gor", "Горонтало", "Gorontalo"),
+    ("got", "Готский", "Gothic"),
+    ("grb", "Гребо", "Grebo"),
+    ("grc", "Древнегреческий", "Greek, Ancient (to 1453)"),
+    ("gre", "Новогреческий", "Greek, Modern (1453-)"),
+    ("grn", "Гуарани", "Guarani"),
+    ("gsw", "Швейцарский немецкий", "Swiss German"),
+    ("guj", "Гуджарати", "Gujarati"),
+    ("gwi", "Гвичин", "Gwich'in"),
+    ("hai", "Хайда", "Haida"),
+    ("hat", "Гаитянский", "Haitian"),
+    ("hau", "Хауса", "Hausa"),
+    ("haw", "Гавайский", "Hawaiian"),
+    ("heb", "Иврит", "Hebrew"),
+    ("her", "Гереро", "Herero"),
+    ("hil", "Хилигайнон", "Hiligaynon"),
+    ("him", "Химачали", "Himachali languages"),
+    ("hin", "Хинди", "Hindi"),
+    ("hit", "Хеттский", "Hittite"),
+    ("hmn", "Хмонг", "Hmong"),
+    ("hmo", "Хиримоту", "Hiri Motu"),
+    ("hrv", "Хорватский", "Croatian"),
+    ("hsb", "Верхнелужицкий", "Upper Sorbian"),
+    ("hun", "Венгерский", "Hungarian"),
+    ("hup", "Хупа", "Hupa"),
+    ("iba", "Ибанский", "Iban"),
+    ("ibo", "Игбо", "Igbo"),
+    ("ice", "Исландский", "Icelandic"),
+    ("ido", "Идо", "Ido"),
+    ("iii", "Носу", "Sichuan Yi"),
+    ("ijo", "Иджо", "Ijo languages"),
+    ("iku", "Инуктитут", "Inuktitut"),
+    ("ile", "Интерлингве", "Interlingue"),
+    ("ilo", "Илоканский", "Iloko"),
+    ("ina", "Интерлингва", "Interlingua"),
+    ("inc", "Индоарийские языки", "Indic languages"),
+    ("ind", "Индонезийский", "Indonesian"),
+    ("ine", "Индоевропейские языки", "Indo-European languages"),
+    ("inh", "Ингушский", "Ingush"),
+    ("ipk", "Инупиак", "Inupiaq"),
+    ("ira", "Иранские языки", "Iranian languages"),
+    ("iro", "Ирокезские языки", "Iroquoian languages"),
+    ("ita", "Итальянский", "Italian"),
+    ("jav", "Яванский", "Javanese"),
+    ("jbo", "Ложбан", "Lojban"),
+    ("jpn", "Японский", "Japanese"),
+    ("jpr", "Еврейско-персидский", "Judeo-Persian"),
+    ("jrb", "Еврейско-арабский", "Judeo-Arabic"),
+    ("kaa", "Каракалпакский", "Kara-Kalpak"),
+    ("kab", "Кабильский", "Kabyle"),
+    ("kac", "Качинский", "Kachin"),
+    ("kal", "Гренландский", "Kalaallisut"),
+    ("kam", "Камба", "Kamba"),
+    ("kan", "Каннада", "Kannada"),
+    ("kar", "Каренские языки", "Karen languages"),
+    ("kas", "Кашмирский", "Kashmiri"),
+    ("kau", "Канури", "Kanuri"),
+    ("kaw", "Кави", "Kawi"),
+    ("kaz", "Казахский", "Kazakh"),
+    ("kbd", "Кабардино-черкесский", "Kabardian"),
+    ("kha", "Кхаси", "Khasi"),
+    ("khi", "Койсанские языки", "Khoisan languages"),
+    ("khm", "Кхмерский", "Central Khmer"),
+    ("kho", "Хотаносакский", "Khotanese"),
+    ("kik", "Кикуйю", "Kikuyu"),
+    ("kin", "Руанда", "Kinyarwanda"),
+    ("kir", "Киргизский", "Kirghiz"),
+    ("kmb", "Кимбунду", "Kimbundu"),
+    ("kok", "Конкани", "Konkani"),
+    ("kom", "Коми", "Komi"),
+    ("kon", "Конго", "Kongo"),
+    ("kor", "Корейский", "Korean"),
+    ("kos", "Косраэ", "Kosraean"),
+    ("kpe", "Кпелле", "Kpelle"),
+    ("krc", "Карачаево-балкарский", "Karachay-Balkar"),
+    ("krl", "Карельский", "Karelian"),
+    ("kro", "Кру", "Kru languages"),
+    ("kru", "Курух", "Kurukh"),
+    ("kua", "Кваньяма", "Kuanyama"),
+    ("kum", "Кумыкский", "Kumyk"),
+    ("kur", "Курдский", "Kurdish"),
+    ("kut", "Кутенай", "Kutenai"),
+    ("lad", "Ладино", "Ladino"),
+    ("lah", "Лахнда", "Lahnda"),
+    ("lam", "Ламба", "Lamba"),
+    ("lao", "Лаосский", "Lao"),
+    ("lat", "Латинский", "Latin"),
+    ("lav", "Латышский", "Latvian"),
+    ("lez", "Лезгинский", "Lezghian"),
+    ("lim", "Лимбургский", "Limburgan"),
+    ("lin", "Лингала", "Lingala"),
+    ("lit", "Литовский", "Lithuanian"),
+    ("lol", "Монго", "Mongo"),
+    ("loz", "Лози", "Lozi"),
+    ("ltz", "Люксембургский", "Luxembourgish"),
+    ("lua", "Луба-лулуа", "Luba-Lulua"),
+    ("lub", "Луба-катанга", "Luba-Katanga"),
+    ("lug", "Ганда", "Ganda"),
+    ("lui", "Луисеньо", "Luiseno"),
+    ("lun", "Лунда", "Lunda"),
+    ("luo", "Луо", "Luo (Kenya and Tanzania)"),
+    ("lus", "Лушей", "Lushai"),
+    ("mac", "Македонский", "Macedonian"),
+    ("mad", "Мадурский", "Madurese"),
+    ("mag", "Магахи", "Magahi"),
+    ("mah", "Маршалльский", "Marshallese"),
+    ("mai", "Майтхили", "Maithili"),
+    ("mak", "Макассарский", "Makasar"),
+    ("mal", "Малаялам", "Malayalam"),
+    ("man", "Мандинго", "Mandingo"),
+    ("mao", "Маори", "Maori"),
+    ("map", "Австронезийские языки", "Austronesian languages"),
+    ("mar", "Маратхи", "Marathi"),
+    ("mas", "Масаи", "Masai"),
+    ("may", "Малайский", "Malay"),
+    ("mdf", "Мокшанский", "Moksha"),
+    ("mdr", "Мандарский", "Mandar"),
+    ("men", "Менде", "Mende"),
+    ("mga", "Среднеирландский", "Irish, Middle (900-1200)"),
+    ("mic", "Микмак", "Mi'kmaq"),
+    ("min", "Минангкабау", "Minangkabau"),
+    ("mis", "Некодированные языки", "Uncoded languages"),
+    ("mkh", "Мон-кхмерские языки", "Mon-Khmer languages"),
+    ("mlg", "Малагасийский", "Malagasy"),
+    ("mlt", "Мальтийский", "Maltese"),
+    ("mnc", "Маньчжурский", "Manchu"),
+    ("mni", "Манипури", "Manipuri"),
+    ("mno", "Манобо", "Manobo languages"),
+    ("moh", "Мохаук", "Mohawk"),
+    ("mon", "Монгольский", "Mongolian"),
+    ("mos", "Мооре", "Mossi"),
+    ("mul", "Несколько языков", "Multiple languages"),
+    ("mun", "Языки мунда", "Munda languages"),
+    ("mus", "Крик", "Creek"),
+    ("mwl", "Мирандский", "Mirandese"),
+    ("mwr", "Марвари", "Marwari"),
+    ("myn", "Майяские языки", "Mayan languages"),
+    ("myv", "Эрзянский", "Erzya"),
+    ("nah", "Науатль", "Nahuatl languages"),
+    ("nai", "Языки индейцев Северной Америки", "North American Indian languages"),
+    ("nap", "Неаполитанский", "Neapolitan"),
+    ("nau", "Науруанский", "Nauru"),
+    ("nav", "Навахо", "Navajo"),
+    ("nbl", "Южный ндебеле", "Ndebele, South"),
+    ("nde", "Северный ндебеле", "Ndebele, North"),
+    ("ndo", "Ндонга", "Ndonga"),
+    ("nds", "Нижненемецкий", "Low German"),
+    ("nep", "Непальский", "Nepali"),
+    ("new", "Неварский", "Nepal Bhasa"),
+    ("nia", "Ниас", "Nias"),
+    ("nic", "Нигеро-кордофанские языки", "Niger-Kordofanian languages"),
+    ("niu", "Ниуэ", "Niuean"),
+    ("nno", "Нюнорск", "Norwegian Nynorsk"),
+    ("nob", "Букмол", "Norwegian Bokmål"),
+    ("nog", "Ногайский", "Nogai"),
+    ("non", "Древнескандинавский", "Norse, Old"),
+    ("nor", "Норвежский", "Norwegian"),
+    ("nqo", "Нко", "N'Ko"),
+    ("nso", "Северный сото", "Pedi"),
+    ("nub", "Нубийские языки", "Nubian languages"),
+    ("nwc", "Классический неварский", "Classical Newari"),
+    ("nya", "Ньянджа", "Chichewa"),
+    ("nym", "Ньямвези", "Nyamwezi"),
+    ("nyn", "Ньянколе", "Nyankole"),
+    ("nyo", "Ньоро", "Nyoro"),
+    ("nzi", "Нзима", "Nzima"),
+    ("oci", "Окситанский", "Occitan (post 1500)"),
+    ("oji", "Оджибве", "Ojibwa"),
+    ("ori", "Ория", "Oriya"),
+    ("orm", "Оромо", "Oromo"),
+    ("osa", "Осейдж", "Osage"),
+    ("oss", "Осетинский", "Ossetian"),
+    ("ota", "Османский", "Turkish, Ottoman (1500-1928)"),
+    ("oto", "Отомийские языки", "Otomian languages"),
+    ("paa", "Папуасские языки", "Papuan languages"),
+    ("pag", "Пангасинан", "Pangasinan"),
+    ("pal", "Пехлеви", "Pahlavi"),
+    ("pam", "Пампанга", "Pampanga"),
+    ("pan", "Панджаби", "Panjabi"),
+    ("pap", "Папьяменто", "Papiamento"),
+    ("pau", "Палау", "Palauan"),
+    ("peo", "Древнеперсидский", "Persian, Old (ca.600-400 B.C.)"),
+    ("per", "Персидский", "Persian"),
+    ("phi", "Филиппинские языки", "Philippine languages"),
+    ("phn", "Финикийский", "Phoenician"),
+    ("pli", "Пали", "Pali"),
+    ("pol", "Польский", "Polish"),
+    ("pon", "Понапе", "Pohnpeian"),
+    ("por", "Португальский", "Portuguese"),
+    ("pra", "Пракриты", "Prakrit languages"),
+    ("pro", "Старопровансальский", "Provençal, Old (to 1500)"),
+    ("pus", "Пушту", "Pushto"),
+    ("que", "Кечуа", "Quechua"),
+    ("raj", "Раджастхани", "Rajasthani"),
+    ("rap", "Рапануйский", "Rapanui"),
+    ("rar", "Раротонганский", "Rarotongan"),
+    ("roa", "Романские языки", "Romance languages"),
+    ("roh", "Романшский", "Romansh"),
+    ("rom", "Цыганский", "Romany"),
+    ("rum", "Румынский", "Romanian"),
+    ("run", "Рунди", "Rundi"),
+    ("rup", "Арумынский", "Aromanian"),
+    ("rus", "Русский", "Russian"),
+    ("sad", "Сандаве", "Sandawe"),
+    ("sag", "Санго", "Sango"),
+    ("sah", "Якутский", "Yakut"),
+    ("sai", "Языки индейцев Южной Америки", "South American Indian languages"),
+    ("sal", "Салишские языки", "Salishan languages"),
+    ("sam", "Самаритянский арамейский", "Samaritan Aramaic"),
+    ("san", "Санскрит", "Sanskrit"),
+    ("sas", "Сасакский", "Sasak"),
+    ("sat", "Сантали", "Santali"),
+    ("scn", "Сицилийский", "Sicilian"),
+    ("sco", "Шотландский (англо-шотландский)", "Scots"),
+    ("sel", "Селькупский", "Selkup"),
+    ("sem", "Семитские языки", "Semitic languages"),
+    ("sga", "Древнеирландский", "Irish, Old (to 900)"),
+    ("sgn", "Жестовые языки", "Sign Languages"),
+    ("shn", "Шанский", "Shan"),
+    ("sid", "Сидамо", "Sidamo"),
+    ("sin", "Сингальский", "Sinhala"),
+    ("sio", "Сиуанские языки", "Siouan languages"),
+    ("sit", "Сино-тибетские языки", "Sino-Tibetan languages"),
+    ("sla", "Славянские языки", "Slavic languages"),
+    ("slo", "Словацкий", "Slovak"),
+    ("slv", "Словенский", "Slovenian"),
+    ("sma", "Южносаамский", "Southern Sami"),
+    ("sme", "Северносаамский", "Northern Sami"),
+    ("smi", "Саамские языки", "Sami languages"),
+    ("smj", "Луле-саамский", "Lule Sami"),
+    ("smn", "Инари-саамский", "Inari Sami"),
+    ("smo", "Самоанский", "Samoan"),
+    ("sms", "Колтта-саамский", "Skolt Sami"),
+    ("sna", "Шона", "Shona"),
+    ("snd", "Синдхи", "Sindhi"),
+    ("snk", "Сонинке", "Soninke"),
+    ("sog", "Согдийский", "Sogdian"),
+    ("som", "Сомали", "Somali"),
+    ("son", "Сонгай", "Songhai languages"),
+    ("sot", "Южный сото", "Sotho, Southern"),
+    ("spa", "Испанский", "Spanish"),
+    ("srd", "Сардинский", "Sardinian"),
+    ("srn", "Сранан-тонго", "Sranan Tongo"),
+    ("srp", "Сербский", "Serbian"),
+    ("srr", "Серер", "Serer"),
+    ("ssa", "Нило-сахарские языки", "Nilo-Saharan languages"),
+    ("ssw", "Свази", "Swati"),
+    ("suk", "Сукума", "Sukuma"),
+    ("sun", "Сунданский", "Sundanese"),
+    ("sus", "Сусу", "Susu"),
+    ("sux", "Шумерский", "Sumerian"),
+    ("swa", "Суахили", "Swahili"),
+    ("swe", "Шведский", "Swedish"),
+    ("syc", "Классический сирийский", "Classical Syriac"),
+    ("syr", "Сирийский", "Syriac"),
+    ("tah", "Таитянский", "Tahitian"),
+    ("tai", "Тайские языки", "Tai languages"),
+    ("tam", "Тамильский", "Tamil"),
+    ("tat", "Татарский", "Tatar"),
+    ("tel", "Телугу", "Telugu"),
+    ("tem", "Темне", "Timne"),
+    ("ter", "Терена", "Tereno"),
+    ("tet", "Тетум", "Tetum"),
+    ("tgk", "Таджикский", "Tajik"),
+    ("tgl", "Тагальский", "Tagalog"),
+    ("tha", "Тайский", "Thai"),
+    ("tib", "Тибетский", "Tibetan"),
+    ("tig", "Тигре", "Tigre"),
+    ("tir", "Тигринья", "Tigrinya"),
+    ("tiv", "Тив", "Tiv"),
+    ("tkl", "Токелау", "Tokelau"),
+    ("tlh", "Клингонский", "Klingon"),
+    ("tli", "Тлинкитский", "Tlingit"),
+    ("tmh", "Тамашек", "Tamashek"),
+    ("tog", "Тонга (Ньяса)", "Tonga (Nyasa)"),
+    ("ton", "Тонганский", "Tonga (Tonga Islands)"),
+    ("tpi", "Ток-писин", "Tok Pisin"),
+    ("tsi", "Цимшиан", "Tsimshian"),
+    ("tsn", "Тсвана", "Tswana"),
+    ("tso", "Тсонга", "Tsonga"),
+    ("tuk", "Туркменский", "Turkmen"),
+    ("tum", "Тумбука", "Tumbuka"),
+    ("tup", "Тупи", "Tupi languages"),
+    ("tur", "Турецкий", "Turkish"),
+    ("tut", "Алтайские языки", "Altaic languages"),
+    ("tvl", "Тувалу", "Tuvalu"),
+    ("twi", "Тви", "Twi"),
+    ("tyv", "Тувинский", "Tuvinian"),
+    ("udm", "Удмуртский", "Udmurt"),
+    ("uga", "Угаритский", "Ugaritic"),
+    ("uig", "Уйгурский", "Uighur"),
+    ("ukr", "Украинский", "Ukrainian"),
+    ("umb", "Умбунду", "Umbundu"),
+    ("und", "Неопределенный", "Undetermined"),
+    ("urd", "Урду", "Urdu"),
+    ("uzb", "Узбекский", "Uzbek"),
+    ("vai", "Ваи", "Vai"),
+    ("ven", "Венда", "Venda"),
+    ("vie", "Вьетнамский", "Vietnamese"),
+    ("vol", "Волапюк", "Volapük"),
+    ("vot", "Водский", "Votic"),
+    ("wak", "Вакашские языки", "Wakashan languages"),
+    ("wal", "Волайтта", "Wolaitta"),
+    ("war", "Варай", "Waray"),
+    ("was", "Вашо", "Washo"),
+    ("wel", "Валлийский", "Welsh"),
+    ("wen", "Лужицкие языки", "Sorbian languages"),
+    ("wln", "Валлонский", "Walloon"),
+    ("wol", "Волоф", "Wolof"),
+    ("xal", "Калмыцкий", "Kalmyk"),
+    ("xho", "Коса", "Xhosa"),
+    ("yao", "Яо", "Yao"),
+    ("yap", "Яп", "Yapese"),
+    ("yid", "Идиш", "Yiddish"),
+    ("yor", "Йоруба", "Yoruba"),
+    ("ypk", "Юпикские языки", "Yupik languages"),
+    ("zap", "Сапотекский", "Zapotec"),
+    ("zbl", "Блиссимволика", "Blissymbols"),
+    ("zen", "Зенага", "Zenaga"),
+    ("zgh", "Стандартный марокканский тамазигхт", "Standard Moroccan Tamazight"),
+    ("zha", "Чжуанский", "Zhuang"),
+    ("znd", "Занде", "Zande languages"),
+    ("zul", "Зулу", "Zulu"),
+    ("zun", "Зуни", "Zuni"),
+    ("zxx", "Нет лингвистического содержания", "No linguistic content"),
+    ("zza", "Заза", "Zaza"),
+];
+
+/// ISO 639-2/T codes that differ from their bibliographic (B) counterparts.
+///
+/// `(terminology code, bibliographic code)`
+pub(super) const TERMINOLOGY_TO_BIBLIOGRAPHIC: &[(&str, &str)] = &[
+    ("bod", "tib"),
+    ("ces", "cze"),
+    ("cym", "wel"),
+    ("deu", "ger"),
+    ("ell", "gre"),
+    ("eus", "baq"),
+    ("fas", "per"),
+    ("fra", "fre"),
+    ("hye", "arm"),
+    ("isl", "ice"),
+    ("kat", "geo"),
+    ("mkd", "mac"),
+    ("mri", "mao"),
+    ("msa", "may"),
+    ("mya", "bur"),
+    ("nld", "dut"),
+    ("ron", "rum"),
+    ("slk", "slo"),
+    ("sqi", "alb"),
+    ("zho", "chi"),
+];
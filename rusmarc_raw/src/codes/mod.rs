@@ -0,0 +1,131 @@
+//! Coded values used by 1xx fields: languages (ISO 639-2/B) and countries (ISO 3166-1).
+//!
+//! Both types keep whatever code the record contains, so that nothing is lost
+//! on unknown values. Use `is_known` to check a code against the embedded
+//! tables and `name_ru` / `name_en` to show it to a human.
+//!
+//! ```
+//! use rusmarc_raw::codes::{CountryCode, LanguageCode};
+//!
+//! let rus = LanguageCode::from("rus".to_string());
+//! assert_eq!(rus.name_ru(), Some("Русский"));
+//! assert_eq!(rus.name_en(), Some("Russian"));
+//!
+//! // Terminology codes are converted to bibliographic ones
+//! assert_eq!(LanguageCode::from("deu".to_string()).code(), "ger");
+//!
+//! let ru = CountryCode::from("ru".to_string());
+//! assert_eq!(ru.code(), "RU");
+//! assert_eq!(ru.name_ru(), Some("Россия"));
+//!
+//! assert!(!LanguageCode::from("xyz".to_string()).is_known());
+//! ```
+
+mod countries;
+mod languages;
+
+use std::fmt;
+
+use countries::COUNTRIES;
+use languages::{LANGUAGES, TERMINOLOGY_TO_BIBLIOGRAPHIC};
+
+fn find_names(
+    table: &[(&str, &'static str, &'static str)],
+    code: &str,
+) -> Option<(&'static str, &'static str)> {
+    table
+        .binary_search_by(|(table_code, _, _)| (*table_code).cmp(code))
+        .ok()
+        .map(|i| (table[i].1, table[i].2))
+}
+
+/// ISO 639-2/B language code, e.g. `rus`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LanguageCode {
+    code: String,
+}
+
+impl LanguageCode {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Whether the code is in ISO 639-2 or in the `qaa-qtz` range reserved for local use.
+    pub fn is_known(&self) -> bool {
+        self.is_local() || find_names(LANGUAGES, &self.code).is_some()
+    }
+
+    /// Whether the code is in the `qaa-qtz` range reserved for local use.
+    pub fn is_local(&self) -> bool {
+        ("qaa"..="qtz").contains(&self.code.as_str()) && self.code.len() == 3
+    }
+
+    pub fn name_ru(&self) -> Option<&'static str> {
+        find_names(LANGUAGES, &self.code).map(|(ru, _)| ru)
+    }
+
+    pub fn name_en(&self) -> Option<&'static str> {
+        find_names(LANGUAGES, &self.code).map(|(_, en)| en)
+    }
+}
+
+impl From<String> for LanguageCode {
+    /// Lowercases the code and replaces ISO 639-2/T codes with their ISO 639-2/B variants.
+    fn from(value: String) -> Self {
+        let code = value.trim().to_lowercase();
+        let code = TERMINOLOGY_TO_BIBLIOGRAPHIC
+            .iter()
+            .find(|(terminology, _)| *terminology == code)
+            .map_or(code, |(_, bibliographic)| bibliographic.to_string());
+
+        Self { code }
+    }
+}
+
+impl fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+/// ISO 3166-1 alpha-2 country code, e.g. `RU`.
+///
+/// Besides current ISO codes, `XX` (unknown country) and codes of former
+/// states that are common in older records (`SU`, `YU`, `CS`, `DD`) are known.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CountryCode {
+    code: String,
+}
+
+impl CountryCode {
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn is_known(&self) -> bool {
+        find_names(COUNTRIES, &self.code).is_some()
+    }
+
+    pub fn name_ru(&self) -> Option<&'static str> {
+        find_names(COUNTRIES, &self.code).map(|(ru, _)| ru)
+    }
+
+    pub fn name_en(&self) -> Option<&'static str> {
+        find_names(COUNTRIES, &self.code).map(|(_, en)| en)
+    }
+}
+
+impl From<String> for CountryCode {
+    /// Uppercases the code.
+    fn from(value: String) -> Self {
+        Self {
+            code: value.trim().to_uppercase(),
+        }
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
@@ -0,0 +1,370 @@
+//! Duplicate detection across vendors, who send the same document under
+//! their own 001.
+//!
+//! Records are reduced to a [`Fingerprint`] and added to a [`Deduplicator`].
+//! Two records match when they share an ISBN, or when their titles, first
+//! authors and years are close enough; matching records are clustered, and
+//! the most complete record of a cluster is proposed as its master.
+//!
+//! ```
+//! use rusmarc_raw::dedup::{Deduplicator, Fingerprint, MatchReason};
+//! use rusmarc_raw::description::{Description, Name, Role};
+//!
+//! let book = |id: &str, isbn: Option<&str>, title: &str, author: &str, date: &str| Description {
+//!     id: Some(id.to_string()),
+//!     isbns: isbn.into_iter().map(|isbn| isbn.parse().unwrap()).collect(),
+//!     title: Some(title.to_string()),
+//!     creators: vec![Name::Person { family: author.to_string(), given: None }.with_role(Role::Author)],
+//!     date: Some(date.to_string()),
+//!     ..Description::default()
+//! };
+//!
+//! let mut dedup = Deduplicator::default();
+//! dedup.add(Fingerprint::from(&book("lan-1", Some("978-5-8114-1234-1"), "Основы права", "Иванов", "2021")));
+//! dedup.add(Fingerprint::from(&book("zn-77", Some("ISBN 5-8114-1234-7"), "Основы права.", "Иванов", "2021")));
+//! dedup.add(Fingerprint::from(&book("ssga-3", None, "Основы  права", "Иваноф", "[2021]")));
+//! dedup.add(Fingerprint::from(&book("ssga-4", None, "Основы права", "Иванов", "2015")));
+//!
+//! let clusters = dedup.clusters();
+//! assert_eq!(clusters.len(), 1);
+//! assert_eq!(clusters[0].members, [0, 1, 2]);
+//! assert!(clusters[0].confidence > 0.9 && clusters[0].confidence < 1.0);
+//!
+//! // A record checked before it is stored, as on import
+//! let new = Fingerprint::from(&book("new", Some("9785811412341"), "Другое", "Петров", "2023"));
+//! let matches = dedup.matches(&new);
+//! assert_eq!(matches[0].0, 0);
+//! assert_eq!(matches[0].1.reason, MatchReason::Isbn);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::description::{Description, Name, Role};
+use crate::identifiers::Isbn;
+use crate::typed_record::TypedRecord;
+
+/// What records are compared by, normalized.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// Record identifier (001), to report clusters.
+    pub id: Option<String>,
+    /// Valid ISBNs; invalid ones are not compared.
+    pub isbns: Vec<Isbn>,
+    /// Title and other title information, lowercase letters and digits
+    /// separated by single spaces.
+    pub title: String,
+    /// Family name of the first author or the first organization, normalized
+    /// as the title.
+    pub author: Option<String>,
+    pub year: Option<u16>,
+    /// Number of filled description elements; the most complete record of
+    /// a cluster is its master.
+    pub completeness: usize,
+}
+
+impl From<&Description> for Fingerprint {
+    fn from(description: &Description) -> Self {
+        let first_author = description
+            .creators_with(Role::Author)
+            .next()
+            .or_else(|| description.creators.first().map(|creator| &creator.name));
+        let author = first_author.map(|name| match name {
+            Name::Person { family, .. } => normalize(family),
+            Name::Organization(name) => normalize(name),
+        });
+
+        let completeness = [
+            description.title.is_some(),
+            description.subtitle.is_some(),
+            description.responsibility.is_some(),
+            description.place.is_some(),
+            description.publisher.is_some(),
+            description.date.is_some(),
+            description.extent.is_some(),
+            description.summary.is_some(),
+            description.image.is_some(),
+        ]
+        .into_iter()
+        .filter(|&present| present)
+        .count()
+            + description.creators.len()
+            + description.isbns.len()
+            + description.languages.len()
+            + description.urls.len();
+
+        Self {
+            id: description.id.clone(),
+            isbns: description.isbns.clone(),
+            title: normalize(&description.full_title().unwrap_or_default()),
+            author: author.filter(|author| !author.is_empty()),
+            year: description.date.as_deref().and_then(year),
+            completeness,
+        }
+    }
+}
+
+impl From<&TypedRecord> for Fingerprint {
+    fn from(record: &TypedRecord) -> Self {
+        Self::from(&Description::from(record))
+    }
+}
+
+/// Lowercase letters and digits, `ё` as `е`, words separated by single spaces.
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .replace('ё', "е")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// First four-digit number, as in `[2021]` or `c2021`.
+fn year(date: &str) -> Option<u16> {
+    date.split(|c: char| !c.is_ascii_digit())
+        .find(|digits| digits.len() == 4)
+        .and_then(|digits| digits.parse().ok())
+}
+
+/// `1.0` for equal strings down to `0.0`, by the edit distance.
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    // One row of the Levenshtein distance table at a time
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    1.0 - row[b.len()] as f32 / a.len().max(b.len()) as f32
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchReason {
+    /// Records share an ISBN.
+    Isbn,
+    /// Titles, first authors and years are close.
+    Metadata,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Match {
+    pub reason: MatchReason,
+    /// From the threshold of the [`Deduplicator`] to `1.0`.
+    pub confidence: f32,
+}
+
+/// Titles less similar than this never match, whatever the rest.
+const MIN_TITLE_SIMILARITY: f32 = 0.8;
+/// Same for authors known on both sides.
+const MIN_AUTHOR_SIMILARITY: f32 = 0.8;
+
+/// How likely `a` and `b` describe the same document, `None` if unlikely.
+///
+/// Records with different years are different editions, unless they share
+/// an ISBN.
+pub fn compare(a: &Fingerprint, b: &Fingerprint) -> Option<Match> {
+    if a.isbns.iter().any(|isbn| b.isbns.contains(isbn)) {
+        return Some(Match {
+            reason: MatchReason::Isbn,
+            confidence: 1.0,
+        });
+    }
+
+    if a.title.is_empty() || b.title.is_empty() {
+        return None;
+    }
+    let title = similarity(&a.title, &b.title);
+    if title < MIN_TITLE_SIMILARITY {
+        return None;
+    }
+    let author = match (&a.author, &b.author) {
+        (Some(a), Some(b)) => match similarity(a, b) {
+            author if author < MIN_AUTHOR_SIMILARITY => return None,
+            author => author,
+        },
+        (None, None) => 0.5,
+        _ => 0.25,
+    };
+    let year = match (a.year, b.year) {
+        (Some(a), Some(b)) if a != b => return None,
+        (Some(_), Some(_)) => 1.0,
+        _ => 0.5,
+    };
+
+    Some(Match {
+        reason: MatchReason::Metadata,
+        confidence: 0.6 * title + 0.3 * author + 0.1 * year,
+    })
+}
+
+/// Records that are probably the same document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Cluster {
+    /// Positions of the records in the order they were added.
+    pub members: Vec<usize>,
+    /// Position of the most complete record, the first one of equals.
+    pub master: usize,
+    /// Confidence of the weakest match that joined the cluster.
+    pub confidence: f32,
+}
+
+/// Clusters of matching records, built as records are added.
+#[derive(Debug, Clone)]
+pub struct Deduplicator {
+    threshold: f32,
+    fingerprints: Vec<Fingerprint>,
+    /// Positions of the records by the keys that candidates share: an ISBN,
+    /// the start of the title or of the author.
+    blocks: HashMap<String, Vec<usize>>,
+    /// Union-find parent of every record.
+    parents: Vec<usize>,
+    /// Weakest match joined into a cluster, by its root.
+    confidence: HashMap<usize, f32>,
+}
+
+impl Default for Deduplicator {
+    /// Matches with confidence of at least `0.85`.
+    fn default() -> Self {
+        Self::new(0.85)
+    }
+}
+
+impl Deduplicator {
+    /// Deduplicator that ignores matches with confidence below `threshold`.
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            fingerprints: Vec::new(),
+            blocks: HashMap::new(),
+            parents: Vec::new(),
+            confidence: HashMap::new(),
+        }
+    }
+
+    pub fn fingerprints(&self) -> &[Fingerprint] {
+        &self.fingerprints
+    }
+
+    /// Records added so far that match `fingerprint`, the most confident first.
+    pub fn matches(&self, fingerprint: &Fingerprint) -> Vec<(usize, Match)> {
+        let mut candidates: Vec<usize> = block_keys(fingerprint)
+            .iter()
+            .filter_map(|key| self.blocks.get(key))
+            .flatten()
+            .copied()
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut matches: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|i| Some((i, compare(&self.fingerprints[i], fingerprint)?)))
+            .filter(|(_, found)| found.confidence >= self.threshold)
+            .collect();
+        matches
+            .sort_by(|(a_i, a), (b_i, b)| b.confidence.total_cmp(&a.confidence).then(a_i.cmp(b_i)));
+        matches
+    }
+
+    /// Adds a record and joins it with the clusters it matches. Returns the
+    /// position of the record.
+    pub fn add(&mut self, fingerprint: Fingerprint) -> usize {
+        let matches = self.matches(&fingerprint);
+        let position = self.fingerprints.len();
+
+        for key in block_keys(&fingerprint) {
+            self.blocks.entry(key).or_default().push(position);
+        }
+        self.fingerprints.push(fingerprint);
+        self.parents.push(position);
+
+        for (other, found) in matches {
+            let (root, other_root) = (self.root(position), self.root(other));
+            if root == other_root {
+                continue;
+            }
+
+            let weakest = [
+                self.confidence.remove(&root),
+                self.confidence.remove(&other_root),
+                Some(found.confidence),
+            ]
+            .into_iter()
+            .flatten()
+            .fold(1.0f32, f32::min);
+            self.parents[root] = other_root;
+            self.confidence.insert(other_root, weakest);
+        }
+        position
+    }
+
+    fn root(&self, mut position: usize) -> usize {
+        while self.parents[position] != position {
+            position = self.parents[position];
+        }
+        position
+    }
+
+    /// Clusters of two or more records, in the order of their first records.
+    pub fn clusters(&self) -> Vec<Cluster> {
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for position in 0..self.fingerprints.len() {
+            members
+                .entry(self.root(position))
+                .or_default()
+                .push(position);
+        }
+
+        let mut clusters: Vec<Cluster> = members
+            .into_iter()
+            .filter(|(_, members)| members.len() > 1)
+            .map(|(root, members)| {
+                let master = members
+                    .iter()
+                    .copied()
+                    .rev()
+                    .max_by_key(|&i| self.fingerprints[i].completeness)
+                    .unwrap(); // Clusters are never empty
+                Cluster {
+                    master,
+                    confidence: self.confidence[&root],
+                    members,
+                }
+            })
+            .collect();
+        clusters.sort_by_key(|cluster| cluster.members[0]);
+        clusters
+    }
+}
+
+/// Keys that the records compared with this one must share with it.
+fn block_keys(fingerprint: &Fingerprint) -> Vec<String> {
+    let prefix = |text: &str| text.chars().take(4).collect::<String>();
+
+    let mut keys: Vec<String> = fingerprint
+        .isbns
+        .iter()
+        .map(|isbn| format!("isbn:{}", isbn.compact()))
+        .collect();
+    if !fingerprint.title.is_empty() {
+        keys.push(format!("title:{}", prefix(&fingerprint.title)));
+    }
+    if let Some(author) = &fingerprint.author {
+        keys.push(format!("author:{}", prefix(author)));
+    }
+    keys
+}
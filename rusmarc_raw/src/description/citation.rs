@@ -0,0 +1,521 @@
+//! References for reading lists and reference managers.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde_json::{json, Map, Value};
+
+use super::{Description, Name, Role};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationStyle {
+    /// GOST R 7.0.100-2018 bibliographic reference.
+    Gost,
+    /// APA, 7th edition.
+    Apa,
+    BibTex,
+    /// RIS, as read by Zotero, Mendeley and EndNote.
+    Ris,
+    /// CSL-JSON item, as read by citeproc processors.
+    CslJson,
+}
+
+impl CitationStyle {
+    /// Media type of the formatted citation, e.g. for HTTP responses.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Self::Gost | Self::Apa => "text/plain; charset=utf-8",
+            Self::BibTex => "application/x-bibtex; charset=utf-8",
+            Self::Ris => "application/x-research-info-systems; charset=utf-8",
+            Self::CslJson => "application/vnd.citationstyles.csl+json",
+        }
+    }
+}
+
+impl FromStr for CitationStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gost" => Ok(Self::Gost),
+            "apa" => Ok(Self::Apa),
+            "bibtex" => Ok(Self::BibTex),
+            "ris" => Ok(Self::Ris),
+            "csl-json" | "csl" => Ok(Self::CslJson),
+            other => Err(format!(
+                "Unknown citation style `{other}`, expected one of: gost, apa, bibtex, ris, csl-json"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CitationStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Gost => "gost",
+            Self::Apa => "apa",
+            Self::BibTex => "bibtex",
+            Self::Ris => "ris",
+            Self::CslJson => "csl-json",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// First four digits in a row, `[2021]` or `cop. 2021` → `2021`.
+pub(super) fn year(date: &str) -> Option<&str> {
+    date.as_bytes()
+        .windows(4)
+        .position(|window| window.iter().all(u8::is_ascii_digit))
+        .map(|start| &date[start..start + 4])
+}
+
+/// `320 с.` → `320`.
+pub(super) fn page_count(extent: &str) -> Option<&str> {
+    let end = extent
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(extent.len());
+    (end > 0).then(|| &extent[..end])
+}
+
+/// Adds a period unless the text already ends with a punctuation mark.
+fn with_period(text: &str) -> String {
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{text}.")
+    }
+}
+
+/// Russian letters by GOST 7.79-2000 system B, for citation keys. Other
+/// characters besides ASCII letters and digits are dropped.
+fn transliterate(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let latin = match c {
+            'а' => "a",
+            'б' => "b",
+            'в' => "v",
+            'г' => "g",
+            'д' => "d",
+            'е' | 'э' => "e",
+            'ё' => "yo",
+            'ж' => "zh",
+            'з' => "z",
+            'и' => "i",
+            'й' => "j",
+            'к' => "k",
+            'л' => "l",
+            'м' => "m",
+            'н' => "n",
+            'о' => "o",
+            'п' => "p",
+            'р' => "r",
+            'с' => "s",
+            'т' => "t",
+            'у' => "u",
+            'ф' => "f",
+            'х' => "x",
+            'ц' => "cz",
+            'ч' => "ch",
+            'ш' => "sh",
+            'щ' => "shh",
+            'ы' => "y",
+            'ю' => "yu",
+            'я' => "ya",
+            c if c.is_ascii_alphanumeric() => {
+                result.push(c);
+                continue;
+            }
+            _ => continue,
+        };
+        result.push_str(latin);
+    }
+    result
+}
+
+fn escape_bibtex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '&' | '%' | '$' | '#' | '_' | '{' | '}') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl Description {
+    fn authors(&self) -> Vec<&Name> {
+        self.creators_with(Role::Author).collect()
+    }
+
+    fn editors(&self) -> Vec<&Name> {
+        self.creators_with(Role::Editor)
+            .chain(self.creators_with(Role::Compiler))
+            .collect()
+    }
+
+    /// Formats a reference; online documents get today as the date of access.
+    pub fn cite(&self, style: CitationStyle) -> String {
+        self.cite_accessed_on(style, chrono::Local::now().date_naive())
+    }
+
+    /// Same as [`Description::cite`], with a fixed date of access.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use rusmarc_raw::description::{CitationStyle, Description, Name, Role};
+    ///
+    /// let person = |family: &str, given: &str| Name::Person {
+    ///     family: family.to_string(),
+    ///     given: Some(given.to_string()),
+    /// };
+    /// let description = Description {
+    ///     title: Some("Основы программирования".to_string()),
+    ///     subtitle: Some("учебник".to_string()),
+    ///     responsibility: Some("И. И. Иванов, П. П. Петров".to_string()),
+    ///     creators: vec![
+    ///         person("Иванов", "И. И.").with_role(Role::Author),
+    ///         person("Петров", "П. П.").with_role(Role::Author),
+    ///     ],
+    ///     edition: Some("2-е изд., перераб.".to_string()),
+    ///     place: Some("Санкт-Петербург".to_string()),
+    ///     publisher: Some("Лань".to_string()),
+    ///     date: Some("2021".to_string()),
+    ///     extent: Some("320 с.".to_string()),
+    ///     isbns: vec!["978-5-8114-1234-1".parse().unwrap()],
+    ///     ..Description::default()
+    /// };
+    /// let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    ///
+    /// assert_eq!(
+    ///     description.cite_accessed_on(CitationStyle::Gost, today),
+    ///     "Иванов, И. И. Основы программирования : учебник / И. И. Иванов, П. П. Петров. — \
+    ///      2-е изд., перераб. — Санкт-Петербург : Лань, 2021. — 320 с. — ISBN 978-5-8114-1234-1. — Текст : непосредственный."
+    /// );
+    /// assert_eq!(
+    ///     description.cite_accessed_on(CitationStyle::Apa, today),
+    ///     "Иванов, И. И., & Петров, П. П. (2021). Основы программирования: учебник. Лань."
+    /// );
+    ///
+    /// let bibtex = description.cite_accessed_on(CitationStyle::BibTex, today);
+    /// assert!(bibtex.starts_with("@book{ivanov2021,\n"));
+    /// assert!(bibtex.contains("  author = {Иванов, И. И. and Петров, П. П.},\n"));
+    ///
+    /// let ris = description.cite_accessed_on(CitationStyle::Ris, today);
+    /// assert!(ris.starts_with("TY  - BOOK\r\nAU  - Иванов, И. И.\r\n"));
+    ///
+    /// let csl: serde_json::Value =
+    ///     serde_json::from_str(&description.cite_accessed_on(CitationStyle::CslJson, today)).unwrap();
+    /// assert_eq!(csl["author"][1]["family"], "Петров");
+    /// assert_eq!(csl["issued"]["date-parts"][0][0], 2021);
+    /// assert_eq!(csl["number-of-pages"], "320");
+    /// ```
+    pub fn cite_accessed_on(&self, style: CitationStyle, accessed: NaiveDate) -> String {
+        match style {
+            CitationStyle::Gost => self.gost(accessed),
+            CitationStyle::Apa => self.apa(),
+            CitationStyle::BibTex => self.bibtex(),
+            CitationStyle::Ris => self.ris(),
+            CitationStyle::CslJson => self.to_csl_json().to_string(),
+        }
+    }
+
+    /// GOST R 7.0.100-2018, section 5: the first author is the heading of
+    /// documents by up to three authors.
+    fn gost(&self, accessed: NaiveDate) -> String {
+        let authors = self.authors();
+        let mut areas = Vec::new();
+
+        let mut title = String::new();
+        if let [first @ Name::Person { .. }, ..] = authors[..] {
+            if authors.len() <= 3 {
+                title.push_str(&with_period(&first.inverted()));
+                title.push(' ');
+            }
+        }
+        title.push_str(self.title.as_deref().unwrap_or("[Без заглавия]"));
+        if let Some(subtitle) = &self.subtitle {
+            title.push_str(&format!(" : {subtitle}"));
+        }
+
+        let responsibility = match &self.responsibility {
+            Some(responsibility) => Some(responsibility.clone()),
+            None if authors.is_empty() => None,
+            None if authors.len() <= 3 => Some(
+                authors
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            None => Some(format!(
+                "{}, {}, {} [и др.]",
+                authors[0], authors[1], authors[2]
+            )),
+        };
+        if let Some(responsibility) = responsibility {
+            title.push_str(&format!(" / {responsibility}"));
+        }
+        if let Some(other) = &self.other_responsibility {
+            title.push_str(&format!(" ; {other}"));
+        }
+        areas.push(title);
+        areas.extend(self.edition.clone());
+
+        let publication = match (&self.place, &self.publisher, &self.date) {
+            (None, None, None) => None,
+            (place, publisher, date) => Some(format!(
+                "{} : {}, {}",
+                place.as_deref().unwrap_or("[Б. м.]"),
+                publisher.as_deref().unwrap_or("[б. и.]"),
+                date.as_deref().unwrap_or("[б. г.]"),
+            )),
+        };
+        areas.extend(publication);
+        areas.extend(self.extent.clone());
+        areas.extend(self.isbns.first().map(|isbn| format!("ISBN {isbn}")));
+
+        match self.urls.first() {
+            Some(url) => {
+                areas.push("Текст : электронный".to_string());
+                areas.push(format!(
+                    "URL: {url} (дата обращения: {})",
+                    accessed.format("%d.%m.%Y")
+                ));
+            }
+            None => areas.push("Текст : непосредственный".to_string()),
+        }
+
+        // Areas are separated by `. — `, keeping abbreviations like `320 с.` intact
+        areas
+            .iter()
+            .map(|area| with_period(area))
+            .collect::<Vec<_>>()
+            .join(" — ")
+    }
+
+    /// APA 7: `Author, A. A., & Author, B. B. (Year). Title: Subtitle. Publisher. URL`
+    fn apa(&self) -> String {
+        let names = |names: Vec<&Name>| match names.as_slice() {
+            [] => None,
+            [name] => Some(name.inverted()),
+            [rest @ .., last] => Some(format!(
+                "{}, & {}",
+                rest.iter()
+                    .map(|name| name.inverted())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                last.inverted()
+            )),
+        };
+
+        let mut title = self.title.clone().unwrap_or_default();
+        if let Some(subtitle) = &self.subtitle {
+            title.push_str(&format!(": {subtitle}"));
+        }
+        let date = format!(
+            "({})",
+            self.date.as_deref().and_then(year).unwrap_or("n.d.")
+        );
+
+        let editors = self.editors();
+        let mut parts = match (names(self.authors()), names(editors.clone())) {
+            (Some(authors), _) => vec![
+                with_period(&authors),
+                format!("{date}."),
+                with_period(&title),
+            ],
+            (None, Some(editors)) => {
+                let ed = if editors.len() == 1 { "Ed." } else { "Eds." };
+                vec![
+                    format!("{editors} ({ed})."),
+                    format!("{date}."),
+                    with_period(&title),
+                ]
+            }
+            (None, None) => vec![with_period(&title), format!("{date}.")],
+        };
+        parts.extend(self.publisher.as_deref().map(with_period));
+        parts.extend(self.urls.first().cloned());
+        parts.join(" ")
+    }
+
+    /// `ivanov2021`, from the first author and the year.
+    fn citation_key(&self) -> String {
+        let name = self.creators.first().map(|creator| match &creator.name {
+            Name::Person { family, .. } => family.clone(),
+            Name::Organization(name) => name.clone(),
+        });
+        let name = name.or_else(|| self.title.clone()).unwrap_or_default();
+        let name: String = transliterate(&name).chars().take(20).collect();
+        let year = self.date.as_deref().and_then(year).unwrap_or("");
+        match format!("{name}{year}") {
+            key if key.is_empty() => self.id.clone().unwrap_or_else(|| "book".to_string()),
+            key => key,
+        }
+    }
+
+    fn bibtex(&self) -> String {
+        let names = |names: Vec<&Name>| {
+            let names: Vec<_> = names
+                .into_iter()
+                .map(|name| match name {
+                    // Braces keep organizations from being split into first and last names
+                    Name::Organization(name) => format!("{{{}}}", escape_bibtex(name)),
+                    name => escape_bibtex(&name.inverted()),
+                })
+                .collect();
+            (!names.is_empty()).then(|| names.join(" and "))
+        };
+
+        let fields = [
+            ("author", names(self.authors())),
+            ("editor", names(self.editors())),
+            (
+                "title",
+                self.full_title().map(|title| escape_bibtex(&title)),
+            ),
+            ("publisher", self.publisher.as_deref().map(escape_bibtex)),
+            ("address", self.place.as_deref().map(escape_bibtex)),
+            (
+                "year",
+                self.date.as_deref().and_then(year).map(str::to_string),
+            ),
+            ("isbn", self.isbns.first().map(ToString::to_string)),
+            ("url", self.urls.first().cloned()),
+            ("language", self.languages.first().cloned()),
+        ];
+
+        let mut entry = format!("@book{{{},\n", self.citation_key());
+        for (name, value) in fields {
+            if let Some(value) = value {
+                entry.push_str(&format!("  {name} = {{{value}}},\n"));
+            }
+        }
+        entry.push_str("}\n");
+        entry
+    }
+
+    /// Lines end with CRLF, as the RIS specification requires.
+    fn ris(&self) -> String {
+        let kind = if self.urls.is_empty() {
+            "BOOK"
+        } else {
+            "EBOOK"
+        };
+        let mut lines = vec![("TY", kind.to_string())];
+        lines.extend(
+            self.authors()
+                .into_iter()
+                .map(|name| ("AU", name.inverted())),
+        );
+        lines.extend(
+            self.editors()
+                .into_iter()
+                .map(|name| ("ED", name.inverted())),
+        );
+        lines.extend(self.full_title().map(|title| ("TI", title)));
+        lines.extend(self.place.clone().map(|place| ("CY", place)));
+        lines.extend(self.publisher.clone().map(|publisher| ("PB", publisher)));
+        lines.extend(
+            self.date
+                .as_deref()
+                .and_then(year)
+                .map(|year| ("PY", year.to_string())),
+        );
+        lines.extend(self.isbns.iter().map(|isbn| ("SN", isbn.to_string())));
+        lines.extend(self.urls.iter().map(|url| ("UR", url.clone())));
+        lines.extend(self.summary.clone().map(|summary| ("AB", summary)));
+        lines.extend(
+            self.languages
+                .iter()
+                .map(|language| ("LA", language.clone())),
+        );
+        lines.push(("ER", String::new()));
+
+        lines
+            .into_iter()
+            .map(|(tag, value)| format!("{tag}  - {value}\r\n"))
+            .collect()
+    }
+
+    /// CSL-JSON item (<https://citeproc-js.readthedocs.io/en/latest/csl-json/markup.html>).
+    pub fn to_csl_json(&self) -> Value {
+        let names = |role: Role| {
+            let names: Vec<Value> = self
+                .creators_with(role)
+                .map(|name| match name {
+                    Name::Person { family, given } => match given {
+                        Some(given) => json!({ "family": family, "given": given }),
+                        None => json!({ "family": family }),
+                    },
+                    Name::Organization(name) => json!({ "literal": name }),
+                })
+                .collect();
+            (!names.is_empty()).then_some(Value::Array(names))
+        };
+
+        let mut item = Map::new();
+        let mut set = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                item.insert(key.to_string(), value);
+            }
+        };
+
+        set(
+            "id",
+            Some(json!(self
+                .id
+                .clone()
+                .unwrap_or_else(|| self.citation_key()))),
+        );
+        set("type", Some(json!("book")));
+        set("title", self.full_title().map(|title| json!(title)));
+        set("author", names(Role::Author));
+        set("editor", names(Role::Editor));
+        set("compiler", names(Role::Compiler));
+        set("translator", names(Role::Translator));
+        set("illustrator", names(Role::Illustrator));
+        set("contributor", names(Role::Contributor));
+        set(
+            "publisher",
+            self.publisher.as_ref().map(|publisher| json!(publisher)),
+        );
+        set(
+            "publisher-place",
+            self.place.as_ref().map(|place| json!(place)),
+        );
+        set(
+            "issued",
+            self.date.as_deref().map(|date| match year(date) {
+                Some(year) => json!({ "date-parts": [[year.parse::<u32>().unwrap()]] }),
+                None => json!({ "literal": date }),
+            }),
+        );
+        set(
+            "number-of-pages",
+            self.extent
+                .as_deref()
+                .and_then(page_count)
+                .map(|pages| json!(pages)),
+        );
+        set(
+            "ISBN",
+            self.isbns.first().map(|isbn| json!(isbn.to_string())),
+        );
+        set("URL", self.urls.first().map(|url| json!(url)));
+        set(
+            "abstract",
+            self.summary.as_ref().map(|summary| json!(summary)),
+        );
+        set(
+            "language",
+            self.languages.first().map(|language| json!(language)),
+        );
+
+        Value::Object(item)
+    }
+}
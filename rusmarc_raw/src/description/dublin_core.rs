@@ -0,0 +1,65 @@
+//! Simple Dublin Core in the `oai_dc` schema of OAI-PMH
+//! (<http://www.openarchives.org/OAI/2.0/oai_dc.xsd>).
+
+use std::io::{self, Write};
+
+use quick_xml::escape::escape;
+
+use super::{Description, Role};
+
+const OAI_DC_START: &str = r#"<oai_dc:dc xmlns:oai_dc="http://www.openarchives.org/OAI/2.0/oai_dc/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd">"#;
+
+impl Description {
+    /// Writes an `oai_dc:dc` element, without an XML declaration.
+    ///
+    /// Authors are `dc:creator`, everyone else is `dc:contributor`.
+    pub fn write_oai_dc(&self, output: &mut impl Write) -> io::Result<()> {
+        writeln!(output, "{OAI_DC_START}")?;
+        self.write_dc_elements(output)?;
+        writeln!(output, "</oai_dc:dc>")
+    }
+
+    /// Same as [`Description::write_oai_dc`], as a string.
+    pub fn to_oai_dc(&self) -> String {
+        let mut output = Vec::new();
+        self.write_oai_dc(&mut output).unwrap(); // Writing into a `Vec` does not fail
+        String::from_utf8(output).unwrap()
+    }
+
+    fn write_dc_elements(&self, output: &mut impl Write) -> io::Result<()> {
+        let mut element = |name: &str, text: &str| -> io::Result<()> {
+            writeln!(output, "  <dc:{name}>{}</dc:{name}>", escape(text))
+        };
+
+        if let Some(title) = self.full_title() {
+            element("title", &title)?;
+        }
+        for creator in &self.creators {
+            let name = match creator.role {
+                Role::Author => "creator",
+                _ => "contributor",
+            };
+            element(name, &creator.name.inverted())?;
+        }
+        if let Some(summary) = &self.summary {
+            element("description", summary)?;
+        }
+        if let Some(publisher) = &self.publisher {
+            element("publisher", publisher)?;
+        }
+        if let Some(date) = &self.date {
+            element("date", date)?;
+        }
+        element("type", "Text")?;
+        for isbn in &self.isbns {
+            element("identifier", &format!("urn:isbn:{}", isbn.compact()))?;
+        }
+        for url in &self.urls {
+            element("identifier", url)?;
+        }
+        for language in &self.languages {
+            element("language", language)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+//! Short bibliographic description of a document: the facts that catalogue
+//! pages, search engines and citations need, without the rest of RUSMARC.
+//!
+//! A [`Description`] is built from a [`TypedRecord`] or filled in directly by
+//! applications that keep their publications elsewhere, and is written as
+//! Dublin Core ([`Description::to_oai_dc`]), schema.org JSON-LD
+//! ([`Description::to_schema_org`]) or a citation ([`Description::cite`]).
+//!
+//! ```
+//! # use std::io::Cursor;
+//! use rusmarc_raw::description::{Description, Name, Role};
+//! use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+//! use rusmarc_raw::typed_record::TypedRecord;
+//!
+//! let data = "#1: lan-1\n#10: ^A978-5-8114-1234-1 (в пер.)\n#10: ^Aб. и.\n#101: ^Arus\n\
+//!             #200: ^AОсновы^EУчебник^FИ. И. Иванов\n#210: ^AСПб^CЛань^D2021\n\
+//!             #330: ^AКраткое содержание\n#700: ^AИванов^BИ. И.^4070\n#702: ^AПетров^BП. П.^4340\n";
+//! let record = RecordsReader::new(Cursor::new(data)).next().unwrap().unwrap();
+//! let (record, _errors) = TypedRecord::parse(record_remove_errors(record).into_iter());
+//!
+//! let description = Description::from(&record);
+//! assert_eq!(description.title.as_deref(), Some("Основы"));
+//! assert_eq!(description.publisher.as_deref(), Some("Лань"));
+//! assert_eq!(description.creators[0].name.inverted(), "Иванов, И. И.");
+//! assert_eq!(description.creators[1].role, Role::Editor);
+//! // Qualifiers are dropped, and so are the ISBNs that don't parse
+//! assert_eq!(description.isbns.len(), 1);
+//!
+//! let dc = description.to_oai_dc();
+//! assert!(dc.contains("<dc:title>Основы : Учебник</dc:title>"));
+//! assert!(dc.contains("<dc:creator>Иванов, И. И.</dc:creator>"));
+//! assert!(dc.contains("<dc:contributor>Петров, П. П.</dc:contributor>"));
+//! assert!(dc.contains("<dc:identifier>urn:isbn:9785811412341</dc:identifier>"));
+//!
+//! let json = description.to_schema_org();
+//! assert_eq!(json["@type"], "Book");
+//! assert_eq!(json["author"][0]["name"], "И. И. Иванов");
+//! assert_eq!(json["editor"][0]["familyName"], "Петров");
+//! assert_eq!(json["isbn"], "978-5-8114-1234-1");
+//!
+//! // Applications may fill a description without a record
+//! let description = Description {
+//!     title: Some("Сборник".to_string()),
+//!     creators: vec![Name::Organization("Издательство Лань".to_string()).with_role(Role::Author)],
+//!     ..Description::default()
+//! };
+//! assert_eq!(description.to_schema_org()["author"][0]["@type"], "Organization");
+//! ```
+
+mod citation;
+mod dublin_core;
+mod schema_org;
+
+use std::fmt;
+
+pub use citation::CitationStyle;
+
+use crate::identifiers::Isbn;
+
+use crate::typed_record::{
+    CorporateName, Field001RecordId, Field010Isbn, Field101Language, Field200Header,
+    Field205Edition, Field210PublicationDistribution, Field215PhysicalDescription, Field330Summary,
+    Field700PersonPrimary, Field701PersonAlternative, Field702PersonSecondary,
+    Field710OrganizationPrimary, Field711OrganizationAlternative, Field712OrganizationSecondary,
+    PersonalName, TypedRecord,
+};
+
+/// Person or organization responsible for a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Name {
+    Person {
+        family: String,
+        given: Option<String>,
+    },
+    Organization(String),
+}
+
+impl Name {
+    /// `Иванов, И. И.`, as names are sorted in catalogues.
+    pub fn inverted(&self) -> String {
+        match self {
+            Self::Person {
+                family,
+                given: Some(given),
+            } => format!("{family}, {given}"),
+            Self::Person {
+                family,
+                given: None,
+            } => family.clone(),
+            Self::Organization(name) => name.clone(),
+        }
+    }
+
+    pub fn with_role(self, role: Role) -> Creator {
+        Creator { name: self, role }
+    }
+}
+
+impl fmt::Display for Name {
+    /// `И. И. Иванов`, in the natural order.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Person {
+                family,
+                given: Some(given),
+            } => write!(f, "{given} {family}"),
+            Self::Person {
+                family,
+                given: None,
+            } => write!(f, "{family}"),
+            Self::Organization(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl From<&PersonalName> for Name {
+    fn from(name: &PersonalName) -> Self {
+        Self::Person {
+            family: name.entry_element.clone(),
+            given: name.rest_of_name.clone(),
+        }
+    }
+}
+
+impl From<&CorporateName> for Name {
+    fn from(name: &CorporateName) -> Self {
+        Self::Organization(name.full_name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Author,
+    Compiler,
+    Editor,
+    Illustrator,
+    Translator,
+    Contributor,
+}
+
+impl Role {
+    /// Role by a RUSMARC relator code (Appendix C), `default` for other codes.
+    pub fn from_relator_code(code: Option<&str>, default: Role) -> Role {
+        match code.map(str::trim) {
+            Some("070") => Self::Author,
+            Some("220") => Self::Compiler,
+            Some("340") => Self::Editor,
+            Some("440") => Self::Illustrator,
+            Some("730") => Self::Translator,
+            Some(_) | None => default,
+        }
+    }
+
+    /// The code of the role for subfield $4, none for contributors.
+    ///
+    /// ```
+    /// use rusmarc_raw::description::Role;
+    ///
+    /// for role in [Role::Author, Role::Compiler, Role::Editor, Role::Illustrator, Role::Translator] {
+    ///     assert_eq!(Role::from_relator_code(role.relator_code(), Role::Contributor), role);
+    /// }
+    /// assert_eq!(Role::Contributor.relator_code(), None);
+    /// ```
+    pub fn relator_code(self) -> Option<&'static str> {
+        match self {
+            Self::Author => Some("070"),
+            Self::Compiler => Some("220"),
+            Self::Editor => Some("340"),
+            Self::Illustrator => Some("440"),
+            Self::Translator => Some("730"),
+            Self::Contributor => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Creator {
+    pub name: Name,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Description {
+    /// Record identifier (001).
+    pub id: Option<String>,
+    /// Title proper (200 $a).
+    pub title: Option<String>,
+    /// Other title information (200 $e).
+    pub subtitle: Option<String>,
+    /// Statement of responsibility as on the title page (200 $f).
+    pub responsibility: Option<String>,
+    /// Subsequent statements of responsibility (200 $g).
+    pub other_responsibility: Option<String>,
+    /// People and organizations in the order of the record (7xx).
+    pub creators: Vec<Creator>,
+    /// Edition statement, e.g. `2-е изд., перераб.` (205 $a).
+    pub edition: Option<String>,
+    /// Place of publication (210 $a).
+    pub place: Option<String>,
+    /// Publisher (210 $c).
+    pub publisher: Option<String>,
+    /// Date of publication as catalogued, usually a year (210 $d).
+    pub date: Option<String>,
+    /// Extent, e.g. `320 с.` (215 $a).
+    pub extent: Option<String>,
+    /// ISBNs of the record (010 $a), without the ones that don't parse.
+    pub isbns: Vec<Isbn>,
+    /// Summary (330 $a).
+    pub summary: Option<String>,
+    /// ISO 639-2 codes of the text (101 $a).
+    pub languages: Vec<String>,
+    /// Pages of the document, for applications that know them.
+    pub urls: Vec<String>,
+    /// Cover image, for applications that know it.
+    pub image: Option<String>,
+}
+
+impl Description {
+    /// Title with other title information, `Основы : Учебник`.
+    pub fn full_title(&self) -> Option<String> {
+        match (&self.title, &self.subtitle) {
+            (Some(title), Some(subtitle)) => Some(format!("{title} : {subtitle}")),
+            (Some(title), None) => Some(title.clone()),
+            (None, subtitle) => subtitle.clone(),
+        }
+    }
+
+    /// Year of publication from the date, `[2021]` or `cop. 2021` → `2021`.
+    pub fn year(&self) -> Option<i32> {
+        self.date.as_deref().and_then(citation::year)?.parse().ok()
+    }
+
+    /// Number of pages from the extent, `320 с.` → `320`.
+    pub fn page_count(&self) -> Option<u32> {
+        self.extent
+            .as_deref()
+            .and_then(citation::page_count)?
+            .parse()
+            .ok()
+    }
+
+    /// Creators with the role, in order.
+    pub fn creators_with(&self, role: Role) -> impl Iterator<Item = &Name> {
+        self.creators
+            .iter()
+            .filter(move |creator| creator.role == role)
+            .map(|creator| &creator.name)
+    }
+}
+
+impl From<&TypedRecord> for Description {
+    /// Only the first 200 and 210 are used. 7xx without a relator code are
+    /// authors, except for 702 and 712 which are contributors.
+    fn from(record: &TypedRecord) -> Self {
+        let title = record.get_fields::<Field200Header>().next();
+        let publication = record
+            .get_fields::<Field210PublicationDistribution>()
+            .next();
+
+        // Keep the order of the record, not of the field numbers
+        let mut creators = Vec::new();
+        for field in &record.fields {
+            let field = field.any_ref();
+            let person = |name: &PersonalName, default| {
+                Name::from(name).with_role(Role::from_relator_code(
+                    name.relator_codes.first().map(String::as_str),
+                    default,
+                ))
+            };
+            let organization = |name: &CorporateName, default| {
+                Name::from(name).with_role(Role::from_relator_code(
+                    name.relator_codes.first().map(String::as_str),
+                    default,
+                ))
+            };
+
+            if let Some(field) = field.downcast_ref::<Field700PersonPrimary>() {
+                creators.push(person(&field.name, Role::Author));
+            } else if let Some(field) = field.downcast_ref::<Field701PersonAlternative>() {
+                creators.push(person(&field.name, Role::Author));
+            } else if let Some(field) = field.downcast_ref::<Field702PersonSecondary>() {
+                creators.push(person(&field.name, Role::Contributor));
+            } else if let Some(field) = field.downcast_ref::<Field710OrganizationPrimary>() {
+                creators.push(organization(&field.name, Role::Author));
+            } else if let Some(field) = field.downcast_ref::<Field711OrganizationAlternative>() {
+                creators.push(organization(&field.name, Role::Author));
+            } else if let Some(field) = field.downcast_ref::<Field712OrganizationSecondary>() {
+                creators.push(organization(&field.name, Role::Contributor));
+            }
+        }
+
+        Self {
+            id: record
+                .get_fields::<Field001RecordId>()
+                .next()
+                .map(|field| field.id.clone()),
+            title: title.and_then(|title| title.main_title.clone()),
+            subtitle: title.and_then(|title| title.title_related_info.clone()),
+            responsibility: title.and_then(|title| title.main_responsibility.clone()),
+            other_responsibility: title.and_then(|title| title.other_responsibility.clone()),
+            creators,
+            edition: record
+                .get_fields::<Field205Edition>()
+                .next()
+                .and_then(|field| field.edition.clone()),
+            place: publication.and_then(|publication| publication.place.first().cloned()),
+            publisher: publication.and_then(|publication| publication.publisher.first().cloned()),
+            date: publication.and_then(|publication| publication.date.clone()),
+            extent: record
+                .get_fields::<Field215PhysicalDescription>()
+                .next()
+                .and_then(|field| field.extent.first().cloned()),
+            isbns: record
+                .get_fields::<Field010Isbn>()
+                .filter_map(|field| field.parsed_isbn().ok())
+                .fold(Vec::new(), |mut isbns, isbn| {
+                    if !isbns.contains(&isbn) {
+                        isbns.push(isbn);
+                    }
+                    isbns
+                }),
+            summary: record
+                .get_fields::<Field330Summary>()
+                .next()
+                .map(|field| field.text.clone()),
+            languages: record
+                .get_fields::<Field101Language>()
+                .flat_map(|field| field.language_text.iter().map(|code| code.to_string()))
+                .collect(),
+            urls: Vec::new(),
+            image: None,
+        }
+    }
+}
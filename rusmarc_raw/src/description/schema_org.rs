@@ -0,0 +1,122 @@
+//! schema.org `Book` (<https://schema.org/Book>) as JSON-LD.
+
+use serde_json::{json, Map, Value};
+
+use super::{Description, Name, Role};
+
+fn agent(name: &Name) -> Value {
+    match name {
+        Name::Person { family, given } => {
+            let mut person = json!({
+                "@type": "Person",
+                "name": name.to_string(),
+                "familyName": family,
+            });
+            if let Some(given) = given {
+                person["givenName"] = json!(given);
+            }
+            person
+        }
+        Name::Organization(organization) => json!({
+            "@type": "Organization",
+            "name": organization,
+        }),
+    }
+}
+
+/// A single value as is, several as an array.
+fn one_or_many(mut values: Vec<Value>) -> Option<Value> {
+    match values.len() {
+        0 => None,
+        1 => values.pop(),
+        _ => Some(Value::Array(values)),
+    }
+}
+
+impl Description {
+    /// JSON-LD of a schema.org `Book`, for `<script type="application/ld+json">`.
+    ///
+    /// Compilers are listed as editors; languages stay ISO 639-2 codes.
+    pub fn to_schema_org(&self) -> Value {
+        let mut book = Map::new();
+        let mut set = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                book.insert(key.to_string(), value);
+            }
+        };
+
+        set("@context", Some(json!("https://schema.org")));
+        set("@type", Some(json!("Book")));
+        set("identifier", self.id.as_ref().map(|id| json!(id)));
+        set("name", self.title.as_ref().map(|title| json!(title)));
+        set(
+            "alternativeHeadline",
+            self.subtitle.as_ref().map(|subtitle| json!(subtitle)),
+        );
+
+        let agents = |roles: &[Role]| {
+            let names = self
+                .creators
+                .iter()
+                .filter(|creator| roles.contains(&creator.role))
+                .map(|creator| agent(&creator.name))
+                .collect::<Vec<_>>();
+            (!names.is_empty()).then_some(Value::Array(names))
+        };
+        set("author", agents(&[Role::Author]));
+        set("editor", agents(&[Role::Editor, Role::Compiler]));
+        set("translator", agents(&[Role::Translator]));
+        set("illustrator", agents(&[Role::Illustrator]));
+        set("contributor", agents(&[Role::Contributor]));
+
+        set(
+            "publisher",
+            self.publisher.as_ref().map(|publisher| {
+                json!({
+                    "@type": "Organization",
+                    "name": publisher,
+                })
+            }),
+        );
+        set(
+            "locationCreated",
+            self.place.as_ref().map(|place| {
+                json!({
+                    "@type": "Place",
+                    "name": place,
+                })
+            }),
+        );
+        set("datePublished", self.date.as_ref().map(|date| json!(date)));
+        set(
+            "bookEdition",
+            self.edition.as_ref().map(|edition| json!(edition)),
+        );
+        set(
+            "isbn",
+            one_or_many(
+                self.isbns
+                    .iter()
+                    .map(|isbn| json!(isbn.to_string()))
+                    .collect(),
+            ),
+        );
+        set(
+            "inLanguage",
+            one_or_many(
+                self.languages
+                    .iter()
+                    .map(|language| json!(language))
+                    .collect(),
+            ),
+        );
+        set(
+            "description",
+            self.summary.as_ref().map(|summary| json!(summary)),
+        );
+        set("url", self.urls.first().map(|url| json!(url)));
+        set("image", self.image.as_ref().map(|image| json!(image)));
+
+        Value::Object(book)
+    }
+}
@@ -0,0 +1,229 @@
+//! Three-way merge of a record edited locally and updated by its vendor.
+
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use crate::field::{Field, FieldData};
+use crate::record::ErrorlessRecord;
+
+use super::{align, as_text, same_marker, same_number, Step};
+
+/// Field changed differently on both sides. The merged record keeps the
+/// local version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Conflict {
+    pub number: u128,
+    #[serde(serialize_with = "as_text")]
+    pub base: FieldData,
+    /// `None` if the field was removed locally.
+    #[serde(serialize_with = "as_optional_text")]
+    pub local: Option<FieldData>,
+    /// `None` if the field was removed by the vendor.
+    #[serde(serialize_with = "as_optional_text")]
+    pub remote: Option<FieldData>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |data: &Option<FieldData>| match data {
+            Some(data) => data.to_string(),
+            None => "(removed)".to_string(),
+        };
+        writeln!(f, "#{} was changed on both sides", self.number)?;
+        writeln!(f, "    base:   {}", self.base)?;
+        writeln!(f, "    local:  {}", side(&self.local))?;
+        writeln!(f, "    remote: {}", side(&self.remote))
+    }
+}
+
+fn as_optional_text<S: Serializer>(
+    data: &Option<FieldData>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match data {
+        Some(data) => serializer.collect_str(data),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Merge {
+    pub record: ErrorlessRecord,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Applies the changes from `base` to `remote` (the record as the vendor sent
+/// it again) to `local` (the record with our edits).
+///
+/// A field changed on one side only takes that change. When both sides
+/// changed different subfields of a field, both changes are kept; any other
+/// field changed on both sides is a [`Conflict`]. Fields added on both sides
+/// are all kept, vendor's first, and added once if equal. The version
+/// identifier (005) is never a conflict: the later one wins.
+///
+/// ```
+/// # use std::io::Cursor;
+/// use rusmarc_raw::diff::merge;
+/// use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+///
+/// let read = |data: &str| {
+///     record_remove_errors(RecordsReader::new(Cursor::new(data.to_string())).next().unwrap().unwrap())
+/// };
+/// let base = read("#1: lan-1\n#5: 20240101000000.0\n#200: ^AОсновы^EУчебник\n#215: ^A320 с.\n");
+/// // Our edit: a subject heading and a fixed title
+/// let local = read("#1: lan-1\n#5: 20240101000000.0\n#200: ^AОсновы права^EУчебник\n#215: ^A320 с.\n#606: ^AПраво\n");
+/// // Vendor's update: second edition
+/// let remote = read("#1: lan-1\n#5: 20250301120000.0\n#200: ^AОсновы^EУчебное пособие\n#215: ^A352 с.\n");
+///
+/// let merged = merge(&base, &local, &remote);
+/// assert!(merged.conflicts.is_empty());
+/// let text: Vec<_> = merged.record.iter().map(|field| format!("#{}: {}", field.number, field.data)).collect();
+/// assert_eq!(text, [
+///     "#1: lan-1",
+///     "#5: 20250301120000.0",
+///     "#200: ^AОсновы права^EУчебное пособие",
+///     "#215: ^A352 с.",
+///     "#606: ^AПраво",
+/// ]);
+///
+/// // We fixed the extent too, differently
+/// let local = read("#1: lan-1\n#5: 20240101000000.0\n#200: ^AОсновы^EУчебник\n#215: ^A321 с.\n");
+/// let merged = merge(&base, &local, &remote);
+/// assert_eq!(merged.conflicts.len(), 1);
+/// assert_eq!(merged.conflicts[0].number, 215);
+/// assert_eq!(merged.record[3].data.to_string(), "^A321 с.");
+/// ```
+pub fn merge(base: &[Field], local: &[Field], remote: &[Field]) -> Merge {
+    let (record, conflicts) = merge_sequences(base, local, remote, same_number, merge_fields);
+    let conflicts = conflicts
+        .into_iter()
+        .map(|(base, local, remote)| Conflict {
+            number: base.number,
+            base: base.data,
+            local: local.map(|field| field.data),
+            remote: remote.map(|field| field.data),
+        })
+        .collect();
+
+    Merge { record, conflicts }
+}
+
+/// Field changed on both sides, if the changes can be combined.
+fn merge_fields(base: &Field, local: &Field, remote: &Field) -> Option<Field> {
+    // Timestamps `YYYYMMDDHHMMSS.T` compare as strings
+    if base.number == 5 {
+        return Some(if local.data.to_string() > remote.data.to_string() {
+            local.clone()
+        } else {
+            remote.clone()
+        });
+    }
+
+    let number = base.number;
+    let (
+        FieldData::Subfields { subfields: base },
+        FieldData::Subfields { subfields: local },
+        FieldData::Subfields { subfields: remote },
+    ) = (&base.data, &local.data, &remote.data)
+    else {
+        return None;
+    };
+
+    let (subfields, conflicts) = merge_sequences(base, local, remote, same_marker, |_, _, _| None);
+    conflicts.is_empty().then_some(Field {
+        number,
+        data: FieldData::Subfields { subfields },
+    })
+}
+
+type SequenceConflict<T> = (T, Option<T>, Option<T>);
+
+/// Changes from `base` to `local` and to `remote` in one sequence, and the
+/// items `resolve` could not combine, with the local version kept.
+fn merge_sequences<T: Clone + PartialEq>(
+    base: &[T],
+    local: &[T],
+    remote: &[T],
+    similar: fn(&T, &T) -> bool,
+    resolve: impl Fn(&T, &T, &T) -> Option<T>,
+) -> (Vec<T>, Vec<SequenceConflict<T>>) {
+    let local_side = Side::new(base, local, similar);
+    let remote_side = Side::new(base, remote, similar);
+
+    let mut merged = Vec::new();
+    let mut conflicts = Vec::new();
+    let insert = |merged: &mut Vec<T>, anchor: usize| {
+        let remote_inserted: Vec<&T> = remote_side.inserted[anchor]
+            .iter()
+            .map(|&j| &remote[j])
+            .collect();
+        merged.extend(remote_inserted.iter().copied().cloned());
+        merged.extend(
+            local_side.inserted[anchor]
+                .iter()
+                .map(|&j| &local[j])
+                .filter(|item| !remote_inserted.contains(item))
+                .cloned(),
+        );
+    };
+
+    insert(&mut merged, 0);
+    for (i, item) in base.iter().enumerate() {
+        let ours = local_side.kept[i].map(|j| &local[j]);
+        let theirs = remote_side.kept[i].map(|j| &remote[j]);
+
+        match (ours, theirs) {
+            (Some(ours), Some(theirs)) if ours == theirs || theirs == item => {
+                merged.push(ours.clone())
+            }
+            (Some(ours), Some(theirs)) if ours == item => merged.push(theirs.clone()),
+            (Some(ours), Some(theirs)) => match resolve(item, ours, theirs) {
+                Some(resolved) => merged.push(resolved),
+                None => {
+                    merged.push(ours.clone());
+                    conflicts.push((item.clone(), Some(ours.clone()), Some(theirs.clone())));
+                }
+            },
+            (None, None) => {}
+            (None, Some(theirs)) if theirs == item => {}
+            (None, Some(theirs)) => conflicts.push((item.clone(), None, Some(theirs.clone()))),
+            (Some(ours), None) if ours == item => {}
+            (Some(ours), None) => {
+                merged.push(ours.clone());
+                conflicts.push((item.clone(), Some(ours.clone()), None));
+            }
+        }
+        insert(&mut merged, i + 1);
+    }
+
+    (merged, conflicts)
+}
+
+/// Where the items of the base went on one side.
+struct Side {
+    /// Position of each base item on this side, `None` if removed.
+    kept: Vec<Option<usize>>,
+    /// Positions of new items, by the number of base items before them.
+    inserted: Vec<Vec<usize>>,
+}
+
+impl Side {
+    fn new<T: PartialEq>(base: &[T], side: &[T], similar: fn(&T, &T) -> bool) -> Self {
+        let mut kept = vec![None; base.len()];
+        let mut inserted = vec![Vec::new(); base.len() + 1];
+        let mut anchor = 0;
+
+        for step in align(base, side, similar) {
+            match step {
+                Step::Same(i, j) | Step::Changed(i, j) => {
+                    kept[i] = Some(j);
+                    anchor = i + 1;
+                }
+                Step::Removed(i) => anchor = i + 1,
+                Step::Added(j) => inserted[anchor].push(j),
+            }
+        }
+        Self { kept, inserted }
+    }
+}
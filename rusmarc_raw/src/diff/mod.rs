@@ -0,0 +1,277 @@
+//! Field and subfield level differences between two versions of a record,
+//! and a three-way merge of them ([`merge`]).
+//!
+//! Fields are matched by the longest common subsequence of equal fields;
+//! fields with the same number left between the matches are reported as
+//! changed, in order, the rest as added or removed. Subfields of a changed
+//! field are matched the same way by their markers.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! use rusmarc_raw::diff::{diff, FieldChange};
+//! use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+//!
+//! let read = |data: &str| {
+//!     record_remove_errors(RecordsReader::new(Cursor::new(data.to_string())).next().unwrap().unwrap())
+//! };
+//! let old = read("#1: lan-1\n#200: ^AОсновы^EУчебник\n#700: ^AИванов^BИ. И.\n");
+//! let new = read("#1: lan-1\n#200: ^AОсновы^EУчебное пособие\n#702: ^AПетров^BП. П.\n");
+//!
+//! let changes = diff(&old, &new);
+//! assert_eq!(changes.changes.len(), 3);
+//! assert!(matches!(changes.changes[1], FieldChange::Removed { number: 700, .. }));
+//! assert_eq!(
+//!     changes.to_string(),
+//!     "~ #200\n    ~ $E: `Учебник` -> `Учебное пособие`\n\
+//!      - #700: ^AИванов^BИ. И.\n\
+//!      + #702: ^AПетров^BП. П.\n"
+//! );
+//!
+//! let json = serde_json::to_value(&changes).unwrap();
+//! assert_eq!(json["changes"][0]["change"], "changed");
+//! assert_eq!(json["changes"][0]["subfields"][0]["new"], "Учебное пособие");
+//! ```
+
+mod merge;
+
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use crate::field::{Field, FieldData, Subfield};
+
+pub use merge::{merge, Conflict, Merge};
+
+/// Change of one field, from the old record to the new one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum FieldChange {
+    Added {
+        number: u128,
+        #[serde(serialize_with = "as_text")]
+        data: FieldData,
+    },
+    Removed {
+        number: u128,
+        #[serde(serialize_with = "as_text")]
+        data: FieldData,
+    },
+    /// Subfield changes are empty when either side is a full line field.
+    Changed {
+        number: u128,
+        #[serde(serialize_with = "as_text")]
+        old: FieldData,
+        #[serde(serialize_with = "as_text")]
+        new: FieldData,
+        subfields: Vec<SubfieldChange>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum SubfieldChange {
+    Added {
+        marker: char,
+        text: String,
+    },
+    Removed {
+        marker: char,
+        text: String,
+    },
+    Changed {
+        marker: char,
+        old: String,
+        new: String,
+    },
+}
+
+/// Changes in the order of the fields, empty for equal records.
+///
+/// Displays as one line per field, `+` for added, `-` for removed and `~` for
+/// changed fields, with changed subfields indented below.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RecordDiff {
+    pub changes: Vec<FieldChange>,
+}
+
+impl RecordDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Differences between `old` and `new`.
+pub fn diff(old: &[Field], new: &[Field]) -> RecordDiff {
+    let changes = align(old, new, same_number)
+        .into_iter()
+        .filter_map(|step| match step {
+            Step::Same(..) => None,
+            Step::Added(j) => Some(FieldChange::Added {
+                number: new[j].number,
+                data: new[j].data.clone(),
+            }),
+            Step::Removed(i) => Some(FieldChange::Removed {
+                number: old[i].number,
+                data: old[i].data.clone(),
+            }),
+            Step::Changed(i, j) => Some(FieldChange::Changed {
+                number: new[j].number,
+                old: old[i].data.clone(),
+                new: new[j].data.clone(),
+                subfields: diff_subfields(&old[i].data, &new[j].data),
+            }),
+        })
+        .collect();
+
+    RecordDiff { changes }
+}
+
+fn diff_subfields(old: &FieldData, new: &FieldData) -> Vec<SubfieldChange> {
+    let (FieldData::Subfields { subfields: old }, FieldData::Subfields { subfields: new }) =
+        (old, new)
+    else {
+        return Vec::new();
+    };
+
+    align(old, new, same_marker)
+        .into_iter()
+        .filter_map(|step| match step {
+            Step::Same(..) => None,
+            Step::Added(j) => Some(SubfieldChange::Added {
+                marker: new[j].marker,
+                text: new[j].text.clone(),
+            }),
+            Step::Removed(i) => Some(SubfieldChange::Removed {
+                marker: old[i].marker,
+                text: old[i].text.clone(),
+            }),
+            Step::Changed(i, j) => Some(SubfieldChange::Changed {
+                marker: new[j].marker,
+                old: old[i].text.clone(),
+                new: new[j].text.clone(),
+            }),
+        })
+        .collect()
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { number, data } => writeln!(f, "+ #{number}: {data}"),
+            Self::Removed { number, data } => writeln!(f, "- #{number}: {data}"),
+            Self::Changed {
+                number,
+                old,
+                new,
+                subfields,
+            } if subfields.is_empty() => writeln!(f, "~ #{number}: `{old}` -> `{new}`"),
+            Self::Changed {
+                number, subfields, ..
+            } => {
+                writeln!(f, "~ #{number}")?;
+                subfields
+                    .iter()
+                    .try_for_each(|change| writeln!(f, "    {change}"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for SubfieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { marker, text } => write!(f, "+ ${marker}: `{text}`"),
+            Self::Removed { marker, text } => write!(f, "- ${marker}: `{text}`"),
+            Self::Changed { marker, old, new } => write!(f, "~ ${marker}: `{old}` -> `{new}`"),
+        }
+    }
+}
+
+impl fmt::Display for RecordDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.changes
+            .iter()
+            .try_for_each(|change| write!(f, "{change}"))
+    }
+}
+
+/// Field data in the text format, as people read it in the other commands.
+fn as_text<S: Serializer>(data: &FieldData, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(data)
+}
+
+fn same_number(a: &Field, b: &Field) -> bool {
+    a.number == b.number
+}
+
+fn same_marker(a: &Subfield, b: &Subfield) -> bool {
+    a.marker == b.marker
+}
+
+/// How an item of the old sequence got into the new one, by positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Same(usize, usize),
+    Changed(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// Steps from `old` to `new`, in the order of both sequences.
+///
+/// Equal items are matched by the longest common subsequence. Between the
+/// matches, each old item is paired with the next new item it is `similar`
+/// to, so pairs never cross.
+fn align<T: PartialEq>(old: &[T], new: &[T], similar: fn(&T, &T) -> bool) -> Vec<Step> {
+    // Length of the common subsequence of `old[i..]` and `new[j..]`
+    let width = new.len() + 1;
+    let mut lengths = vec![0usize; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    let mut steps = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (match_i, match_j) in matches
+        .into_iter()
+        .chain(std::iter::once((old.len(), new.len())))
+    {
+        for (old_i, item) in old.iter().enumerate().take(match_i).skip(i) {
+            match (j..match_j).find(|&new_j| similar(item, &new[new_j])) {
+                Some(new_j) => {
+                    steps.extend((j..new_j).map(Step::Added));
+                    steps.push(Step::Changed(old_i, new_j));
+                    j = new_j + 1;
+                }
+                None => steps.push(Step::Removed(old_i)),
+            }
+        }
+        steps.extend((j..match_j).map(Step::Added));
+
+        if match_i < old.len() {
+            steps.push(Step::Same(match_i, match_j));
+        }
+        (i, j) = (match_i + 1, match_j + 1);
+    }
+    steps
+}
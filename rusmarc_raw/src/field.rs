@@ -2,15 +2,16 @@ use std::io::{self, BufRead};
 
 use serde::{Deserialize, Serialize};
 
+use crate::record_ref::FieldRef;
 use crate::typed_record::{AnyTypedField, ParseTypedField, ParseTypedFieldError};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Subfield {
     pub marker: char,
     pub text: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FieldData {
     FullLine { text: String },
     Subfields { subfields: Vec<Subfield> },
@@ -20,13 +21,10 @@ impl FieldData {
     pub fn as_singular_text(&self) -> Option<&str> {
         match self {
             Self::FullLine { text } => Some(text.as_str()),
-            Self::Subfields { subfields } => {
-                if subfields.len() > 0 {
-                    None
-                } else {
-                    Some(&subfields[0].text)
-                }
-            }
+            Self::Subfields { subfields } => match subfields.as_slice() {
+                [subfield] => Some(&subfield.text),
+                _ => None,
+            },
         }
     }
     pub fn get_main_subfields(&self) -> Vec<&str> {
@@ -55,13 +53,25 @@ impl FieldData {
     }
 }
 
+/// Data as in the text format: `^Atext^Btext` or the full line.
+impl std::fmt::Display for FieldData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FullLine { text } => write!(f, "{text}"),
+            Self::Subfields { subfields } => subfields
+                .iter()
+                .try_for_each(|subfield| write!(f, "^{}{}", subfield.marker, subfield.text)),
+        }
+    }
+}
+
 impl FieldData {
     pub fn parse<T: ParseTypedField>(self) -> Result<Box<dyn AnyTypedField>, ParseTypedFieldError> {
         T::parse(self)
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Field {
     pub number: u128,
     pub data: FieldData,
@@ -73,6 +83,17 @@ pub enum Error {
     NoNumberPresent,
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IoError(err) => write!(f, "{err}"),
+            Self::NoNumberPresent => write!(f, "Field has no number"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 #[derive(Debug)]
 pub enum FieldReadResult {
     Ok(Field),
@@ -105,63 +126,22 @@ impl<T: io::BufRead> FieldsReader<T> {
             }
         };
 
-        // Remove LF or CRLF from the end
-        if line.ends_with("\n") {
-            line.remove(line.len() - 1);
-        }
-        if line.ends_with("\r") {
-            line.remove(line.len() - 1);
-        }
-
-        // Parse
-        let line = line.trim();
-        if line.len() == 0 {
-            return FieldReadResult::Empty;
-        }
-
-        // Line starts are: `#<integer>:`
-        // Due to format being undocumented, parser will allow omitting `#` and `:`
-        let (number_part, data_part) = get_number_and_data_parts(&line);
-
-        if number_part.len() == 0 {
-            return FieldReadResult::Err(Error::NoNumberPresent);
-        }
-        if data_part.len() == 0 {
-            return FieldReadResult::Empty;
-        }
-
-        // Parse the number
-        let number: u128 = number_part.parse().unwrap();
-
-        // Parse the data
-        let data = if data_part.starts_with("^") {
-            let subfields: Vec<_> = data_part
-                .split("^")
-                .into_iter()
-                .filter(|x| x.len() > 1)
-                .map(|x| {
-                    let mut indices = x.char_indices();
-                    let (_, first_char) = indices.next().unwrap();
-                    let (offset, _) = indices.next().unwrap();
-                    Subfield {
-                        marker: first_char,
-                        text: x[offset..].to_owned(),
-                    }
-                })
-                .collect();
-
-            FieldData::Subfields { subfields }
-        } else {
-            FieldData::FullLine {
-                text: data_part.to_string(),
-            }
-        };
+        parse_line(&line)
+    }
+}
 
-        FieldReadResult::Ok(Field { number, data })
+/// Parses a single line of a record, with or without the line break.
+///
+/// Never returns [`FieldReadResult::End`].
+pub fn parse_line(line: &str) -> FieldReadResult {
+    match FieldRef::parse(line) {
+        None => FieldReadResult::Empty,
+        Some(Ok(field)) => FieldReadResult::Ok(field.to_field()),
+        Some(Err(err)) => FieldReadResult::Err(err),
     }
 }
 
-fn get_number_and_data_parts(mut line: &str) -> (&str, &str) {
+pub(crate) fn get_number_and_data_parts(mut line: &str) -> (&str, &str) {
     // 1. Find where number starts
     if line.starts_with("#") {
         // skip hashtag
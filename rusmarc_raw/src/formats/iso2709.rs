@@ -0,0 +1,337 @@
+//! ISO 2709 exchange format, as used by RUSMARC and MARC 21.
+//!
+//! Fields with tags below 010 are control fields and are read as
+//! [`FieldData::FullLine`]. Data fields get blank indicators on writing; the
+//! indicators of read records are dropped, since [`Field`] has no place for them,
+//! unless read with [`Iso2709Reader::next_marc21`]. Subfield codes are written
+//! in lowercase, as in MARCXML. Data is always written in UTF-8.
+
+use std::io::{self, BufRead, Write};
+
+use crate::field::{Error, Field, FieldData, Subfield};
+use crate::marc21::{Marc21Field, Marc21Record};
+use crate::record::Record;
+
+use super::{has_marc_tag, invalid_data, to_marc21_record, RecordWriter, BLANK_INDICATORS};
+
+const SUBFIELD_DELIMITER: u8 = 0x1F;
+const FIELD_TERMINATOR: u8 = 0x1E;
+const RECORD_TERMINATOR: u8 = 0x1D;
+
+const LEADER_LEN: usize = 24;
+const DIRECTORY_ENTRY_LEN: usize = 12;
+
+/// Parts of the 24-character record label that are not computed on writing.
+///
+/// Names follow RUSMARC; MARC 21 uses the same positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leader {
+    /// Position 5: `n` — new, `c` — corrected, `d` — deleted.
+    pub status: char,
+    /// Position 6: `a` — printed text, `l` — electronic resource, ...
+    pub record_type: char,
+    /// Position 7: `m` — monograph, `s` — serial, `a` — analytic, ...
+    pub bibliographic_level: char,
+    /// Position 8.
+    pub hierarchical_level: char,
+    /// Position 9. MARC 21 uses `a` for UCS / Unicode.
+    pub coding_scheme: char,
+    /// Position 17.
+    pub encoding_level: char,
+    /// Position 18.
+    pub cataloguing_form: char,
+    /// Position 19.
+    pub position_19: char,
+    /// Position 23: blank in RUSMARC, `0` in MARC 21.
+    pub position_23: char,
+}
+
+impl Default for Leader {
+    /// New printed monograph.
+    fn default() -> Self {
+        Self {
+            status: 'n',
+            record_type: 'a',
+            bibliographic_level: 'm',
+            hierarchical_level: ' ',
+            coding_scheme: ' ',
+            encoding_level: ' ',
+            cataloguing_form: ' ',
+            position_19: ' ',
+            position_23: ' ',
+        }
+    }
+}
+
+impl Leader {
+    pub(super) fn parse(label: &[u8]) -> Self {
+        let at = |i: usize| label.get(i).map_or(' ', |b| *b as char);
+        Self {
+            status: at(5),
+            record_type: at(6),
+            bibliographic_level: at(7),
+            hierarchical_level: at(8),
+            coding_scheme: at(9),
+            encoding_level: at(17),
+            cataloguing_form: at(18),
+            position_19: at(19),
+            position_23: at(23),
+        }
+    }
+
+    pub(super) fn to_label(&self, record_len: usize, base_address: usize) -> String {
+        format!(
+            "{record_len:05}{}{}{}{}{}22{base_address:05}{}{}{}450{}",
+            self.status,
+            self.record_type,
+            self.bibliographic_level,
+            self.hierarchical_level,
+            self.coding_scheme,
+            self.encoding_level,
+            self.cataloguing_form,
+            self.position_19,
+            self.position_23,
+        )
+    }
+}
+
+pub struct Iso2709Reader<R: BufRead> {
+    input: R,
+    leader: Option<Leader>,
+}
+
+impl<R: BufRead> Iso2709Reader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            leader: None,
+        }
+    }
+
+    /// Leader of the last read record.
+    pub fn leader(&self) -> Option<&Leader> {
+        self.leader.as_ref()
+    }
+}
+
+impl<R: BufRead> Iso2709Reader<R> {
+    fn read_record(&mut self) -> Option<io::Result<Vec<Result<Marc21Field, Error>>>> {
+        let mut bytes = Vec::new();
+        if let Err(err) = self.input.read_until(RECORD_TERMINATOR, &mut bytes) {
+            return Some(Err(err));
+        }
+
+        // Dumps are often terminated by a line break
+        let start = bytes.iter().position(|b| !b.is_ascii_whitespace())?;
+        let bytes = &bytes[start..];
+
+        Some(parse_record(bytes).map(|(leader, fields)| {
+            self.leader = Some(leader);
+            fields
+        }))
+    }
+
+    /// Reads the next record as MARC 21, keeping its leader and indicators.
+    ///
+    /// Unlike the iterator, fails the whole record on a broken field.
+    pub fn next_marc21(&mut self) -> Option<io::Result<Marc21Record>> {
+        let fields = match self.read_record()? {
+            Ok(fields) => fields,
+            Err(err) => return Some(Err(err)),
+        };
+        let leader = self.leader.clone().unwrap_or_default();
+        Some(to_marc21_record(leader, fields))
+    }
+}
+
+impl<R: BufRead> Iterator for Iso2709Reader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.read_record()?.map(|fields| {
+            fields
+                .into_iter()
+                .map(|field| field.map(Field::from))
+                .collect()
+        }))
+    }
+}
+
+fn parse_number(bytes: &[u8]) -> Option<usize> {
+    std::str::from_utf8(bytes).ok()?.trim().parse().ok()
+}
+
+fn parse_record(bytes: &[u8]) -> io::Result<(Leader, Vec<Result<Marc21Field, Error>>)> {
+    if bytes.len() < LEADER_LEN {
+        return Err(invalid_data("ISO 2709 record is shorter than its leader"));
+    }
+    let label = &bytes[..LEADER_LEN];
+    let base_address = parse_number(&label[12..17])
+        .filter(|address| *address <= bytes.len())
+        .ok_or_else(|| invalid_data("Invalid base address in ISO 2709 leader"))?;
+
+    let directory = &bytes[LEADER_LEN..base_address.saturating_sub(1).max(LEADER_LEN)];
+    let data = &bytes[base_address..];
+
+    let record = directory
+        .chunks_exact(DIRECTORY_ENTRY_LEN)
+        .map(|entry| {
+            let number = parse_number(&entry[0..3]).ok_or(Error::NoNumberPresent)? as u128;
+            let (length, start) = match (parse_number(&entry[3..7]), parse_number(&entry[7..12])) {
+                (Some(length), Some(start)) => (length, start),
+                _ => return Err(Error::IoError(invalid_data("Invalid directory entry"))),
+            };
+            let field = data
+                .get(start..start + length)
+                .ok_or_else(|| Error::IoError(invalid_data("Field is out of record bounds")))?;
+            let field = field.strip_suffix(&[FIELD_TERMINATOR]).unwrap_or(field);
+
+            let (indicators, data) = match field {
+                _ if number < 10 => (BLANK_INDICATORS, field),
+                [ind1, ind2, data @ ..] => ([*ind1 as char, *ind2 as char], data),
+                _ => (BLANK_INDICATORS, &[][..]),
+            };
+
+            Ok(Marc21Field {
+                tag: number,
+                indicators,
+                data: parse_field_data(number, data),
+            })
+        })
+        .collect();
+
+    Ok((Leader::parse(label), record))
+}
+
+fn parse_field_data(number: u128, bytes: &[u8]) -> FieldData {
+    if number < 10 {
+        return FieldData::FullLine {
+            text: String::from_utf8_lossy(bytes).into_owned(),
+        };
+    }
+
+    if !bytes.contains(&SUBFIELD_DELIMITER) {
+        return FieldData::FullLine {
+            text: String::from_utf8_lossy(bytes).into_owned(),
+        };
+    }
+
+    let subfields = bytes
+        .split(|b| *b == SUBFIELD_DELIMITER)
+        .skip(1)
+        .filter_map(|subfield| {
+            let subfield = String::from_utf8_lossy(subfield);
+            let mut chars = subfield.chars();
+            let marker = chars.next()?;
+            Some(Subfield {
+                marker,
+                text: chars.as_str().to_string(),
+            })
+        })
+        .collect();
+
+    FieldData::Subfields { subfields }
+}
+
+/// Writes records in ISO 2709.
+///
+/// Fields with numbers above 999 are skipped.
+pub struct Iso2709Writer<W: Write> {
+    output: W,
+    leader: Leader,
+}
+
+impl<W: Write> Iso2709Writer<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            leader: Leader::default(),
+        }
+    }
+
+    pub fn with_leader(mut self, leader: Leader) -> Self {
+        self.leader = leader;
+        self
+    }
+}
+
+fn write_field_data(data: &FieldData, number: u128, indicators: [char; 2], buffer: &mut Vec<u8>) {
+    if number >= 10 {
+        for indicator in indicators {
+            let mut bytes = [0; 4];
+            buffer.extend_from_slice(indicator.encode_utf8(&mut bytes).as_bytes());
+        }
+    }
+    match data {
+        FieldData::FullLine { text } => buffer.extend_from_slice(text.as_bytes()),
+        FieldData::Subfields { subfields } => {
+            for subfield in subfields {
+                buffer.push(SUBFIELD_DELIMITER);
+                let mut marker = [0; 4];
+                let marker = subfield.marker.to_ascii_lowercase().encode_utf8(&mut marker);
+                buffer.extend_from_slice(marker.as_bytes());
+                buffer.extend_from_slice(subfield.text.as_bytes());
+            }
+        }
+    }
+    buffer.push(FIELD_TERMINATOR);
+}
+
+impl<W: Write> Iso2709Writer<W> {
+    fn write_fields<'f>(
+        &mut self,
+        leader: &Leader,
+        fields: impl IntoIterator<Item = (u128, [char; 2], &'f FieldData)>,
+    ) -> io::Result<()> {
+        let mut directory = String::new();
+        let mut data = Vec::new();
+
+        for (number, indicators, field_data) in fields {
+            let start = data.len();
+            write_field_data(field_data, number, indicators, &mut data);
+            let length = data.len() - start;
+            if length > 9999 || start > 99999 {
+                return Err(invalid_data(format!(
+                    "Field {number} does not fit into an ISO 2709 record"
+                )));
+            }
+            directory.push_str(&format!("{number:03}{length:04}{start:05}"));
+        }
+
+        let base_address = LEADER_LEN + directory.len() + 1;
+        let record_len = base_address + data.len() + 1;
+        if record_len > 99999 {
+            return Err(invalid_data("Record does not fit into an ISO 2709 record"));
+        }
+
+        self.output
+            .write_all(leader.to_label(record_len, base_address).as_bytes())?;
+        self.output.write_all(directory.as_bytes())?;
+        self.output.write_all(&[FIELD_TERMINATOR])?;
+        self.output.write_all(&data)?;
+        self.output.write_all(&[RECORD_TERMINATOR])
+    }
+}
+
+impl<W: Write> RecordWriter for Iso2709Writer<W> {
+    fn write_record(&mut self, record: &[Field]) -> io::Result<()> {
+        let leader = self.leader.clone();
+        let fields = record
+            .iter()
+            .filter(|field| has_marc_tag(field))
+            .map(|field| (field.number, BLANK_INDICATORS, &field.data));
+        self.write_fields(&leader, fields)
+    }
+
+    fn write_marc21(&mut self, record: &Marc21Record) -> io::Result<()> {
+        let fields = record
+            .fields
+            .iter()
+            .map(|field| (field.tag, field.indicators, &field.data));
+        self.write_fields(&record.leader, fields)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
@@ -0,0 +1,64 @@
+use std::io::{self, BufRead, Write};
+
+use crate::field::Field;
+use crate::record::Record;
+
+use super::{invalid_data, RecordWriter};
+
+/// Reads JSON Lines, one record per line. Empty lines are skipped.
+pub struct JsonReader<R: BufRead> {
+    input: R,
+    line: String,
+}
+
+impl<R: BufRead> JsonReader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            line: String::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for JsonReader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.input.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) if self.line.trim().is_empty() => continue,
+                Ok(_) => {
+                    return Some(
+                        serde_json::from_str::<Vec<Field>>(&self.line)
+                            .map(|fields| fields.into_iter().map(Ok).collect())
+                            .map_err(|err| invalid_data(err.to_string())),
+                    )
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+pub struct JsonWriter<W: Write> {
+    output: W,
+}
+
+impl<W: Write> JsonWriter<W> {
+    pub fn new(output: W) -> Self {
+        Self { output }
+    }
+}
+
+impl<W: Write> RecordWriter for JsonWriter<W> {
+    fn write_record(&mut self, record: &[Field]) -> io::Result<()> {
+        serde_json::to_writer(&mut self.output, record)?;
+        writeln!(self.output)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
@@ -0,0 +1,324 @@
+//! MARCXML (<http://www.loc.gov/standards/marcxml/>).
+//!
+//! Tags below 010 are written as `controlfield`, others as `datafield` with
+//! blank indicators. A full-line data field becomes a single subfield `a`.
+//! Record lengths in written leaders are zeros. Leaders and indicators of
+//! read records are dropped, unless read with [`MarcXmlReader::next_marc21`].
+
+use std::io::{self, BufRead, Write};
+
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::field::{Error, Field, FieldData, Subfield};
+use crate::marc21::{Marc21Field, Marc21Record};
+use crate::record::Record;
+
+use super::iso2709::Leader;
+use super::{has_marc_tag, invalid_data, to_marc21_record, RecordWriter, BLANK_INDICATORS};
+
+const NAMESPACE: &str = "http://www.loc.gov/MARC21/slim";
+
+pub struct MarcXmlReader<R: BufRead> {
+    reader: Reader<R>,
+    buffer: Vec<u8>,
+}
+
+impl<R: BufRead> MarcXmlReader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            reader: Reader::from_reader(input),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+/// Element the reader is inside of, for the text it collects.
+enum Current {
+    None,
+    Leader(String),
+    ControlField(Result<u128, Error>, String),
+    DataField(Result<u128, Error>, [char; 2], Vec<Subfield>),
+    Subfield(Result<u128, Error>, [char; 2], Vec<Subfield>, char, String),
+}
+
+/// Parts of a record read so far.
+type Fields = Vec<Result<Marc21Field, Error>>;
+
+fn tag(element: &BytesStart) -> Result<u128, Error> {
+    element
+        .try_get_attribute("tag")
+        .ok()
+        .flatten()
+        .and_then(|tag| tag.unescape_value().ok()?.trim().parse().ok())
+        .ok_or(Error::NoNumberPresent)
+}
+
+fn indicator(element: &BytesStart, name: &str) -> char {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|indicator| indicator.unescape_value().ok()?.chars().next())
+        .unwrap_or(' ')
+}
+
+fn code(element: &BytesStart) -> char {
+    element
+        .try_get_attribute("code")
+        .ok()
+        .flatten()
+        .and_then(|code| code.unescape_value().ok()?.chars().next())
+        .unwrap_or(' ')
+}
+
+fn to_io_error(err: quick_xml::Error) -> io::Error {
+    match err {
+        quick_xml::Error::Io(err) => io::Error::new(err.kind(), err.to_string()),
+        err => invalid_data(err.to_string()),
+    }
+}
+
+impl<R: BufRead> MarcXmlReader<R> {
+    fn read_record(&mut self) -> io::Result<Option<(Leader, Fields)>> {
+        let mut record: Option<(Leader, Fields)> = None;
+        let mut current = Current::None;
+
+        loop {
+            self.buffer.clear();
+            let event = self
+                .reader
+                .read_event_into(&mut self.buffer)
+                .map_err(to_io_error)?;
+
+            match event {
+                Event::Eof => {
+                    return match record {
+                        Some(_) => Err(invalid_data("Unexpected end of MARCXML")),
+                        None => Ok(None),
+                    }
+                }
+                Event::Start(element) => match element.local_name().as_ref() {
+                    b"record" => record = Some((Leader::default(), Vec::new())),
+                    b"leader" => current = Current::Leader(String::new()),
+                    b"controlfield" => {
+                        current = Current::ControlField(tag(&element), String::new())
+                    }
+                    b"datafield" => {
+                        let indicators = [indicator(&element, "ind1"), indicator(&element, "ind2")];
+                        current = Current::DataField(tag(&element), indicators, Vec::new());
+                    }
+                    b"subfield" => {
+                        if let Current::DataField(number, indicators, subfields) = current {
+                            current = Current::Subfield(
+                                number,
+                                indicators,
+                                subfields,
+                                code(&element),
+                                String::new(),
+                            );
+                        } else {
+                            current = Current::None;
+                        }
+                    }
+                    _ => {}
+                },
+                Event::Text(text) => {
+                    if let Current::Leader(buffer)
+                    | Current::ControlField(_, buffer)
+                    | Current::Subfield(_, _, _, _, buffer) = &mut current
+                    {
+                        buffer.push_str(&text.unescape().map_err(to_io_error)?);
+                    }
+                }
+                Event::CData(text) => {
+                    if let Current::Leader(buffer)
+                    | Current::ControlField(_, buffer)
+                    | Current::Subfield(_, _, _, _, buffer) = &mut current
+                    {
+                        buffer.push_str(&String::from_utf8_lossy(&text));
+                    }
+                }
+                Event::End(element) => match element.local_name().as_ref() {
+                    b"record" => return Ok(record),
+                    b"leader" => {
+                        if let (Current::Leader(text), Some((leader, _))) =
+                            (std::mem::replace(&mut current, Current::None), &mut record)
+                        {
+                            *leader = Leader::parse(text.as_bytes());
+                        }
+                    }
+                    b"controlfield" | b"datafield" => {
+                        let field = match std::mem::replace(&mut current, Current::None) {
+                            Current::ControlField(number, text) => number.map(|tag| Marc21Field {
+                                tag,
+                                indicators: BLANK_INDICATORS,
+                                data: FieldData::FullLine { text },
+                            }),
+                            Current::DataField(number, indicators, subfields) => {
+                                number.map(|tag| Marc21Field {
+                                    tag,
+                                    indicators,
+                                    data: FieldData::Subfields { subfields },
+                                })
+                            }
+                            _ => continue,
+                        };
+                        if let Some((_, fields)) = &mut record {
+                            fields.push(field);
+                        }
+                    }
+                    b"subfield" => {
+                        if let Current::Subfield(number, indicators, mut subfields, marker, text) =
+                            std::mem::replace(&mut current, Current::None)
+                        {
+                            subfields.push(Subfield { marker, text });
+                            current = Current::DataField(number, indicators, subfields);
+                        }
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<R: BufRead> MarcXmlReader<R> {
+    /// Reads the next record as MARC 21, keeping its leader and indicators.
+    ///
+    /// Unlike the iterator, fails the whole record on a broken field.
+    pub fn next_marc21(&mut self) -> Option<io::Result<Marc21Record>> {
+        match self.read_record() {
+            Ok(record) => record.map(|(leader, fields)| to_marc21_record(leader, fields)),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for MarcXmlReader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, fields) = match self.read_record() {
+            Ok(record) => record?,
+            Err(err) => return Some(Err(err)),
+        };
+        Some(Ok(fields
+            .into_iter()
+            .map(|field| field.map(Field::from))
+            .collect()))
+    }
+}
+
+/// Writes a MARCXML `collection`. Fields with numbers above 999 are skipped.
+pub struct MarcXmlWriter<W: Write> {
+    output: W,
+    leader: Leader,
+    started: bool,
+}
+
+impl<W: Write> MarcXmlWriter<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            leader: Leader::default(),
+            started: false,
+        }
+    }
+
+    pub fn with_leader(mut self, leader: Leader) -> Self {
+        self.leader = leader;
+        self
+    }
+
+    fn start(&mut self) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            writeln!(self.output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+            writeln!(self.output, r#"<collection xmlns="{NAMESPACE}">"#)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> MarcXmlWriter<W> {
+    fn write_fields<'f>(
+        &mut self,
+        leader: &Leader,
+        fields: impl IntoIterator<Item = (u128, [char; 2], &'f FieldData)>,
+    ) -> io::Result<()> {
+        self.start()?;
+        writeln!(self.output, "  <record>")?;
+        writeln!(
+            self.output,
+            "    <leader>{}</leader>",
+            leader.to_label(0, 0)
+        )?;
+
+        for (number, [ind1, ind2], data) in fields {
+            let tag = format!("{number:03}");
+            match data {
+                FieldData::FullLine { text } if number < 10 => writeln!(
+                    self.output,
+                    r#"    <controlfield tag="{tag}">{}</controlfield>"#,
+                    escape(text.as_str())
+                )?,
+                data => {
+                    writeln!(
+                        self.output,
+                        r#"    <datafield tag="{tag}" ind1="{}" ind2="{}">"#,
+                        escape(ind1.to_string()),
+                        escape(ind2.to_string())
+                    )?;
+                    match data {
+                        FieldData::FullLine { text } => writeln!(
+                            self.output,
+                            r#"      <subfield code="a">{}</subfield>"#,
+                            escape(text.as_str())
+                        )?,
+                        FieldData::Subfields { subfields } => {
+                            for subfield in subfields {
+                                writeln!(
+                                    self.output,
+                                    r#"      <subfield code="{}">{}</subfield>"#,
+                                    escape(subfield.marker.to_lowercase().to_string()),
+                                    escape(subfield.text.as_str())
+                                )?;
+                            }
+                        }
+                    }
+                    writeln!(self.output, "    </datafield>")?;
+                }
+            }
+        }
+
+        writeln!(self.output, "  </record>")
+    }
+}
+
+impl<W: Write> RecordWriter for MarcXmlWriter<W> {
+    fn write_record(&mut self, record: &[Field]) -> io::Result<()> {
+        let leader = self.leader.clone();
+        let fields = record
+            .iter()
+            .filter(|field| has_marc_tag(field))
+            .map(|field| (field.number, BLANK_INDICATORS, &field.data));
+        self.write_fields(&leader, fields)
+    }
+
+    fn write_marc21(&mut self, record: &Marc21Record) -> io::Result<()> {
+        let fields = record
+            .fields
+            .iter()
+            .map(|field| (field.tag, field.indicators, &field.data));
+        self.write_fields(&record.leader, fields)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.start()?;
+        writeln!(self.output, "</collection>")?;
+        self.output.flush()
+    }
+}
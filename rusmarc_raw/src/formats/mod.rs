@@ -0,0 +1,207 @@
+//! Reading and writing records in interchange formats.
+//!
+//! - [`Format::Text`] — export format of the vendors, see the crate README.
+//! - [`Format::Iso2709`] — binary exchange format (`.iso`, `.mrc`).
+//! - [`Format::MarcXml`] — MARCXML slim schema.
+//! - [`Format::Json`] — JSON Lines: every line is one record, an array of [`Field`]s.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! use rusmarc_raw::formats::{read_records, record_writer, Format};
+//! use rusmarc_raw::record::record_remove_errors;
+//!
+//! let data = "#1: id-001\n#200: ^AНазвание^FАвтор\n*****\n#1: id-002\n";
+//!
+//! let mut iso = Vec::new();
+//! let mut writer = record_writer(&mut iso, Format::Iso2709);
+//! for record in read_records(Cursor::new(data), Format::Text) {
+//!     writer.write_record(&record_remove_errors(record.unwrap())).unwrap();
+//! }
+//! writer.finish().unwrap();
+//! drop(writer);
+//!
+//! let mut text = Vec::new();
+//! let mut writer = record_writer(&mut text, Format::Text);
+//! for record in read_records(Cursor::new(iso), Format::Iso2709) {
+//!     writer.write_record(&record_remove_errors(record.unwrap())).unwrap();
+//! }
+//! drop(writer);
+//!
+//! // Subfield codes are lowercase in ISO 2709 and MARCXML
+//! assert_eq!(
+//!     String::from_utf8(text).unwrap(),
+//!     "#1: id-001\n#200: ^aНазвание^fАвтор\n*****\n#1: id-002\n*****\n"
+//! );
+//! ```
+
+pub mod iso2709;
+pub mod json;
+pub mod marcxml;
+pub mod text;
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::field::{self, Field};
+use crate::marc21::{Marc21Field, Marc21Record};
+use crate::record::{Record, RecordsReader};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Iso2709,
+    MarcXml,
+    Json,
+}
+
+impl Format {
+    /// Guesses the format by the file extension.
+    ///
+    /// ```
+    /// use rusmarc_raw::formats::Format;
+    ///
+    /// assert_eq!(Format::from_path("dump.mrc".as_ref()), Some(Format::Iso2709));
+    /// assert_eq!(Format::from_path("LAN.TXT".as_ref()), Some(Format::Text));
+    /// assert_eq!(Format::from_path("dump".as_ref()), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "txt" => Some(Self::Text),
+            "iso" | "mrc" | "marc" => Some(Self::Iso2709),
+            "xml" => Some(Self::MarcXml),
+            "json" | "jsonl" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Extension for files written in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Iso2709 => "iso",
+            Self::MarcXml => "xml",
+            Self::Json => "jsonl",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(Self::Text),
+            "iso2709" | "iso" | "mrc" => Ok(Self::Iso2709),
+            "marcxml" | "xml" => Ok(Self::MarcXml),
+            "json" | "jsonl" => Ok(Self::Json),
+            other => Err(format!(
+                "Unknown format `{other}`, expected one of: text, iso2709, marcxml, json"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Text => "text",
+            Self::Iso2709 => "iso2709",
+            Self::MarcXml => "marcxml",
+            Self::Json => "json",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Destination for records in some format.
+pub trait RecordWriter {
+    fn write_record(&mut self, record: &[Field]) -> io::Result<()>;
+
+    /// Writes a MARC 21 record with its leader and indicators.
+    ///
+    /// Only ISO 2709 and MARCXML can hold one.
+    fn write_marc21(&mut self, _record: &Marc21Record) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "MARC 21 records can only be written as ISO 2709 or MARCXML",
+        ))
+    }
+
+    /// Writes whatever the format needs after the last record and flushes the output.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+pub fn read_records<'a>(
+    input: impl BufRead + 'a,
+    format: Format,
+) -> Box<dyn Iterator<Item = io::Result<Record>> + 'a> {
+    match format {
+        Format::Text => Box::new(RecordsReader::new(input)),
+        Format::Iso2709 => Box::new(iso2709::Iso2709Reader::new(input)),
+        Format::MarcXml => Box::new(marcxml::MarcXmlReader::new(input)),
+        Format::Json => Box::new(json::JsonReader::new(input)),
+    }
+}
+
+/// MARC 21 records with their leaders and indicators.
+///
+/// Only ISO 2709 and MARCXML hold indicators; other formats give a single error.
+pub fn read_marc21<'a>(
+    input: impl BufRead + 'a,
+    format: Format,
+) -> Box<dyn Iterator<Item = io::Result<Marc21Record>> + 'a> {
+    match format {
+        Format::Iso2709 => {
+            let mut reader = iso2709::Iso2709Reader::new(input);
+            Box::new(std::iter::from_fn(move || reader.next_marc21()))
+        }
+        Format::MarcXml => {
+            let mut reader = marcxml::MarcXmlReader::new(input);
+            Box::new(std::iter::from_fn(move || reader.next_marc21()))
+        }
+        Format::Text | Format::Json => Box::new(std::iter::once(Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "MARC 21 records can only be read from ISO 2709 or MARCXML",
+        )))),
+    }
+}
+
+pub fn record_writer<'a>(output: impl Write + 'a, format: Format) -> Box<dyn RecordWriter + 'a> {
+    match format {
+        Format::Text => Box::new(text::TextWriter::new(output)),
+        Format::Iso2709 => Box::new(iso2709::Iso2709Writer::new(output)),
+        Format::MarcXml => Box::new(marcxml::MarcXmlWriter::new(output)),
+        Format::Json => Box::new(json::JsonWriter::new(output)),
+    }
+}
+
+const BLANK_INDICATORS: [char; 2] = [' ', ' '];
+
+/// Fields with numbers above 999 are local to vendor exports and have no
+/// place in formats with three-character tags.
+fn has_marc_tag(field: &Field) -> bool {
+    field.number <= 999
+}
+
+/// Fails on the first broken field: a MARC 21 record has no place for errors.
+fn to_marc21_record(
+    leader: iso2709::Leader,
+    fields: Vec<Result<Marc21Field, field::Error>>,
+) -> io::Result<Marc21Record> {
+    let fields = fields
+        .into_iter()
+        .map(|field| {
+            field.map_err(|err| match err {
+                field::Error::NoNumberPresent => invalid_data("Field has no tag"),
+                field::Error::IoError(err) => err,
+            })
+        })
+        .collect::<io::Result<_>>()?;
+    Ok(Marc21Record { leader, fields })
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
@@ -0,0 +1,29 @@
+use std::io::{self, Write};
+
+use crate::field::Field;
+
+use super::RecordWriter;
+
+/// Writes records in the vendor text format, which [`crate::record::RecordsReader`] reads.
+pub struct TextWriter<W: Write> {
+    output: W,
+}
+
+impl<W: Write> TextWriter<W> {
+    pub fn new(output: W) -> Self {
+        Self { output }
+    }
+}
+
+impl<W: Write> RecordWriter for TextWriter<W> {
+    fn write_record(&mut self, record: &[Field]) -> io::Result<()> {
+        for field in record {
+            writeln!(self.output, "#{}: {}", field.number, field.data)?;
+        }
+        writeln!(self.output, "*****")
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
@@ -0,0 +1,599 @@
+//! Standard numbers found in 01x and 07x fields: ISBN, ISSN, ISMN and EAN-13.
+//!
+//! Vendors write these numbers with hyphens, spaces, `ISBN` prefixes and
+//! trailing qualifiers like `(в пер.)`. Every type here accepts such raw text,
+//! verifies the check digit and keeps only the canonical compact form, so two
+//! values compare (and hash) equal no matter how they were written.
+//!
+//! ```
+//! use rusmarc_raw::identifiers::{Isbn, Issn};
+//!
+//! let lan: Isbn = "ISBN 5-8114-1234-7 (в пер.)".parse().unwrap();
+//! let znanium: Isbn = "978-5-8114-1234-1".parse().unwrap();
+//! assert_eq!(lan, znanium);
+//! assert_eq!(lan.compact(), "9785811412341");
+//! assert_eq!(lan.to_isbn10().unwrap(), "5811412347");
+//! assert_eq!(lan.hyphenated().unwrap(), "978-5-8114-1234-1");
+//!
+//! let issn: Issn = "0317-8471".parse().unwrap();
+//! assert_eq!(issn.to_string(), "0317-8471");
+//! assert!("0317-8472".parse::<Issn>().is_err());
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierError {
+    /// No digits were found in the text
+    Empty,
+    /// Amount of digits does not match any form of the identifier
+    InvalidLength(usize),
+    /// `X` is found where only digits are allowed
+    UnexpectedX,
+    /// Check digit does not match the rest of the number
+    InvalidChecksum { expected: char, found: char },
+    /// Number starts with a prefix that this identifier cannot have
+    InvalidPrefix(String),
+}
+
+impl fmt::Display for IdentifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "No digits found"),
+            Self::InvalidLength(len) => write!(f, "Unexpected amount of digits: {len}"),
+            Self::UnexpectedX => write!(f, "`X` is only allowed as a check digit"),
+            Self::InvalidChecksum { expected, found } => {
+                write!(f, "Invalid check digit `{found}`, expected `{expected}`")
+            }
+            Self::InvalidPrefix(prefix) => write!(f, "Unexpected prefix `{prefix}`"),
+        }
+    }
+}
+
+impl std::error::Error for IdentifierError {}
+
+/// Collects digits (and `X`) of the first number in `text`.
+///
+/// Hyphens are skipped. A space is skipped too, unless the digits collected so
+/// far already form a number of one of the `lengths`: this way
+/// `5-8114-1234-7 3000 экз.` does not swallow the circulation.
+fn extract_digits(text: &str, lengths: &[usize]) -> Result<String, IdentifierError> {
+    let mut digits = String::new();
+
+    for c in text.chars().skip_while(|c| !c.is_ascii_digit()) {
+        match c {
+            '0'..='9' => digits.push(c),
+            'X' | 'x' | 'Х' | 'х' => {
+                // `X` is always the last character (cyrillic `Х` is a common typo)
+                digits.push('X');
+                break;
+            }
+            '-' | '\u{2010}'..='\u{2015}' => continue,
+            ' ' | '\u{a0}' if !lengths.contains(&digits.len()) => continue,
+            _ => break,
+        }
+    }
+
+    if digits.is_empty() {
+        return Err(IdentifierError::Empty);
+    }
+    if !lengths.contains(&digits.len()) {
+        return Err(IdentifierError::InvalidLength(digits.len()));
+    }
+    Ok(digits)
+}
+
+fn digit_values(digits: &str) -> Result<Vec<u32>, IdentifierError> {
+    digits
+        .chars()
+        .map(|c| c.to_digit(10).ok_or(IdentifierError::UnexpectedX))
+        .collect()
+}
+
+/// Mod 11 check digit with weights `n+1..2`, as used by ISBN-10 and ISSN.
+fn mod11_check_digit(body: &str) -> Result<char, IdentifierError> {
+    let values = digit_values(body)?;
+    let weight_start = values.len() as u32 + 1;
+    let sum: u32 = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v * (weight_start - i as u32))
+        .sum();
+
+    Ok(match (11 - sum % 11) % 11 {
+        10 => 'X',
+        digit => char::from_digit(digit, 10).unwrap(),
+    })
+}
+
+/// Mod 10 check digit with weights `1, 3, 1, 3...`, as used by EAN-13.
+fn ean13_check_digit(body: &str) -> Result<char, IdentifierError> {
+    let values = digit_values(body)?;
+    let sum: u32 = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| if i % 2 == 0 { *v } else { v * 3 })
+        .sum();
+
+    Ok(char::from_digit((10 - sum % 10) % 10, 10).unwrap())
+}
+
+fn verify_check_digit(digits: &str, expected: char) -> Result<(), IdentifierError> {
+    let found = digits.chars().last().unwrap();
+    if found == expected {
+        Ok(())
+    } else {
+        Err(IdentifierError::InvalidChecksum { expected, found })
+    }
+}
+
+fn parse_ean13_digits(digits: &str) -> Result<(), IdentifierError> {
+    let expected = ean13_check_digit(&digits[..12])?;
+    verify_check_digit(digits, expected)
+}
+
+/// Range of 7-digit values and the length of the element that uses them.
+type Ranges = &'static [(u32, u32, usize)];
+
+/// Registration group lengths for ISBN prefixes 978 and 979.
+const GROUP_RANGES: &[(&str, Ranges)] = &[
+    (
+        "978",
+        &[
+            (0, 5999999, 1),
+            (6000000, 6499999, 3),
+            (6500000, 6599999, 2),
+            (6600000, 6999999, 3),
+            (7000000, 7999999, 1),
+            (8000000, 9499999, 2),
+            (9500000, 9899999, 3),
+            (9900000, 9989999, 4),
+            (9990000, 9999999, 5),
+        ],
+    ),
+    ("979", &[(1000000, 1299999, 2), (8000000, 8499999, 1)]),
+];
+
+/// Registrant (publisher) lengths for the most common registration groups.
+///
+/// This is a subset of the International ISBN Agency range message, covering
+/// the groups our vendors use. ISBNs from other groups cannot be hyphenated.
+const REGISTRANT_RANGES: &[(&str, Ranges)] = &[
+    (
+        "978-0",
+        &[
+            (0, 1999999, 2),
+            (2000000, 6999999, 3),
+            (7000000, 8499999, 4),
+            (8500000, 8999999, 5),
+            (9000000, 9499999, 6),
+            (9500000, 9999999, 7),
+        ],
+    ),
+    (
+        "978-1",
+        &[
+            (0, 999999, 2),
+            (1000000, 3999999, 3),
+            (4000000, 5499999, 4),
+            (5500000, 8697999, 5),
+            (8698000, 9989999, 6),
+            (9990000, 9999999, 7),
+        ],
+    ),
+    (
+        "978-2",
+        &[
+            (0, 1999999, 2),
+            (2000000, 3499999, 3),
+            (3500000, 3999999, 5),
+            (4000000, 6999999, 3),
+            (7000000, 8399999, 4),
+            (8400000, 8999999, 5),
+            (9000000, 9499999, 6),
+            (9500000, 9999999, 7),
+        ],
+    ),
+    (
+        "978-3",
+        &[
+            (0, 299999, 2),
+            (300000, 339999, 3),
+            (340000, 369999, 4),
+            (370000, 399999, 5),
+            (400000, 1999999, 2),
+            (2000000, 6999999, 3),
+            (7000000, 8499999, 4),
+            (8500000, 8999999, 5),
+            (9000000, 9499999, 6),
+            (9500000, 9539999, 7),
+            (9540000, 9699999, 5),
+            (9700000, 9849999, 7),
+            (9850000, 9999999, 5),
+        ],
+    ),
+    (
+        "978-4",
+        &[
+            (0, 1999999, 2),
+            (2000000, 6999999, 3),
+            (7000000, 8499999, 4),
+            (8500000, 8999999, 5),
+            (9000000, 9499999, 6),
+            (9500000, 9999999, 7),
+        ],
+    ),
+    (
+        "978-5",
+        &[
+            (0, 1999999, 2),
+            (2000000, 6999999, 3),
+            (7000000, 8499999, 4),
+            (8500000, 8999999, 5),
+            (9000000, 9099999, 6),
+            (9100000, 9199999, 5),
+            (9200000, 9299999, 4),
+            (9300000, 9499999, 5),
+            (9500000, 9500999, 7),
+            (9501000, 9799999, 4),
+            (9800000, 9899999, 5),
+            (9900000, 9909999, 7),
+            (9910000, 9999999, 4),
+        ],
+    ),
+    (
+        "978-7",
+        &[
+            (0, 999999, 2),
+            (1000000, 4999999, 3),
+            (5000000, 7999999, 4),
+            (8000000, 8999999, 5),
+            (9000000, 9999999, 6),
+        ],
+    ),
+    (
+        "979-10",
+        &[
+            (0, 1999999, 2),
+            (2000000, 6999999, 3),
+            (7000000, 8999999, 4),
+            (9000000, 9759999, 5),
+            (9760000, 9999999, 6),
+        ],
+    ),
+];
+
+/// Looks up the element length for the first 7 digits of `digits`.
+fn range_length(ranges: Ranges, digits: &str) -> Option<usize> {
+    let mut padded: String = digits.chars().take(7).collect();
+    while padded.len() < 7 {
+        padded.push('0');
+    }
+    let value: u32 = padded.parse().ok()?;
+
+    ranges
+        .iter()
+        .find(|(start, end, _)| (*start..=*end).contains(&value))
+        .map(|(_, _, length)| *length)
+}
+
+fn find_ranges(table: &[(&str, Ranges)], key: &str) -> Option<Ranges> {
+    table
+        .iter()
+        .find(|(prefix, _)| *prefix == key)
+        .map(|(_, ranges)| *ranges)
+}
+
+/// International Standard Book Number.
+///
+/// Both ISBN-10 and ISBN-13 are accepted; the value is always stored as ISBN-13.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Isbn {
+    digits: String,
+}
+
+impl Isbn {
+    pub fn parse(text: &str) -> Result<Self, IdentifierError> {
+        let digits = extract_digits(text, &[10, 13])?;
+
+        if digits.len() == 10 {
+            if digits[..9].contains('X') {
+                return Err(IdentifierError::UnexpectedX);
+            }
+            verify_check_digit(&digits, mod11_check_digit(&digits[..9])?)?;
+
+            let body = format!("978{}", &digits[..9]);
+            let check = ean13_check_digit(&body)?;
+            return Ok(Self {
+                digits: format!("{body}{check}"),
+            });
+        }
+
+        parse_ean13_digits(&digits)?;
+        if !(digits.starts_with("978") || digits.starts_with("979")) || digits.starts_with("9790") {
+            return Err(IdentifierError::InvalidPrefix(digits[..4].to_string()));
+        }
+        Ok(Self { digits })
+    }
+
+    /// ISBN-13 without hyphens, e.g. `9785811412341`.
+    pub fn compact(&self) -> &str {
+        &self.digits
+    }
+
+    /// ISBN-10 without hyphens. Only ISBNs with `978` prefix have one.
+    pub fn to_isbn10(&self) -> Option<String> {
+        if !self.digits.starts_with("978") {
+            return None;
+        }
+        let body = &self.digits[3..12];
+        let check = mod11_check_digit(body).ok()?;
+        Some(format!("{body}{check}"))
+    }
+
+    /// ISBN-13 split into prefix, registration group, registrant, publication
+    /// and check digit: `978-5-8114-1234-1`.
+    ///
+    /// Returns `None` if the registration group is not in the embedded range table.
+    pub fn hyphenated(&self) -> Option<String> {
+        let prefix = &self.digits[..3];
+        let rest = &self.digits[3..12];
+        let check = &self.digits[12..];
+
+        let group_length = range_length(find_ranges(GROUP_RANGES, prefix)?, rest)?;
+        let (group, rest) = rest.split_at(group_length);
+
+        let registrant_ranges = find_ranges(REGISTRANT_RANGES, &format!("{prefix}-{group}"))?;
+        let registrant_length = range_length(registrant_ranges, rest)?;
+        if registrant_length >= rest.len() {
+            return None;
+        }
+        let (registrant, publication) = rest.split_at(registrant_length);
+
+        Some(format!(
+            "{prefix}-{group}-{registrant}-{publication}-{check}"
+        ))
+    }
+
+    /// ISBN-10 with hyphens: `5-8114-1234-7`.
+    pub fn hyphenated_isbn10(&self) -> Option<String> {
+        let isbn10 = self.to_isbn10()?;
+        let hyphenated = self.hyphenated()?;
+        // Drop `978-` and replace the check digit
+        let body = &hyphenated[4..hyphenated.len() - 1];
+        Some(format!("{body}{}", isbn10.chars().last().unwrap()))
+    }
+
+    pub fn to_ean13(&self) -> Ean13 {
+        Ean13 {
+            digits: self.digits.clone(),
+        }
+    }
+}
+
+impl FromStr for Isbn {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Isbn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.hyphenated() {
+            Some(hyphenated) => write!(f, "{hyphenated}"),
+            None => write!(f, "{}", self.digits),
+        }
+    }
+}
+
+/// International Standard Serial Number.
+///
+/// Accepts `1234-5679` as well as an EAN-13 with `977` prefix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Issn {
+    digits: String,
+}
+
+impl Issn {
+    pub fn parse(text: &str) -> Result<Self, IdentifierError> {
+        let digits = extract_digits(text, &[8, 13])?;
+
+        if digits.len() == 13 {
+            parse_ean13_digits(&digits)?;
+            if !digits.starts_with("977") {
+                return Err(IdentifierError::InvalidPrefix(digits[..3].to_string()));
+            }
+            let body = &digits[3..10];
+            let check = mod11_check_digit(body)?;
+            return Ok(Self {
+                digits: format!("{body}{check}"),
+            });
+        }
+
+        if digits[..7].contains('X') {
+            return Err(IdentifierError::UnexpectedX);
+        }
+        verify_check_digit(&digits, mod11_check_digit(&digits[..7])?)?;
+        Ok(Self { digits })
+    }
+
+    /// ISSN without hyphen, e.g. `03178471`.
+    pub fn compact(&self) -> &str {
+        &self.digits
+    }
+
+    /// EAN-13 with `977` prefix and `00` issue variant.
+    pub fn to_ean13(&self) -> Ean13 {
+        let body = format!("977{}00", &self.digits[..7]);
+        let check = ean13_check_digit(&body).unwrap();
+        Ean13 {
+            digits: format!("{body}{check}"),
+        }
+    }
+}
+
+impl FromStr for Issn {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Issn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", &self.digits[..4], &self.digits[4..])
+    }
+}
+
+/// Registrant lengths for ISMN (after the `979-0` prefix).
+const ISMN_REGISTRANT_RANGES: Ranges = &[
+    (0, 999999, 3),
+    (1000000, 3999999, 4),
+    (4000000, 6999999, 5),
+    (7000000, 8999999, 6),
+    (9000000, 9999999, 7),
+];
+
+/// International Standard Music Number.
+///
+/// Accepts both the current `979-0-2306-7118-7` form and the legacy `M-2306-7118-7` one.
+///
+/// ```
+/// use rusmarc_raw::identifiers::Ismn;
+///
+/// let legacy: Ismn = "M-2306-7118-7".parse().unwrap();
+/// let current: Ismn = "979-0-2306-7118-7".parse().unwrap();
+/// assert_eq!(legacy, current);
+/// assert_eq!(current.to_legacy(), "M230671187");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ismn {
+    digits: String,
+}
+
+impl Ismn {
+    pub fn parse(text: &str) -> Result<Self, IdentifierError> {
+        let text = text.trim();
+        let text = text
+            .strip_prefix("ISMN")
+            .or_else(|| text.strip_prefix("ismn"))
+            .unwrap_or(text)
+            .trim_start();
+
+        // Legacy form: `M` stands for `9790` and keeps the same check digit
+        let digits = match text.strip_prefix(['M', 'm', 'М', 'м']) {
+            Some(rest) => format!("9790{}", extract_digits(rest, &[9])?),
+            None => extract_digits(text, &[13])?,
+        };
+
+        parse_ean13_digits(&digits)?;
+        if !digits.starts_with("9790") {
+            return Err(IdentifierError::InvalidPrefix(digits[..4].to_string()));
+        }
+        Ok(Self { digits })
+    }
+
+    /// ISMN-13 without hyphens, e.g. `9790230671187`.
+    pub fn compact(&self) -> &str {
+        &self.digits
+    }
+
+    /// Legacy 10-character form, e.g. `M230671187`.
+    pub fn to_legacy(&self) -> String {
+        format!("M{}", &self.digits[4..])
+    }
+
+    /// ISMN split into prefix, registrant, item and check digit: `979-0-2306-7118-7`.
+    pub fn hyphenated(&self) -> String {
+        let rest = &self.digits[4..12];
+        let registrant_length = range_length(ISMN_REGISTRANT_RANGES, rest).unwrap();
+        let (registrant, item) = rest.split_at(registrant_length);
+        format!("979-0-{registrant}-{item}-{}", &self.digits[12..])
+    }
+
+    pub fn to_ean13(&self) -> Ean13 {
+        Ean13 {
+            digits: self.digits.clone(),
+        }
+    }
+}
+
+impl FromStr for Ismn {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Ismn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.hyphenated())
+    }
+}
+
+/// International Article Number (EAN-13).
+///
+/// A 12-digit UPC-A is accepted as well and is stored with a leading zero.
+///
+/// ```
+/// use rusmarc_raw::identifiers::{Ean13, Isbn};
+///
+/// let ean: Ean13 = "9785811412341".parse().unwrap();
+/// assert_eq!(ean.to_isbn(), Some("5-8114-1234-7".parse::<Isbn>().unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ean13 {
+    digits: String,
+}
+
+impl Ean13 {
+    pub fn parse(text: &str) -> Result<Self, IdentifierError> {
+        let mut digits = extract_digits(text, &[12, 13])?;
+        if digits.len() == 12 {
+            digits.insert(0, '0');
+        }
+
+        parse_ean13_digits(&digits)?;
+        Ok(Self { digits })
+    }
+
+    pub fn compact(&self) -> &str {
+        &self.digits
+    }
+
+    /// Whether the number belongs to the "Bookland" (978/979) range.
+    pub fn is_bookland(&self) -> bool {
+        self.digits.starts_with("978") || self.digits.starts_with("979")
+    }
+
+    pub fn to_isbn(&self) -> Option<Isbn> {
+        Isbn::parse(&self.digits).ok()
+    }
+
+    pub fn to_issn(&self) -> Option<Issn> {
+        Issn::parse(&self.digits).ok()
+    }
+
+    pub fn to_ismn(&self) -> Option<Ismn> {
+        Ismn::parse(&self.digits).ok()
+    }
+}
+
+impl FromStr for Ean13 {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Ean13 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.digits)
+    }
+}
@@ -1,5 +1,16 @@
 // #![feature(trait_upcasting)]
 
+pub mod codes;
+pub mod dedup;
+pub mod description;
+pub mod diff;
 pub mod field;
+pub mod formats;
+pub mod identifiers;
+pub mod marc21;
+pub mod parallel;
+pub mod query;
 pub mod record;
+pub mod record_ref;
+pub mod stats;
 pub mod typed_record;
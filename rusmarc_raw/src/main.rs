@@ -1,41 +1,771 @@
-use std::io::Write;
-use std::{fs::File, io::BufReader};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use rusmarc_raw::record::{record_remove_errors, RecordsReader};
-use rusmarc_raw::typed_record::TypedRecord;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::json;
 
-fn main() {
-    println!("Hello, world!");
+use rusmarc_raw::dedup::{Deduplicator, Fingerprint};
+use rusmarc_raw::description::{CitationStyle, Description};
+use rusmarc_raw::diff::{diff, merge};
+use rusmarc_raw::field::Field;
+use rusmarc_raw::formats::{read_marc21, read_records, record_writer, Format, RecordWriter};
+use rusmarc_raw::marc21::{self, Loss, Mapping, Marc21Record};
+use rusmarc_raw::parallel::ParallelRecords;
+use rusmarc_raw::query::Query;
+use rusmarc_raw::record::{record_remove_errors, ErrorlessRecord, Record};
+use rusmarc_raw::stats::Stats;
+use rusmarc_raw::typed_record::{is_unimplemented_field_error, Field001RecordId, TypedRecord};
 
-    let stderr = std::io::stderr();
-    let handle = stderr.lock(); // Get a locked handle to stdout
-    let mut writer = std::io::BufWriter::new(handle);
+/// Inspect and convert RUSMARC dumps.
+#[derive(Parser)]
+#[command(name = "rusmarc", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert records to another format
+    Convert {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Convert RUSMARC to MARC 21 (ISO 2709 or MARCXML output only),
+        /// printing a summary of lost data
+        #[arg(long)]
+        marc21: bool,
+        /// Convert MARC 21 (ISO 2709 or MARCXML input only) to RUSMARC,
+        /// printing a summary of lost data
+        #[arg(long, conflicts_with = "marc21")]
+        from_marc21: bool,
+        /// File with MARC 21 -> RUSMARC rules that take precedence over the built-in ones
+        #[arg(long, requires = "from_marc21")]
+        mapping: Option<PathBuf>,
+    },
+    /// Print tag and subfield frequency and error counts
+    Stats {
+        #[command(flatten)]
+        input: InputArgs,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Report fields that cannot be read, parsed or that hold invalid values
+    Validate {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print records that match a query, e.g. `101$a = eng and 210$d >= 2020`
+    Grep {
+        query: String,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Print records that do not match
+        #[arg(short = 'v', long)]
+        invert: bool,
+        /// Only print the number of matching records
+        #[arg(short, long)]
+        count: bool,
+        /// Run the query on typed fields only
+        #[arg(long)]
+        typed: bool,
+    },
+    /// Print records as Dublin Core (oai_dc) or schema.org JSON-LD
+    Describe {
+        #[command(flatten)]
+        input: InputArgs,
+        /// `dc` writes `oai_dc:dc` elements inside a `records` element,
+        /// `jsonld` writes one JSON object per line
+        #[arg(long = "as", default_value = "jsonld")]
+        format: DescriptionFormat,
+        /// Output file, stdout if omitted or `-`
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print references to records in a citation style
+    Cite {
+        #[command(flatten)]
+        input: InputArgs,
+        /// gost (GOST R 7.0.100-2018), apa, bibtex, ris or csl-json
+        #[arg(short, long, default_value = "gost")]
+        style: CitationStyle,
+        /// Output file, stdout if omitted or `-`
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print field and subfield changes between records with the same 001
+    Diff {
+        /// Records before the changes
+        old: PathBuf,
+        /// Records after the changes
+        new: PathBuf,
+        /// Format of both files, guessed by the file extension if omitted; text by default
+        #[arg(short, long)]
+        from: Option<Format>,
+        /// Print one JSON object per changed record
+        #[arg(long)]
+        json: bool,
+    },
+    /// Apply vendor's updates to locally edited records with the same 001,
+    /// printing conflicts
+    Merge {
+        /// Records as the vendor sent them before
+        base: PathBuf,
+        /// Records with our edits
+        local: PathBuf,
+        /// Records as the vendor sent them now
+        remote: PathBuf,
+        /// Format of the input files, guessed by the file extension if omitted; text by default
+        #[arg(short, long)]
+        from: Option<Format>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Print clusters of records that describe the same document, matched by
+    /// ISBN or by title, first author and year
+    Dedup {
+        /// Input files, records of all of them are compared with each other
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        /// Format of the input files, guessed by the file extension if omitted; text by default
+        #[arg(short, long)]
+        from: Option<Format>,
+        /// Lowest confidence of a match, from 0 to 1
+        #[arg(long, default_value_t = 0.85)]
+        threshold: f32,
+        /// Print one JSON object per cluster
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the first records
+    Head {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Number of records
+        #[arg(short = 'n', long, default_value_t = 10)]
+        records: usize,
+    },
+    /// Split records into files of `<prefix><number>.<extension>`
+    Split {
+        #[command(flatten)]
+        input: InputArgs,
+        /// Output format
+        #[arg(short, long, default_value = "text")]
+        to: Format,
+        /// Number of records per file
+        #[arg(short = 'n', long, default_value_t = 1000)]
+        records: usize,
+        /// Prefix of the output files, may include a directory
+        #[arg(long, default_value = "part-")]
+        prefix: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DescriptionFormat {
+    Dc,
+    Jsonld,
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Input file, stdin if omitted or `-`
+    input: Option<PathBuf>,
+    /// Input format, guessed by the file extension if omitted; text by default
+    #[arg(short, long)]
+    from: Option<Format>,
+}
+
+#[derive(Args)]
+struct OutputArgs {
+    /// Output file, stdout if omitted or `-`
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Output format, guessed by the file extension if omitted; text by default
+    #[arg(short, long)]
+    to: Option<Format>,
+}
+
+type Records = Box<dyn Iterator<Item = io::Result<Record>>>;
+
+fn is_stdio(path: &Option<PathBuf>) -> bool {
+    path.as_deref().is_none_or(|path| path == Path::new("-"))
+}
+
+fn guess_format(path: &Option<PathBuf>, format: Option<Format>) -> Format {
+    format
+        .or_else(|| path.as_deref().and_then(Format::from_path))
+        .unwrap_or(Format::Text)
+}
+
+impl InputArgs {
+    fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        let input: Box<dyn BufRead> = if is_stdio(&self.input) {
+            Box::new(io::stdin().lock())
+        } else {
+            Box::new(BufReader::new(File::open(self.input.as_ref().unwrap())?))
+        };
+        Ok(input)
+    }
+
+    fn open(&self) -> io::Result<Records> {
+        Ok(read_records(
+            self.reader()?,
+            guess_format(&self.input, self.from),
+        ))
+    }
+
+    fn open_marc21(&self) -> io::Result<Box<dyn Iterator<Item = io::Result<Marc21Record>>>> {
+        Ok(read_marc21(
+            self.reader()?,
+            guess_format(&self.input, self.from),
+        ))
+    }
+
+    /// Records passed through `map`, in parallel for the text format.
+    fn open_mapped<T: Send + 'static>(
+        &self,
+        map: fn(Record) -> T,
+    ) -> io::Result<Box<dyn Iterator<Item = io::Result<T>>>> {
+        if guess_format(&self.input, self.from) != Format::Text {
+            return Ok(Box::new(self.open()?.map(move |record| record.map(map))));
+        }
+
+        Ok(Box::new(ParallelRecords::new(self.reader()?, map)))
+    }
+}
+
+fn create_output(path: &Option<PathBuf>) -> io::Result<Box<dyn Write>> {
+    let output: Box<dyn Write> = if is_stdio(path) {
+        Box::new(BufWriter::new(io::stdout().lock()))
+    } else {
+        Box::new(BufWriter::new(File::create(path.as_ref().unwrap())?))
+    };
+    Ok(output)
+}
+
+impl OutputArgs {
+    fn open(&self) -> io::Result<Box<dyn RecordWriter>> {
+        Ok(record_writer(
+            create_output(&self.output)?,
+            guess_format(&self.output, self.to),
+        ))
+    }
+}
+
+/// Records of a file with their `001`, or position if they have none.
+fn read_by_id(path: &Path, format: Option<Format>) -> io::Result<Vec<(String, ErrorlessRecord)>> {
+    let input = BufReader::new(File::open(path)?);
+    read_records(input, guess_format(&Some(path.to_path_buf()), format))
+        .enumerate()
+        .map(|(i, record)| {
+            let record = record_remove_errors(record?);
+            let id = record
+                .iter()
+                .find(|field| field.number == 1)
+                .and_then(|field| field.data.as_singular_text())
+                .map_or_else(|| format!("#{}", i + 1), str::to_string);
+            Ok((id, record))
+        })
+        .collect()
+}
+
+fn index_by_id(records: &[(String, ErrorlessRecord)]) -> HashMap<&str, &[Field]> {
+    records
+        .iter()
+        .map(|(id, record)| (id.as_str(), record.as_slice()))
+        .collect()
+}
+
+/// `001` of the record, to point at it in messages.
+fn record_id(record: &TypedRecord) -> String {
+    record
+        .get_fields::<Field001RecordId>()
+        .next()
+        .map_or_else(|| "no 001".to_string(), |field| field.id.clone())
+}
+
+fn convert(
+    records: impl Iterator<Item = io::Result<Record>>,
+    mut writer: Box<dyn RecordWriter>,
+    limit: usize,
+) -> io::Result<()> {
+    for record in records.take(limit) {
+        writer.write_record(&record_remove_errors(record?))?;
+    }
+    writer.finish()
+}
+
+/// Counts lost data by `#field$subfield`.
+fn count_losses(counts: &mut BTreeMap<String, usize>, losses: Vec<Loss>) {
+    for loss in losses {
+        let path = match loss.subfield {
+            Some(subfield) => format!("#{}${subfield}", loss.field),
+            None => format!("#{}", loss.field),
+        };
+        *counts.entry(path).or_default() += 1;
+    }
+}
+
+fn print_losses(counts: BTreeMap<String, usize>) {
+    if !counts.is_empty() {
+        eprintln!("Data that was not converted:");
+        for (path, count) in counts {
+            eprintln!("{path:>10} {count:>10}");
+        }
+    }
+}
+
+fn convert_to_marc21(records: Records, mut writer: Box<dyn RecordWriter>) -> io::Result<()> {
+    let mut losses = BTreeMap::new();
+    for record in records {
+        let (record, record_losses) = marc21::from_rusmarc(&record_remove_errors(record?));
+        writer.write_marc21(&record)?;
+        count_losses(&mut losses, record_losses);
+    }
+    writer.finish()?;
+
+    print_losses(losses);
+    Ok(())
+}
+
+fn convert_from_marc21(
+    input: &InputArgs,
+    mut writer: Box<dyn RecordWriter>,
+    mapping: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut rules = Mapping::default();
+    if let Some(path) = mapping {
+        let custom: Mapping = std::fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+        rules = rules.with_rules(custom.into_rules());
+    }
+
+    let mut losses = BTreeMap::new();
+    for record in input.open_marc21()? {
+        let (record, record_losses) = marc21::to_rusmarc(&record?, &rules);
+        writer.write_record(&record)?;
+        count_losses(&mut losses, record_losses);
+    }
+    writer.finish()?;
+
+    print_losses(losses);
+    Ok(())
+}
+
+fn stats(records: Records, json: bool) -> Result<(), Box<dyn Error>> {
+    let mut stats = Stats::default();
+    for record in records {
+        stats.add_record(&record?);
+    }
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    if json {
+        serde_json::to_writer_pretty(&mut out, &stats)?;
+        writeln!(out)?;
+        return Ok(out.flush()?);
+    }
+
+    writeln!(out, "records:            {}", stats.records)?;
+    writeln!(out, "fields:             {}", stats.fields)?;
+    writeln!(out, "field errors:       {}", stats.field_errors)?;
+    writeln!(out, "typed field errors: {}", stats.typed_field_errors)?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{:>10} {:>10} {:>10}  subfields",
+        "tag", "count", "records"
+    )?;
+    for (tag, tag_stats) in &stats.tags {
+        let subfields: Vec<_> = tag_stats
+            .subfields
+            .iter()
+            .map(|(marker, count)| format!("{marker}:{count}"))
+            .collect();
+        writeln!(
+            out,
+            "{tag:>10} {:>10} {:>10}  {}",
+            tag_stats.occurrences,
+            tag_stats.records,
+            subfields.join(" ")
+        )?;
+    }
+    Ok(out.flush()?)
+}
+
+/// `001` of the record and everything that is wrong with it.
+fn record_problems(record: Record) -> (String, Vec<String>) {
+    let mut problems = Vec::new();
+
+    let (fields, errors): (Vec<_>, Vec<_>) = record.into_iter().partition(|x| x.is_ok());
+    problems.extend(errors.into_iter().map(|err| err.unwrap_err().to_string()));
 
-    writeln!(&mut writer, "Number: {}", 123).unwrap();
+    let (typed, errors) = TypedRecord::parse(fields.into_iter().map(|x| x.unwrap()));
+    problems.extend(
+        errors
+            .into_iter()
+            .filter(|err| !is_unimplemented_field_error(err)),
+    );
+    problems.extend(
+        typed
+            .validate()
+            .into_iter()
+            .map(|finding| finding.to_string()),
+    );
 
-    let filepath = "/home/ussur/Desktop/lan_zipssga_zipznanium_zip/LAN.TXT";
-    let file = File::open(filepath).unwrap();
+    (record_id(&typed), problems)
+}
 
-    let buf_reader = BufReader::new(file);
-    let parser = RecordsReader::new(buf_reader);
+/// Returns the number of records with problems.
+fn validate(input: &InputArgs) -> io::Result<usize> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut invalid = 0;
 
-    for (i, record) in parser.into_iter().enumerate() {
-        let record = record_remove_errors(record.unwrap());
+    for (i, result) in input.open_mapped(record_problems)?.enumerate() {
+        let (id, problems) = result?;
+        if !problems.is_empty() {
+            invalid += 1;
+            for problem in problems {
+                writeln!(out, "record {} ({id}): {problem}", i + 1)?;
+            }
+        }
+    }
+
+    out.flush()?;
+    Ok(invalid)
+}
 
-        let (record, errors) = TypedRecord::parse(record.into_iter());
-        writeln!(&mut writer, "Errors count: {:?}", errors.len()).unwrap();
+fn grep(
+    query: &Query,
+    records: Records,
+    mut writer: Box<dyn RecordWriter>,
+    invert: bool,
+    count_only: bool,
+    typed: bool,
+) -> io::Result<()> {
+    let mut count = 0;
+    for record in records {
+        let record = record_remove_errors(record?);
+        let matches = if typed {
+            let (typed, _errors) = TypedRecord::parse(record.iter().cloned());
+            query.matches(&typed)
+        } else {
+            query.matches(&record)
+        };
 
-        for field in record.fields {
-            writeln!(&mut writer, "{:?}", field).unwrap();
+        if matches != invert {
+            count += 1;
+            if !count_only {
+                writer.write_record(&record)?;
+            }
         }
+    }
 
-        // for f001 in record.get_fields::<Field001RecordId>() {
-        //     writeln!(&mut writer, "Field 001: {:?}", f001).unwrap();
-        // }
+    if count_only {
+        println!("{count}");
+        return Ok(());
+    }
+    writer.finish()
+}
 
-        if i % 1000 == 0 {
-            writer.flush().unwrap();
+fn describe_record(record: Record) -> Description {
+    let (typed, _errors) = TypedRecord::parse(record_remove_errors(record).into_iter());
+    Description::from(&typed)
+}
+
+fn describe(
+    input: &InputArgs,
+    format: DescriptionFormat,
+    output: &Option<PathBuf>,
+) -> io::Result<()> {
+    let mut out = create_output(output)?;
+    if let DescriptionFormat::Dc = format {
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(out, "<records>")?;
+    }
+
+    for description in input.open_mapped(describe_record)? {
+        let description = description?;
+        match format {
+            DescriptionFormat::Dc => description.write_oai_dc(&mut out)?,
+            DescriptionFormat::Jsonld => {
+                serde_json::to_writer(&mut out, &description.to_schema_org())?;
+                writeln!(out)?;
+            }
+        }
+    }
+
+    if let DescriptionFormat::Dc = format {
+        writeln!(out, "</records>")?;
+    }
+    out.flush()
+}
+
+fn cite(input: &InputArgs, style: CitationStyle, output: &Option<PathBuf>) -> io::Result<()> {
+    let mut out = create_output(output)?;
+    if let CitationStyle::CslJson = style {
+        writeln!(out, "[")?;
+    }
+
+    for (i, description) in input.open_mapped(describe_record)?.enumerate() {
+        let citation = description?.cite(style);
+        match style {
+            CitationStyle::Gost | CitationStyle::Apa => writeln!(out, "{citation}")?,
+            CitationStyle::BibTex if i > 0 => write!(out, "\n{citation}")?,
+            CitationStyle::BibTex | CitationStyle::Ris => write!(out, "{citation}")?,
+            CitationStyle::CslJson if i > 0 => write!(out, ",\n{citation}")?,
+            CitationStyle::CslJson => write!(out, "{citation}")?,
+        }
+    }
+
+    if let CitationStyle::CslJson = style {
+        writeln!(out, "\n]")?;
+    }
+    out.flush()
+}
+
+/// Returns the number of changed records.
+fn diff_files(old: &Path, new: &Path, format: Option<Format>, json: bool) -> io::Result<usize> {
+    let old = read_by_id(old, format)?;
+    let new = read_by_id(new, format)?;
+    let old_by_id = index_by_id(&old);
+    let new_by_id = index_by_id(&new);
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut changed = 0;
+    let removed = old
+        .iter()
+        .filter(|(id, _)| !new_by_id.contains_key(id.as_str()));
+    for (id, _) in new.iter().chain(removed) {
+        let before = old_by_id.get(id.as_str()).copied().unwrap_or_default();
+        let after = new_by_id.get(id.as_str()).copied().unwrap_or_default();
+        let changes = diff(before, after);
+        if changes.is_empty() {
+            continue;
+        }
+
+        changed += 1;
+        if json {
+            serde_json::to_writer(&mut out, &json!({ "id": id, "changes": changes.changes }))?;
+            writeln!(out)?;
+        } else {
+            writeln!(out, "record {id}")?;
+            write!(out, "{changes}")?;
+        }
+    }
+
+    out.flush()?;
+    Ok(changed)
+}
+
+/// Returns the number of conflicts.
+fn merge_files(
+    [base, local, remote]: [&Path; 3],
+    format: Option<Format>,
+    mut writer: Box<dyn RecordWriter>,
+) -> io::Result<usize> {
+    let base = read_by_id(base, format)?;
+    let local = read_by_id(local, format)?;
+    let remote = read_by_id(remote, format)?;
+    let base_by_id = index_by_id(&base);
+    let local_by_id = index_by_id(&local);
+    let remote_by_id = index_by_id(&remote);
+
+    let mut conflicts = 0;
+    let added = remote
+        .iter()
+        .filter(|(id, _)| !local_by_id.contains_key(id.as_str()));
+    for (id, _) in local.iter().chain(added) {
+        let [base, local, remote] = [&base_by_id, &local_by_id, &remote_by_id]
+            .map(|records| records.get(id.as_str()).copied().unwrap_or_default());
+        let merged = merge(base, local, remote);
+
+        for conflict in &merged.conflicts {
+            eprint!("record {id}: {conflict}");
+        }
+        conflicts += merged.conflicts.len();
+        // Removed on one side and not changed on the other
+        if !merged.record.is_empty() {
+            writer.write_record(&merged.record)?;
+        }
+    }
+
+    writer.finish()?;
+    Ok(conflicts)
+}
+
+fn dedup(inputs: &[PathBuf], format: Option<Format>, threshold: f32, json: bool) -> io::Result<()> {
+    let mut dedup = Deduplicator::new(threshold);
+    let mut records = Vec::new();
+    for path in inputs {
+        for (id, record) in read_by_id(path, format)? {
+            let (typed, _errors) = TypedRecord::parse(record.into_iter());
+            let description = Description::from(&typed);
+            dedup.add(Fingerprint::from(&description));
+            records.push((id, description));
+        }
+    }
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    for (i, cluster) in dedup.clusters().into_iter().enumerate() {
+        let ids: Vec<_> = cluster.members.iter().map(|&j| &records[j].0).collect();
+        if json {
+            let cluster = json!({
+                "master": records[cluster.master].0,
+                "confidence": (f64::from(cluster.confidence) * 100.0).round() / 100.0,
+                "records": ids,
+            });
+            serde_json::to_writer(&mut out, &cluster)?;
+            writeln!(out)?;
+            continue;
+        }
+
+        if i > 0 {
+            writeln!(out)?;
+        }
+        writeln!(out, "confidence {:.2}", cluster.confidence)?;
+        for &j in &cluster.members {
+            let (id, description) = &records[j];
+            let master = if j == cluster.master { '*' } else { ' ' };
+            let title = description.full_title().unwrap_or_default();
+            match &description.date {
+                Some(date) => writeln!(out, "{master} {id:<20} {title} ({date})")?,
+                None => writeln!(out, "{master} {id:<20} {title}")?,
+            }
+        }
+    }
+    out.flush()
+}
+
+fn split(records: Records, format: Format, size: usize, prefix: &str) -> io::Result<()> {
+    let mut records = records.peekable();
+    let mut part = 0;
+
+    while records.peek().is_some() {
+        part += 1;
+        let path = format!("{prefix}{part:04}.{}", format.extension());
+        let file = BufWriter::new(File::create(&path)?);
+        convert(records.by_ref(), record_writer(file, format), size)?;
+        eprintln!("{path}");
+    }
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<ExitCode, Box<dyn Error>> {
+    match cli.command {
+        Command::Convert {
+            input,
+            output,
+            marc21,
+            from_marc21,
+            mapping,
+        } => {
+            if marc21 {
+                convert_to_marc21(input.open()?, output.open()?)?
+            } else if from_marc21 {
+                convert_from_marc21(&input, output.open()?, mapping.as_deref())?
+            } else {
+                convert(input.open()?, output.open()?, usize::MAX)?
+            }
+        }
+        Command::Stats { input, json } => stats(input.open()?, json)?,
+        Command::Validate { input } => {
+            let invalid = validate(&input)?;
+            if invalid > 0 {
+                eprintln!("{invalid} records with problems");
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Grep {
+            query,
+            input,
+            output,
+            invert,
+            count,
+            typed,
+        } => {
+            let query = Query::parse(&query)?;
+            grep(&query, input.open()?, output.open()?, invert, count, typed)?
+        }
+        Command::Describe {
+            input,
+            format,
+            output,
+        } => describe(&input, format, &output)?,
+        Command::Cite {
+            input,
+            style,
+            output,
+        } => cite(&input, style, &output)?,
+        Command::Diff {
+            old,
+            new,
+            from,
+            json,
+        } => {
+            if diff_files(&old, &new, from, json)? > 0 {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Merge {
+            base,
+            local,
+            remote,
+            from,
+            output,
+        } => {
+            let conflicts = merge_files([&base, &local, &remote], from, output.open()?)?;
+            if conflicts > 0 {
+                eprintln!("{conflicts} conflicts, local versions kept");
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Dedup {
+            inputs,
+            from,
+            threshold,
+            json,
+        } => dedup(&inputs, from, threshold, json)?,
+        Command::Head {
+            input,
+            output,
+            records,
+        } => convert(input.open()?, output.open()?, records)?,
+        Command::Split {
+            input,
+            to,
+            records,
+            prefix,
+        } => split(input.open()?, to, records.max(1), &prefix)?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(code) => code,
+        // Output piped into `less` or `head` was closed
+        Err(err)
+            if err
+                .downcast_ref::<io::Error>()
+                .is_some_and(|err| err.kind() == io::ErrorKind::BrokenPipe) =>
+        {
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("rusmarc: {err}");
+            ExitCode::from(2)
         }
     }
-    writer.flush().unwrap();
 }
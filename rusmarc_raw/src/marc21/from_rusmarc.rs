@@ -0,0 +1,314 @@
+use crate::field::{Field, FieldData, Subfield};
+use crate::formats::iso2709::Leader;
+use crate::typed_record::TypedRecord;
+
+use super::{Loss, Marc21Field, Marc21Record, COUNTRIES, DATE_TYPES, RELATORS};
+
+/// Field that maps subfield by subfield.
+struct Rule {
+    rusmarc: u128,
+    marc21: u128,
+    indicators: [char; 2],
+    /// RUSMARC marker → MARC 21 code. Other subfields are lost.
+    subfields: &'static [(char, char)],
+}
+
+const fn rule(
+    rusmarc: u128,
+    marc21: u128,
+    indicators: [char; 2],
+    subfields: &'static [(char, char)],
+) -> Rule {
+    Rule {
+        rusmarc,
+        marc21,
+        indicators,
+        subfields,
+    }
+}
+
+const SUBJECT: &[(char, char)] = &[('a', 'a'), ('x', 'x'), ('y', 'z'), ('z', 'y'), ('j', 'v')];
+
+#[rustfmt::skip]
+const RULES: &[Rule] = &[
+    rule(10,  20,  [' ', ' '], &[('a', 'a'), ('b', 'q'), ('d', 'c'), ('z', 'z')]),
+    rule(11,  22,  [' ', ' '], &[('a', 'a'), ('f', 'l'), ('g', 'm'), ('y', 'z'), ('z', 'y')]),
+    rule(13,  24,  ['2', ' '], &[('a', 'a'), ('b', 'q'), ('d', 'c'), ('z', 'z')]),
+    rule(73,  24,  ['3', ' '], &[('a', 'a'), ('b', 'q'), ('d', 'c'), ('z', 'z')]),
+    rule(101, 41,  ['0', ' '], &[('a', 'a'), ('b', 'k'), ('c', 'h'), ('d', 'b'), ('j', 'j')]),
+    rule(102, 44,  [' ', ' '], &[('a', 'c')]),
+    rule(205, 250, [' ', ' '], &[('a', 'a'), ('f', 'b')]),
+    rule(210, 264, [' ', '1'], &[('a', 'a'), ('c', 'b'), ('d', 'c')]),
+    rule(215, 300, [' ', ' '], &[('a', 'a'), ('c', 'b'), ('d', 'c'), ('e', 'e')]),
+    rule(225, 490, ['0', ' '], &[('a', 'a'), ('v', 'v'), ('x', 'x')]),
+    rule(300, 500, [' ', ' '], &[('a', 'a')]),
+    rule(320, 504, [' ', ' '], &[('a', 'a')]),
+    rule(327, 505, ['0', ' '], &[('a', 'a')]),
+    rule(330, 520, [' ', ' '], &[('a', 'a')]),
+    rule(606, 650, [' ', '4'], SUBJECT),
+    rule(607, 651, [' ', '4'], SUBJECT),
+    rule(610, 653, [' ', ' '], &[('a', 'a')]),
+    rule(675, 80,  [' ', ' '], &[('a', 'a'), ('v', '2')]),
+    rule(686, 84,  [' ', ' '], &[('a', 'a'), ('2', '2')]),
+    rule(856, 856, ['4', '0'], &[('u', 'u'), ('q', 'q'), ('y', 'y'), ('z', 'z')]),
+];
+
+/// Personal name: 700 → 100, 701 / 702 → 700.
+const PERSONAL_NAME: &[(char, char)] = &[('c', 'c'), ('f', 'd'), ('g', 'q')];
+/// Corporate name: 710 → 110, 711 / 712 → 710.
+const CORPORATE_NAME: &[(char, char)] = &[('a', 'a'), ('b', 'b'), ('c', 'c')];
+
+fn marker(subfield: &Subfield) -> char {
+    subfield.marker.to_lowercase().next().unwrap()
+}
+
+fn subfields(data: &FieldData) -> &[Subfield] {
+    match data {
+        FieldData::Subfields { subfields } => subfields,
+        FieldData::FullLine { .. } => &[],
+    }
+}
+
+fn data_field(tag: u128, indicators: [char; 2], subfields: Vec<Subfield>) -> Option<Marc21Field> {
+    (!subfields.is_empty()).then_some(Marc21Field {
+        tag,
+        indicators,
+        data: FieldData::Subfields { subfields },
+    })
+}
+
+fn subfield(marker: char, text: impl Into<String>) -> Subfield {
+    Subfield {
+        marker,
+        text: text.into(),
+    }
+}
+
+/// Conversion of a single field. Whatever does not map goes to `losses`.
+struct FieldConversion<'a> {
+    field: &'a Field,
+    losses: &'a mut Vec<Loss>,
+}
+
+impl FieldConversion<'_> {
+    fn lose(&mut self, subfield: &Subfield) {
+        self.losses.push(Loss {
+            field: self.field.number,
+            subfield: Some(marker(subfield)),
+            text: subfield.text.clone(),
+        });
+    }
+
+    fn map_subfields(&mut self, table: &[(char, char)]) -> Vec<Subfield> {
+        let mut result = Vec::new();
+        for source in subfields(&self.field.data) {
+            match table.iter().find(|(from, _)| *from == marker(source)) {
+                Some((_, to)) => result.push(subfield(*to, source.text.clone())),
+                None => self.lose(source),
+            }
+        }
+        result
+    }
+
+    /// 200 → 245 and 246 for a parallel title.
+    fn title(&mut self, has_main_entry: bool, output: &mut Vec<Marc21Field>) {
+        let mut title = Vec::new();
+        let mut responsibility: Vec<&str> = Vec::new();
+        let mut parallel = Vec::new();
+
+        for source in subfields(&self.field.data) {
+            match marker(source) {
+                'a' => title.push(subfield('a', source.text.clone())),
+                'b' => title.push(subfield('h', source.text.clone())),
+                'e' => title.push(subfield('b', source.text.clone())),
+                'h' => title.push(subfield('n', source.text.clone())),
+                'i' => title.push(subfield('p', source.text.clone())),
+                'f' | 'g' => responsibility.push(&source.text),
+                'd' => parallel.push(subfield('a', source.text.clone())),
+                _ => self.lose(source),
+            }
+        }
+        if !responsibility.is_empty() {
+            title.push(subfield('c', responsibility.join(" ; ")));
+        }
+
+        let first_indicator = if has_main_entry { '1' } else { '0' };
+        output.extend(data_field(245, [first_indicator, '0'], title));
+        for parallel in parallel {
+            output.extend(data_field(246, ['3', '1'], vec![parallel]));
+        }
+    }
+
+    /// `$a` surname and `$b` rest of the name become `Surname, Rest`.
+    fn personal_name(&mut self, tag: u128) -> Option<Marc21Field> {
+        let data = &self.field.data;
+        let name = match (
+            data.get_subfields('a').first(),
+            data.get_subfields('b').first(),
+        ) {
+            (Some(surname), Some(rest)) => format!("{surname}, {rest}"),
+            (Some(surname), None) => surname.to_string(),
+            _ => String::new(),
+        };
+
+        let mut result = vec![subfield('a', name)];
+        for source in subfields(data) {
+            match marker(source) {
+                'a' | 'b' => {}
+                '4' => match RELATORS
+                    .iter()
+                    .find(|(code, _)| *code == source.text.trim())
+                {
+                    Some((_, relator)) => result.push(subfield('4', *relator)),
+                    None => self.lose(source),
+                },
+                other => match PERSONAL_NAME.iter().find(|(from, _)| *from == other) {
+                    Some((_, to)) => result.push(subfield(*to, source.text.clone())),
+                    None => self.lose(source),
+                },
+            }
+        }
+
+        if result[0].text.is_empty() {
+            result.remove(0);
+        }
+        data_field(tag, ['1', ' '], result)
+    }
+}
+
+/// 008 for books from RUSMARC 100 (general processing data), 101 and 102.
+fn fixed_length_data(record: &[Field]) -> String {
+    let first = |number: u128, marker: char| {
+        record
+            .iter()
+            .filter(|field| field.number == number)
+            .find_map(|field| {
+                field
+                    .data
+                    .get_subfields(marker)
+                    .first()
+                    .map(|s| s.to_string())
+            })
+    };
+    let general = first(100, 'a').unwrap_or_default();
+    let position = |range: std::ops::Range<usize>| general.get(range).unwrap_or("");
+
+    // yyyymmdd → yymmdd
+    let entered = match position(2..8) {
+        date if date.len() == 6 => date.to_string(),
+        _ => "||||||".to_string(),
+    };
+    let date_type = DATE_TYPES
+        .iter()
+        .find(|(rusmarc, _)| position(8..9).starts_with(*rusmarc))
+        .map_or('|', |(_, marc21)| *marc21);
+    let dates = match position(9..17) {
+        dates if dates.len() == 8 => dates.replace(' ', "u").replace("uuuu", "    "),
+        _ => "||||||||".to_string(),
+    };
+    let country = first(102, 'a').unwrap_or_default().to_uppercase();
+    let place = COUNTRIES
+        .iter()
+        .find(|(iso, _)| *iso == country)
+        .map_or("xx ", |(_, marc21)| marc21);
+    let language = match first(101, 'a') {
+        Some(language) if language.trim().len() == 3 => language.trim().to_lowercase(),
+        _ => "|||".to_string(),
+    };
+
+    format!(
+        "{entered}{date_type}{dates}{place}{}{language} d",
+        "|".repeat(17)
+    )
+}
+
+/// Converts a RUSMARC record into MARC 21, together with whatever could not be converted.
+///
+/// Fields without a MARC 21 equivalent (local 9xx fields, coded data other
+/// than 100–102, ...) are reported whole; unmapped subfields of mapped
+/// fields are reported one by one.
+pub fn from_rusmarc(record: &[Field]) -> (Marc21Record, Vec<Loss>) {
+    let mut fields = Vec::new();
+    let mut losses = Vec::new();
+
+    let has_main_entry = record.iter().any(|field| matches!(field.number, 700 | 710));
+
+    for field in record {
+        let mut conversion = FieldConversion {
+            field,
+            losses: &mut losses,
+        };
+
+        match field.number {
+            1 | 5 => fields.push(Marc21Field {
+                tag: field.number,
+                indicators: [' ', ' '],
+                data: field.data.clone(),
+            }),
+            3 => fields.extend(data_field(
+                24,
+                ['7', ' '],
+                field
+                    .data
+                    .get_subfields('a')
+                    .into_iter()
+                    .map(|uri| subfield('a', uri))
+                    .chain([subfield('2', "uri")])
+                    .collect(),
+            )),
+            // Goes into 008
+            100 => {}
+            200 => conversion.title(has_main_entry, &mut fields),
+            700 => fields.extend(conversion.personal_name(100)),
+            701 | 702 => fields.extend(conversion.personal_name(700)),
+            710 => fields.extend(data_field(
+                110,
+                ['2', ' '],
+                conversion.map_subfields(CORPORATE_NAME),
+            )),
+            711 | 712 => fields.extend(data_field(
+                710,
+                ['2', ' '],
+                conversion.map_subfields(CORPORATE_NAME),
+            )),
+            number => match RULES.iter().find(|rule| rule.rusmarc == number) {
+                Some(rule) => fields.extend(data_field(
+                    rule.marc21,
+                    rule.indicators,
+                    conversion.map_subfields(rule.subfields),
+                )),
+                None => losses.push(Loss {
+                    field: number,
+                    subfield: None,
+                    text: field.data.to_string(),
+                }),
+            },
+        }
+    }
+
+    fields.push(Marc21Field {
+        tag: 8,
+        indicators: [' ', ' '],
+        data: FieldData::FullLine {
+            text: fixed_length_data(record),
+        },
+    });
+    // MARC 21 expects fields in tag order; keep the RUSMARC order within a tag
+    fields.sort_by_key(|field| field.tag);
+
+    let leader = Leader {
+        // UCS / Unicode
+        coding_scheme: 'a',
+        // ISBD punctuation omitted
+        cataloguing_form: 'c',
+        position_23: '0',
+        ..Leader::default()
+    };
+
+    (Marc21Record { leader, fields }, losses)
+}
+
+/// Same as [`from_rusmarc`], for fields that have a typed representation.
+pub fn from_typed_rusmarc(record: &TypedRecord) -> (Marc21Record, Vec<Loss>) {
+    from_rusmarc(&record.to_record())
+}
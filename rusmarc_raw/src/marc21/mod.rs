@@ -0,0 +1,126 @@
+//! MARC 21 records and the crosswalks between RUSMARC and MARC 21.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! use rusmarc_raw::formats::{record_writer, Format};
+//! use rusmarc_raw::marc21::from_rusmarc;
+//! use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+//!
+//! let data = "#1: lan-1\n#10: ^A978-5-8114-1234-1^D500 р.\n#101: ^Arus\n\
+//!             #200: ^AОсновы^EУчебник^FИ. И. Иванов\n#210: ^AСПб^CЛань^D2021\n\
+//!             #700: ^AИванов^BИ. И.^4070\n#999: ^Zлокальное\n";
+//! let record = record_remove_errors(RecordsReader::new(Cursor::new(data)).next().unwrap().unwrap());
+//!
+//! let (marc21, losses) = from_rusmarc(&record);
+//! let title = marc21.fields.iter().find(|field| field.tag == 245).unwrap();
+//! assert_eq!(title.indicators, ['1', '0']);
+//! assert_eq!(title.data.get_subfields('b'), ["Учебник"]);
+//!
+//! let author = marc21.fields.iter().find(|field| field.tag == 100).unwrap();
+//! assert_eq!(author.data.get_subfields('a'), ["Иванов, И. И."]);
+//! assert_eq!(author.data.get_subfields('4'), ["aut"]);
+//!
+//! assert_eq!(losses.len(), 1);
+//! assert_eq!(losses[0].to_string(), "#999: `^Zлокальное` was not converted");
+//!
+//! let mut xml = Vec::new();
+//! let mut writer = record_writer(&mut xml, Format::MarcXml);
+//! writer.write_marc21(&marc21).unwrap();
+//! writer.finish().unwrap();
+//! drop(writer);
+//! assert!(String::from_utf8(xml).unwrap().contains(r#"<datafield tag="264" ind1=" " ind2="1">"#));
+//! ```
+
+mod from_rusmarc;
+mod to_rusmarc;
+
+use std::fmt;
+
+use crate::field::{Field, FieldData};
+use crate::formats::iso2709::Leader;
+
+pub use from_rusmarc::{from_rusmarc, from_typed_rusmarc};
+pub use to_rusmarc::{to_rusmarc, Mapping, MappingRule};
+
+/// RUSMARC relator codes (Appendix C) → MARC relator terms.
+#[rustfmt::skip]
+const RELATORS: &[(&str, &str)] = &[
+    ("070", "aut"),
+    ("205", "ctb"),
+    ("220", "com"),
+    ("340", "edt"),
+    ("440", "ill"),
+    ("730", "trl"),
+];
+
+/// ISO 3166-1 (RUSMARC 102) → MARC Code List for Countries (008/15-17).
+#[rustfmt::skip]
+const COUNTRIES: &[(&str, &str)] = &[
+    ("RU", "ru "),
+    ("UA", "un "),
+    ("BY", "bw "),
+    ("KZ", "kz "),
+    ("US", "xxu"),
+    ("GB", "xxk"),
+    ("DE", "gw "),
+    ("FR", "fr "),
+];
+
+/// Type of publication date: RUSMARC 100/8 → MARC 21 008/06.
+/// The first entry for a MARC 21 code is used in the other direction.
+#[rustfmt::skip]
+const DATE_TYPES: &[(char, char)] = &[
+    ('a', 'c'),
+    ('b', 'd'),
+    ('d', 's'),
+    ('k', 's'),
+    ('e', 'r'),
+    ('f', 'q'),
+    ('g', 'm'),
+    ('h', 't'),
+    ('i', 'p'),
+    ('j', 'e'),
+];
+
+#[derive(Debug, Clone)]
+pub struct Marc21Field {
+    pub tag: u128,
+    /// Ignored for control fields (tags below 010).
+    pub indicators: [char; 2],
+    pub data: FieldData,
+}
+
+impl From<Marc21Field> for Field {
+    /// Drops the indicators.
+    fn from(field: Marc21Field) -> Self {
+        Field {
+            number: field.tag,
+            data: field.data,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Marc21Record {
+    pub leader: Leader,
+    pub fields: Vec<Marc21Field>,
+}
+
+/// Data that a crosswalk could not carry over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loss {
+    pub field: u128,
+    /// `None` if the whole field is lost.
+    pub subfield: Option<char>,
+    pub text: String,
+}
+
+impl fmt::Display for Loss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.field)?;
+        if let Some(subfield) = self.subfield {
+            write!(f, "${subfield}")?;
+        }
+        write!(f, ": `{}` was not converted", self.text)
+    }
+}
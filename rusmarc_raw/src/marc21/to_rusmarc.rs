@@ -0,0 +1,500 @@
+use std::str::FromStr;
+
+use crate::field::{Field, FieldData, Subfield};
+use crate::record::ErrorlessRecord;
+use crate::typed_record::{is_unimplemented_field_error, parse_typed_field};
+
+use super::{Loss, Marc21Field, Marc21Record, COUNTRIES, DATE_TYPES, RELATORS};
+
+/// Core bibliographic fields, in the syntax of [`Mapping::from_str`].
+///
+/// Control fields (001, 005, 008), the title (245, 246 with a parallel title)
+/// and personal names (100, 700) are converted by code.
+const DEFAULT_MAPPING: &str = "\
+020    -> 010 a=a q=b c=d z=z
+022    -> 011 a=a l=f m=g y=z z=y
+024 2_ -> 013 a=a q=b c=d z=z
+024 3_ -> 073 a=a q=b c=d z=z
+041    -> 101 a=a k=b h=c b=d j=j
+044    -> 102 c=a
+080    -> 675 a=a 2=v
+084    -> 686 a=a 2=2
+110    -> 710 a=a b=b c=c
+250    -> 205 a=a b=f
+260    -> 210 a=a b=c c=d
+264 _1 -> 210 a=a b=c c=d
+300    -> 215 a=a b=c c=d e=e
+490    -> 225 a=a v=v x=x
+500    -> 300 a=a
+504    -> 320 a=a
+505    -> 327 a=a
+520    -> 330 a=a
+650    -> 606 a=a x=x y=z z=y v=j
+651    -> 607 a=a x=x y=z z=y v=j
+653    -> 610 a=a
+710    -> 712 a=a b=b c=c
+856    -> 856 u=u q=q y=y z=z
+";
+
+/// Converts one MARC 21 data field into a RUSMARC field, subfield by subfield.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingRule {
+    pub marc21: u128,
+    /// Indicators the field must have, `None` matches any.
+    pub indicators: [Option<char>; 2],
+    pub rusmarc: u128,
+    /// MARC 21 code → RUSMARC marker. Other subfields are lost.
+    pub subfields: Vec<(char, char)>,
+}
+
+impl MappingRule {
+    fn matches(&self, field: &Marc21Field) -> bool {
+        self.marc21 == field.tag
+            && self
+                .indicators
+                .iter()
+                .zip(field.indicators)
+                .all(|(expected, actual)| expected.is_none_or(|expected| expected == actual))
+    }
+}
+
+/// Table of [`MappingRule`]s. The first matching rule wins.
+///
+/// [`Mapping::default`] covers the core bibliographic fields. Rules are
+/// written one per line: the MARC 21 tag, optionally its indicators (`_` for
+/// any, `#` for blank), `->`, the RUSMARC field and `code=marker` pairs.
+/// Empty lines and lines starting with `#` are skipped.
+///
+/// ```
+/// use rusmarc_raw::marc21::Mapping;
+///
+/// let local: Mapping = "
+///     ## Local call numbers
+///     090    -> 686 a=a
+///     024 7_ -> 003 a=a
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// let mapping = Mapping::default().without(84).with_rules(local.into_rules());
+/// assert_eq!(mapping.rules()[0].rusmarc, 686);
+/// assert!(mapping.rules().iter().all(|rule| rule.marc21 != 84));
+///
+/// assert!("020 010 a=a".parse::<Mapping>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    rules: Vec<MappingRule>,
+}
+
+impl Mapping {
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn rules(&self) -> &[MappingRule] {
+        &self.rules
+    }
+
+    pub fn into_rules(self) -> Vec<MappingRule> {
+        self.rules
+    }
+
+    /// Adds a rule that takes precedence over the existing ones.
+    pub fn with_rule(self, rule: MappingRule) -> Self {
+        self.with_rules([rule])
+    }
+
+    /// Adds rules that take precedence over the existing ones, keeping their order.
+    pub fn with_rules(mut self, rules: impl IntoIterator<Item = MappingRule>) -> Self {
+        self.rules.splice(0..0, rules);
+        self
+    }
+
+    /// Removes the rules for a MARC 21 tag.
+    pub fn without(mut self, marc21: u128) -> Self {
+        self.rules.retain(|rule| rule.marc21 != marc21);
+        self
+    }
+
+    fn find(&self, field: &Marc21Field) -> Option<&MappingRule> {
+        self.rules.iter().find(|rule| rule.matches(field))
+    }
+}
+
+impl Default for Mapping {
+    fn default() -> Self {
+        DEFAULT_MAPPING.parse().expect("Default mapping is valid")
+    }
+}
+
+fn parse_tag(tag: &str) -> Result<u128, String> {
+    match tag.len() {
+        3 => tag.parse().map_err(|_| format!("Invalid tag `{tag}`")),
+        _ => Err(format!("Invalid tag `{tag}`")),
+    }
+}
+
+fn parse_rule(line: &str) -> Result<MappingRule, String> {
+    let (source, target) = line
+        .split_once("->")
+        .ok_or_else(|| "Expected `->`".to_string())?;
+
+    let mut source = source.split_whitespace();
+    let marc21 = parse_tag(source.next().ok_or("No MARC 21 tag")?)?;
+    let indicators = match source
+        .next()
+        .map(|indicators| indicators.chars().collect::<Vec<_>>())
+    {
+        None => [None, None],
+        Some(indicators) => match indicators.as_slice() {
+            [ind1, ind2] => [*ind1, *ind2].map(|indicator| match indicator {
+                '_' => None,
+                '#' => Some(' '),
+                indicator => Some(indicator),
+            }),
+            _ => return Err("Indicators should be two characters".to_string()),
+        },
+    };
+
+    let mut target = target.split_whitespace();
+    let rusmarc = parse_tag(target.next().ok_or("No RUSMARC field")?)?;
+    let subfields = target
+        .map(|pair| {
+            let mut chars = pair.chars();
+            match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                (Some(code), Some('='), Some(marker), None) => Ok((code, marker)),
+                _ => Err(format!(
+                    "Invalid subfield pair `{pair}`, expected `code=marker`"
+                )),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(MappingRule {
+        marc21,
+        indicators,
+        rusmarc,
+        subfields,
+    })
+}
+
+impl FromStr for Mapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rules = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| parse_rule(line).map_err(|err| format!("Line {}: {err}", i + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+}
+
+/// ISBD punctuation that MARC 21 keeps at the end of subfields, e.g. `Title /`.
+fn without_isbd_punctuation(text: &str) -> String {
+    text.trim_end_matches([' ', '/', ':', ';', '=', ','])
+        .to_string()
+}
+
+/// RUSMARC markers are upper case in vendor exports.
+fn subfield(marker: char, text: &str) -> Subfield {
+    Subfield {
+        marker: marker.to_ascii_uppercase(),
+        text: without_isbd_punctuation(text),
+    }
+}
+
+fn subfields(data: &FieldData) -> &[Subfield] {
+    match data {
+        FieldData::Subfields { subfields } => subfields,
+        FieldData::FullLine { .. } => &[],
+    }
+}
+
+fn data_field(number: u128, subfields: Vec<Subfield>) -> Option<Field> {
+    (!subfields.is_empty()).then_some(Field {
+        number,
+        data: FieldData::Subfields { subfields },
+    })
+}
+
+/// Conversion of a single field. Whatever does not map goes to `losses`.
+struct FieldConversion<'a> {
+    field: &'a Marc21Field,
+    losses: &'a mut Vec<Loss>,
+}
+
+impl FieldConversion<'_> {
+    fn lose(&mut self, subfield: &Subfield) {
+        self.losses.push(Loss {
+            field: self.field.tag,
+            subfield: Some(subfield.marker),
+            text: subfield.text.clone(),
+        });
+    }
+
+    fn map_subfields(&mut self, table: &[(char, char)]) -> Vec<Subfield> {
+        let mut result = Vec::new();
+        for source in subfields(&self.field.data) {
+            match table.iter().find(|(from, _)| *from == source.marker) {
+                Some((_, to)) => result.push(subfield(*to, &source.text)),
+                None => self.lose(source),
+            }
+        }
+        result
+    }
+
+    /// 245 → 200. Parallel titles from 246 are added later.
+    fn title(&mut self) -> Option<Field> {
+        let mut title = self.map_subfields(&[
+            ('a', 'a'),
+            ('h', 'b'),
+            ('b', 'e'),
+            ('n', 'h'),
+            ('p', 'i'),
+            ('c', 'f'),
+        ]);
+        // `[electronic resource]` is bracketed in MARC 21 only
+        for subfield in title.iter_mut().filter(|subfield| subfield.marker == 'B') {
+            subfield.text = subfield.text.trim_matches(['[', ']']).to_string();
+        }
+        data_field(200, title)
+    }
+
+    /// 100 → 700, 700 → 701 for co-authors and 702 for everyone else.
+    ///
+    /// `Surname, Rest` in `$a` is split into `$a` and `$b`.
+    fn personal_name(&mut self) -> Option<Field> {
+        let mut result = Vec::new();
+        let mut is_author = true;
+
+        for source in subfields(&self.field.data) {
+            match source.marker {
+                'a' => match source.text.split_once(',') {
+                    Some((surname, rest)) => {
+                        result.push(subfield('a', surname));
+                        result.push(subfield('b', rest.trim()));
+                    }
+                    None => result.push(subfield('a', &source.text)),
+                },
+                'c' => result.push(subfield('c', &source.text)),
+                'd' => result.push(subfield('f', &source.text)),
+                'q' => result.push(subfield('g', &source.text)),
+                '4' => match RELATORS
+                    .iter()
+                    .find(|(_, relator)| *relator == source.text.trim())
+                {
+                    Some((code, relator)) => {
+                        is_author = *relator == "aut";
+                        result.push(subfield('4', code));
+                    }
+                    None => self.lose(source),
+                },
+                _ => self.lose(source),
+            }
+        }
+
+        let number = match (self.field.tag, is_author) {
+            (100, _) => 700,
+            (_, true) => 701,
+            (_, false) => 702,
+        };
+        data_field(number, result)
+    }
+}
+
+/// RUSMARC 100 (general processing data) from MARC 21 008, plus 101 and 102
+/// unless the record has 041 or 044.
+fn general_processing_data(record: &Marc21Record, fixed: &str, output: &mut ErrorlessRecord) {
+    let position = |range: std::ops::Range<usize>| fixed.get(range).unwrap_or("");
+    let has_tag = |tag| record.fields.iter().any(|field| field.tag == tag);
+    let first = |tag, code| {
+        record
+            .fields
+            .iter()
+            .filter(|field| field.tag == tag)
+            .find_map(|field| {
+                field
+                    .data
+                    .get_subfields(code)
+                    .first()
+                    .map(|s| s.to_string())
+            })
+    };
+
+    // yymmdd → yyyymmdd; MARC 21 records exist since 1968
+    let entered = match position(0..6) {
+        date if date.len() == 6 && date.bytes().all(|b| b.is_ascii_digit()) => {
+            let century = if date < "68" { "20" } else { "19" };
+            format!("{century}{date}")
+        }
+        _ => "        ".to_string(),
+    };
+    let date_type = position(6..7).chars().next().and_then(|marc21| {
+        DATE_TYPES
+            .iter()
+            .find(|(_, code)| *code == marc21)
+            .map(|(rusmarc, _)| *rusmarc)
+    });
+    let dates = match position(7..15) {
+        dates if dates.len() == 8 => dates.replace(['u', '|'], " "),
+        _ => " ".repeat(8),
+    };
+    let cataloguing_language = first(40, 'b')
+        .filter(|language| language.len() == 3)
+        .unwrap_or_else(|| "und".to_string());
+    let title = first(245, 'a').unwrap_or_default();
+    let script = if title.chars().any(|c| matches!(c, 'А'..='я' | 'Ё' | 'ё')) {
+        "ca"
+    } else {
+        "ba"
+    };
+
+    output.push(Field {
+        number: 100,
+        data: FieldData::Subfields {
+            subfields: vec![subfield(
+                'a',
+                &format!(
+                    "{entered}{}{dates}u  y0{cataloguing_language}y50      {script}",
+                    date_type.unwrap_or(' ')
+                ),
+            )],
+        },
+    });
+
+    let language = position(35..38);
+    if !has_tag(41) && language.len() == 3 && language.chars().all(|c| c.is_ascii_lowercase()) {
+        output.extend(data_field(101, vec![subfield('a', language)]));
+    }
+    let place = position(15..18);
+    if !has_tag(44) {
+        if let Some((iso, _)) = COUNTRIES.iter().find(|(_, marc21)| *marc21 == place) {
+            output.extend(data_field(102, vec![subfield('a', iso)]));
+        }
+    }
+}
+
+/// Converts a MARC 21 record into RUSMARC, together with whatever could not be converted.
+///
+/// Fields are converted by the first matching rule of `mapping`; control
+/// fields, titles and personal names without a rule are converted by code.
+/// A field that [`parse_typed_field`] would reject is reported as lost whole,
+/// so the result can always be parsed into a [`crate::typed_record::TypedRecord`].
+///
+/// ```
+/// # use std::io::Cursor;
+/// use rusmarc_raw::formats::{read_marc21, Format};
+/// use rusmarc_raw::marc21::{to_rusmarc, Mapping};
+/// use rusmarc_raw::typed_record::{Field200Header, TypedRecord};
+///
+/// let xml = r#"<record xmlns="http://www.loc.gov/MARC21/slim">
+///   <leader>00000nam a2200000 i 4500</leader>
+///   <controlfield tag="001">vendor-1</controlfield>
+///   <controlfield tag="008">210501s2021    ru            000 0 rus d</controlfield>
+///   <datafield tag="020" ind1=" " ind2=" "><subfield code="a">9785811412341</subfield></datafield>
+///   <datafield tag="100" ind1="1" ind2=" ">
+///     <subfield code="a">Иванов, И. И.,</subfield><subfield code="4">aut</subfield>
+///   </datafield>
+///   <datafield tag="245" ind1="1" ind2="0">
+///     <subfield code="a">Основы :</subfield><subfield code="b">учебник /</subfield>
+///     <subfield code="c">И. И. Иванов.</subfield>
+///   </datafield>
+///   <datafield tag="246" ind1="3" ind2="1"><subfield code="a">Basics</subfield></datafield>
+///   <datafield tag="880" ind1=" " ind2=" "><subfield code="a">...</subfield></datafield>
+/// </record>"#;
+/// let record = read_marc21(Cursor::new(xml), Format::MarcXml).next().unwrap().unwrap();
+///
+/// let (fields, losses) = to_rusmarc(&record, &Mapping::default());
+/// let text: Vec<String> = fields.iter().map(|f| format!("#{}: {}", f.number, f.data)).collect();
+/// assert_eq!(
+///     text,
+///     [
+///         "#1: vendor-1",
+///         "#10: ^A9785811412341",
+///         "#100: ^A20210501d2021    u  y0undy50      ca",
+///         "#101: ^Arus",
+///         "#102: ^ARU",
+///         "#200: ^AОсновы^Eучебник^FИ. И. Иванов.^DBasics",
+///         "#700: ^AИванов^BИ. И.^4070",
+///     ]
+/// );
+/// assert_eq!(losses.len(), 1);
+/// assert_eq!(losses[0].field, 880);
+///
+/// let (typed, errors) = TypedRecord::parse(fields.into_iter());
+/// assert_eq!(errors.len(), 1); // 100 has no typed representation yet
+/// let title = typed.get_fields::<Field200Header>().next().unwrap();
+/// assert_eq!(title.main_title.as_deref(), Some("Основы"));
+/// ```
+pub fn to_rusmarc(record: &Marc21Record, mapping: &Mapping) -> (ErrorlessRecord, Vec<Loss>) {
+    let mut fields = Vec::new();
+    let mut losses = Vec::new();
+    let mut parallel_titles = Vec::new();
+
+    for field in &record.fields {
+        let mut conversion = FieldConversion {
+            field,
+            losses: &mut losses,
+        };
+
+        let converted = match (field.tag, mapping.find(field)) {
+            (_, Some(rule)) => data_field(rule.rusmarc, conversion.map_subfields(&rule.subfields)),
+            (1 | 5, None) => Some(Field {
+                number: field.tag,
+                data: field.data.clone(),
+            }),
+            (8, None) => {
+                if let FieldData::FullLine { text } = &field.data {
+                    general_processing_data(record, text, &mut fields);
+                }
+                None
+            }
+            (245, None) => conversion.title(),
+            (246, None) if field.indicators[1] == '1' => {
+                parallel_titles.extend(conversion.map_subfields(&[('a', 'd')]));
+                None
+            }
+            (100 | 700, None) => conversion.personal_name(),
+            (tag, None) => {
+                losses.push(Loss {
+                    field: tag,
+                    subfield: None,
+                    text: field.data.to_string(),
+                });
+                None
+            }
+        };
+
+        let Some(converted) = converted else {
+            continue;
+        };
+        match parse_typed_field(converted.clone()) {
+            Err(err) if !is_unimplemented_field_error(&err) => losses.push(Loss {
+                field: field.tag,
+                subfield: None,
+                text: field.data.to_string(),
+            }),
+            _ => fields.push(converted),
+        }
+    }
+
+    match fields.iter_mut().find(|field| field.number == 200) {
+        Some(Field {
+            data: FieldData::Subfields { subfields },
+            ..
+        }) => subfields.extend(parallel_titles),
+        _ => losses.extend(parallel_titles.into_iter().map(|title| Loss {
+            field: 246,
+            subfield: Some('a'),
+            text: title.text,
+        })),
+    }
+
+    fields.sort_by_key(|field| field.number);
+    (fields, losses)
+}
@@ -0,0 +1,154 @@
+//! Parsing large dumps in batches on all cores.
+//!
+//! Records are split on the reading thread, then parsed and mapped in
+//! parallel. The output keeps the order of the input. Without the `parallel`
+//! feature the same pipeline runs on the current thread.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! use rusmarc_raw::parallel::{typed_records, ParallelRecords};
+//! use rusmarc_raw::record::record_remove_errors;
+//! use rusmarc_raw::typed_record::Field001RecordId;
+//!
+//! let data: String = (0..100).map(|i| format!("#1: id-{i}\n#200: ^AНазвание\n*****\n")).collect();
+//!
+//! let ids: Vec<_> = typed_records(Cursor::new(&data))
+//!     .map(|result| {
+//!         let (record, _errors) = result.unwrap();
+//!         record.get_fields::<Field001RecordId>().next().unwrap().id.clone()
+//!     })
+//!     .collect();
+//! assert_eq!(ids.len(), 100);
+//! assert_eq!(ids[42], "id-42");
+//!
+//! // Any other work per record can be done in parallel too
+//! let field_counts: Vec<usize> = ParallelRecords::new(Cursor::new(&data), |record| {
+//!     record_remove_errors(record).len()
+//! })
+//! .with_batch_size(16)
+//! .map(Result::unwrap)
+//! .collect();
+//! assert!(field_counts.iter().all(|count| *count == 2));
+//! ```
+
+use std::io::{self, BufRead};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::record::{parse_record, record_remove_errors, Record, RecordSplitter};
+use crate::typed_record::{ParseTypedFieldError, TypedRecord};
+
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// Text records, parsed and passed through `map` in parallel.
+pub struct ParallelRecords<R: BufRead, T, F> {
+    splitter: RecordSplitter<R>,
+    map: F,
+    batch_size: usize,
+    /// Texts of the current batch. The strings are reused between batches.
+    texts: Vec<String>,
+    ready: std::vec::IntoIter<T>,
+    error: Option<io::Error>,
+    is_done: bool,
+}
+
+impl<R, T, F> ParallelRecords<R, T, F>
+where
+    R: BufRead,
+    T: Send,
+    F: Fn(Record) -> T + Sync,
+{
+    pub fn new(input: R, map: F) -> Self {
+        Self {
+            splitter: RecordSplitter::new(input),
+            map,
+            batch_size: DEFAULT_BATCH_SIZE,
+            texts: Vec::new(),
+            ready: Vec::new().into_iter(),
+            error: None,
+            is_done: false,
+        }
+    }
+
+    /// Number of records parsed at once. Larger batches use more memory.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    fn read_batch(&mut self) {
+        let mut len = 0;
+        while len < self.batch_size {
+            if len == self.texts.len() {
+                self.texts.push(String::new());
+            }
+            match self.splitter.read_record(&mut self.texts[len]) {
+                Ok(true) => len += 1,
+                Ok(false) => {
+                    self.is_done = true;
+                    break;
+                }
+                Err(err) => {
+                    self.error = Some(err);
+                    self.is_done = true;
+                    break;
+                }
+            }
+        }
+
+        let texts = &self.texts[..len];
+        let map = &self.map;
+
+        #[cfg(feature = "parallel")]
+        let results: Vec<T> = if rayon::current_num_threads() > 1 {
+            texts
+                .par_iter()
+                .map(|text| map(parse_record(text)))
+                .collect()
+        } else {
+            texts.iter().map(|text| map(parse_record(text))).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let results: Vec<T> = texts.iter().map(|text| map(parse_record(text))).collect();
+
+        self.ready = results.into_iter();
+    }
+}
+
+impl<R, T, F> Iterator for ParallelRecords<R, T, F>
+where
+    R: BufRead,
+    T: Send,
+    F: Fn(Record) -> T + Sync,
+{
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.ready.next() {
+                return Some(Ok(item));
+            }
+            if let Some(err) = self.error.take() {
+                return Some(Err(err));
+            }
+            if self.is_done {
+                return None;
+            }
+            self.read_batch();
+        }
+    }
+}
+
+pub type TypedRecordResult = (TypedRecord, Vec<ParseTypedFieldError>);
+
+fn parse_typed(record: Record) -> TypedRecordResult {
+    TypedRecord::parse(record_remove_errors(record).into_iter())
+}
+
+/// Typed records, ignoring fields that cannot be read.
+pub fn typed_records<R: BufRead>(
+    input: R,
+) -> ParallelRecords<R, TypedRecordResult, fn(Record) -> TypedRecordResult> {
+    ParallelRecords::new(input, parse_typed)
+}
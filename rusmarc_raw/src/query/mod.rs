@@ -0,0 +1,165 @@
+//! Small query language to select records by their fields.
+//!
+//! ## Syntax
+//!
+//! - `101$a` — field (`101`) and an optional subfield (`$a`). A path alone
+//!   checks that the field / subfield is present.
+//! - `101$a = eng` — comparison. Operators: `=`, `!=`, `<`, `<=`, `>`, `>=`,
+//!   `~` (regex match) and `!~` (no regex match).
+//! - Values are bare words (`eng`, `2020`) or quoted strings (`"Лань"`, `'a b'`).
+//! - `and` / `&&`, `or` / `||`, `not` / `!` and parentheses.
+//!
+//! A field may repeat and a subfield may repeat within a field, so a path
+//! gives a list of values. A comparison is true if *any* of them satisfies it;
+//! `!=` and `!~` are true if *none* of them is equal / matches.
+//!
+//! `=` ignores case. `<`, `<=`, `>`, `>=` compare numbers if the value is a
+//! number: the number at the start of the field text is used, so `2020 г.`
+//! compares as `2020`. Otherwise strings are compared.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! use rusmarc_raw::query::Query;
+//! use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+//! use rusmarc_raw::typed_record::TypedRecord;
+//!
+//! let data = "#1: lan-1\n#101: ^Aeng\n#210: ^AСПб^DЛань^D2021\n*****\n#1: lan-2\n#10: ^A5-8114-1234-7\n#101: ^Arus";
+//! let records: Vec<_> = RecordsReader::new(Cursor::new(data))
+//!     .map(|record| record_remove_errors(record.unwrap()))
+//!     .collect();
+//!
+//! let query = Query::parse("101$a = eng and 210$d >= 2020 and not 010").unwrap();
+//! assert!(query.matches(&records[0]));
+//! assert!(!query.matches(&records[1]));
+//!
+//! let query: Query = "10$a ~ '^5-8114' || 210$d = лань".parse().unwrap();
+//! assert!(query.matches(&records[0]));
+//! assert!(query.matches(&records[1]));
+//!
+//! // Typed records work the same way
+//! let (typed, _errors) = TypedRecord::parse(records.into_iter().next().unwrap().into_iter());
+//! assert!(Query::parse("101$a = eng").unwrap().matches(&typed));
+//! ```
+
+mod parser;
+mod source;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+pub use parser::{CompareOp, Path};
+use parser::{Expr, Value};
+pub use source::QuerySource;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// Byte offset in the query text
+    pub position: usize,
+    pub message: String,
+}
+
+impl QueryError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at {})", self.message, self.position)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Parsed query. Parse once, then run [`Query::matches`] on many records.
+#[derive(Debug, Clone)]
+pub struct Query {
+    expr: Expr,
+}
+
+impl Query {
+    /// ```
+    /// use rusmarc_raw::query::Query;
+    ///
+    /// assert!(Query::parse("(101$a = eng or 101$a = rus) and 210$d").is_ok());
+    ///
+    /// let error = Query::parse("101$a = ").unwrap_err();
+    /// assert_eq!(error.to_string(), "Expected a value (at 8)");
+    /// assert!(Query::parse("101$a ~ '['").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        Ok(Self {
+            expr: parser::parse(text)?,
+        })
+    }
+
+    pub fn matches<R: QuerySource + ?Sized>(&self, record: &R) -> bool {
+        evaluate(&self.expr, record)
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn evaluate<R: QuerySource + ?Sized>(expr: &Expr, record: &R) -> bool {
+    match expr {
+        Expr::Exists(path) => !record.values(path).is_empty(),
+        Expr::Compare(path, op, value) => {
+            let values = record.values(path);
+            match op {
+                CompareOp::NotEq => !values.iter().any(|v| compare(v, CompareOp::Eq, value)),
+                CompareOp::NotMatches => {
+                    !values.iter().any(|v| compare(v, CompareOp::Matches, value))
+                }
+                _ => values.iter().any(|v| compare(v, *op, value)),
+            }
+        }
+        Expr::And(left, right) => evaluate(left, record) && evaluate(right, record),
+        Expr::Or(left, right) => evaluate(left, record) || evaluate(right, record),
+        Expr::Not(inner) => !evaluate(inner, record),
+    }
+}
+
+/// Number at the start of `text`: `2020 г.` gives `2020`.
+fn leading_number(text: &str) -> Option<f64> {
+    let text = text.trim_start();
+    let end = text
+        .char_indices()
+        .find(|(i, c)| !(c.is_ascii_digit() || *c == '.' || (*i == 0 && *c == '-')))
+        .map_or(text.len(), |(i, _)| i);
+    text[..end].parse().ok()
+}
+
+fn compare(field_value: &str, op: CompareOp, value: &Value) -> bool {
+    let text = match value {
+        Value::Regex(regex) => return regex.is_match(field_value),
+        Value::Text(text) => text,
+    };
+    let field_value = field_value.trim();
+
+    let ordering = match (text.parse::<f64>(), leading_number(field_value)) {
+        (Ok(number), Some(field_number)) => field_number.partial_cmp(&number),
+        (Ok(_), None) if op != CompareOp::Eq => None,
+        _ => Some(field_value.to_lowercase().cmp(&text.to_lowercase())),
+    };
+
+    match (op, ordering) {
+        (_, None) => false,
+        (CompareOp::Eq, Some(ordering)) => ordering == Ordering::Equal,
+        (CompareOp::Less, Some(ordering)) => ordering == Ordering::Less,
+        (CompareOp::LessEq, Some(ordering)) => ordering != Ordering::Greater,
+        (CompareOp::Greater, Some(ordering)) => ordering == Ordering::Greater,
+        (CompareOp::GreaterEq, Some(ordering)) => ordering != Ordering::Less,
+        (CompareOp::NotEq | CompareOp::Matches | CompareOp::NotMatches, _) => unreachable!(),
+    }
+}
@@ -0,0 +1,260 @@
+use regex::Regex;
+
+use super::QueryError;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+    Matches,
+    NotMatches,
+}
+
+/// `101$a` or just `101`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub field: u128,
+    pub subfield: Option<char>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Text(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Exists(Path),
+    Compare(Path, CompareOp, Value),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || "_.-$:/".contains(c)
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        let mut next_is = |expected: char| chars.next_if(|(_, c)| *c == expected).is_some();
+
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '&' if next_is('&') => Token::And,
+            '|' if next_is('|') => Token::Or,
+            '=' => {
+                next_is('=');
+                Token::Op(CompareOp::Eq)
+            }
+            '~' => Token::Op(CompareOp::Matches),
+            '!' if next_is('=') => Token::Op(CompareOp::NotEq),
+            '!' if next_is('~') => Token::Op(CompareOp::NotMatches),
+            '!' => Token::Not,
+            '<' if next_is('=') => Token::Op(CompareOp::LessEq),
+            '<' => Token::Op(CompareOp::Less),
+            '>' if next_is('=') => Token::Op(CompareOp::GreaterEq),
+            '>' => Token::Op(CompareOp::Greater),
+            '"' | '\'' => {
+                let quote = c;
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped)) => value.push(escaped),
+                            None => return Err(QueryError::new(pos, "Unterminated string")),
+                        },
+                        Some((_, c)) if c == quote => break,
+                        Some((_, c)) => value.push(c),
+                        None => return Err(QueryError::new(pos, "Unterminated string")),
+                    }
+                }
+                Token::Quoted(value)
+            }
+            _ if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+                    word.push(c);
+                }
+                match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Word(word),
+                }
+            }
+            _ => return Err(QueryError::new(pos, format!("Unexpected character `{c}`"))),
+        };
+
+        tokens.push((pos, token));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    text_len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.text_len, |(offset, _)| *offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|(_, t)| t.clone());
+        self.position += 1;
+        token
+    }
+
+    fn error(&self, message: impl Into<String>) -> QueryError {
+        QueryError::new(self.offset(), message)
+    }
+
+    // or := and ("or" and)*
+    fn parse_or(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    // and := not ("and" not)*
+    fn parse_and(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.parse_not()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    // not := "not" not | primary
+    fn parse_not(&mut self) -> Result<Expr, QueryError> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    // primary := "(" or ")" | path [op value]
+    fn parse_primary(&mut self) -> Result<Expr, QueryError> {
+        if self.peek() == Some(&Token::LeftParen) {
+            self.next();
+            let expr = self.parse_or()?;
+            if self.next() != Some(Token::RightParen) {
+                self.position -= 1;
+                return Err(self.error("Expected `)`"));
+            }
+            return Ok(expr);
+        }
+
+        let path = self.parse_path()?;
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => return Ok(Expr::Exists(path)),
+        };
+        self.next();
+
+        let value_offset = self.offset();
+        let text = match self.next() {
+            Some(Token::Word(text)) | Some(Token::Quoted(text)) => text,
+            _ => return Err(QueryError::new(value_offset, "Expected a value")),
+        };
+
+        let value = match op {
+            CompareOp::Matches | CompareOp::NotMatches => Value::Regex(
+                Regex::new(&text).map_err(|err| QueryError::new(value_offset, err.to_string()))?,
+            ),
+            _ => Value::Text(text),
+        };
+
+        Ok(Expr::Compare(path, op, value))
+    }
+
+    fn parse_path(&mut self) -> Result<Path, QueryError> {
+        let offset = self.offset();
+        let word = match self.next() {
+            Some(Token::Word(word)) => word,
+            _ => return Err(QueryError::new(offset, "Expected a field, e.g. `101$a`")),
+        };
+
+        let (field, subfield) = match word.split_once('$') {
+            Some((field, subfield)) => (field, Some(subfield)),
+            None => (word.as_str(), None),
+        };
+
+        let field = field
+            .parse()
+            .map_err(|_| QueryError::new(offset, format!("Invalid field number `{field}`")))?;
+
+        let subfield = match subfield {
+            None => None,
+            Some(subfield) => {
+                let mut chars = subfield.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(marker), None) => Some(marker.to_lowercase().next().unwrap()),
+                    _ => {
+                        return Err(QueryError::new(
+                            offset,
+                            format!("Invalid subfield `${subfield}`"),
+                        ))
+                    }
+                }
+            }
+        };
+
+        Ok(Path { field, subfield })
+    }
+}
+
+pub fn parse(text: &str) -> Result<Expr, QueryError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+        text_len: text.len(),
+    };
+
+    if parser.tokens.is_empty() {
+        return Err(QueryError::new(0, "Empty query"));
+    }
+
+    let expr = parser.parse_or()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("Unexpected token"));
+    }
+    Ok(expr)
+}
@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use crate::field::{Field, FieldData};
+use crate::record::{ErrorlessRecord, Record};
+use crate::record_ref::{FieldDataRef, RecordRef};
+use crate::typed_record::{typed_field_subfields, TypedRecord};
+
+use super::Path;
+
+/// Anything a [`super::Query`] can be run on.
+pub trait QuerySource {
+    /// Texts of every occurrence of the `path` in the record.
+    ///
+    /// A path without a subfield gives the text of a full-line field, or the
+    /// texts of all subfields of a field with subfields.
+    fn values(&self, path: &Path) -> Vec<Cow<'_, str>>;
+}
+
+fn field_data_values<'a>(data: &'a FieldData, subfield: Option<char>) -> Vec<Cow<'a, str>> {
+    match (data, subfield) {
+        (_, Some(marker)) => data
+            .get_subfields(marker)
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect(),
+        (FieldData::FullLine { text }, None) => vec![Cow::Borrowed(text.as_str())],
+        (FieldData::Subfields { subfields }, None) => subfields
+            .iter()
+            .map(|s| Cow::Borrowed(s.text.as_str()))
+            .collect(),
+    }
+}
+
+fn fields_values<'a>(fields: impl Iterator<Item = &'a Field>, path: &Path) -> Vec<Cow<'a, str>> {
+    fields
+        .filter(|field| field.number == path.field)
+        .flat_map(|field| field_data_values(&field.data, path.subfield))
+        .collect()
+}
+
+impl QuerySource for [Field] {
+    fn values(&self, path: &Path) -> Vec<Cow<'_, str>> {
+        fields_values(self.iter(), path)
+    }
+}
+
+impl QuerySource for ErrorlessRecord {
+    fn values(&self, path: &Path) -> Vec<Cow<'_, str>> {
+        fields_values(self.iter(), path)
+    }
+}
+
+/// Fields with errors are ignored.
+impl QuerySource for Record {
+    fn values(&self, path: &Path) -> Vec<Cow<'_, str>> {
+        fields_values(self.iter().filter_map(|field| field.as_ref().ok()), path)
+    }
+}
+
+/// Fields with errors are ignored.
+impl QuerySource for RecordRef<'_> {
+    fn values(&self, path: &Path) -> Vec<Cow<'_, str>> {
+        self.get_fields(path.field)
+            .flat_map(|field| match (field.data, path.subfield) {
+                (data, Some(marker)) => data.get_subfields(marker),
+                (FieldDataRef::FullLine { text }, None) => vec![text],
+                (data @ FieldDataRef::Subfields { .. }, None) => {
+                    data.subfields().map(|s| s.text).collect()
+                }
+            })
+            .map(Cow::Borrowed)
+            .collect()
+    }
+}
+
+/// Only fields that have a typed representation are visible to queries.
+impl QuerySource for TypedRecord {
+    fn values(&self, path: &Path) -> Vec<Cow<'_, str>> {
+        self.fields
+            .iter()
+            .filter(|field| field.field_number() == path.field)
+            .flat_map(|field| typed_field_subfields(field.as_ref()))
+            .filter(|(marker, _)| path.subfield.is_none_or(|m| m == *marker))
+            .map(|(_, text)| Cow::Owned(text))
+            .collect()
+    }
+}
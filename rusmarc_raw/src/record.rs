@@ -1,4 +1,4 @@
-use std::io::{BufRead, BufReader, Cursor};
+use std::io::BufRead;
 
 use crate::field::*;
 
@@ -13,57 +13,93 @@ pub fn record_remove_errors(record: Record) -> ErrorlessRecord {
         .collect()
 }
 
-pub struct RecordsReader<T: BufRead> {
+/// Splits the input into texts of records, without parsing them.
+///
+/// ```
+/// # use std::io::Cursor;
+/// use rusmarc_raw::record::{parse_record, RecordSplitter};
+///
+/// let mut splitter = RecordSplitter::new(Cursor::new("#1: a\n*****\n*****\n#1: b\n#2: c\n"));
+/// let mut text = String::new();
+///
+/// assert!(splitter.read_record(&mut text).unwrap());
+/// assert_eq!(text, "#1: a\n");
+/// assert!(splitter.read_record(&mut text).unwrap());
+/// assert_eq!(parse_record(&text).len(), 2);
+/// assert!(!splitter.read_record(&mut text).unwrap());
+/// ```
+pub struct RecordSplitter<T: BufRead> {
     input: T,
 }
 
-impl<T: BufRead> RecordsReader<T> {
+impl<T: BufRead> RecordSplitter<T> {
     pub fn new(input: T) -> Self {
         Self { input }
     }
-}
 
-impl<T: BufRead> Iterator for RecordsReader<T> {
-    type Item = Result<Record, std::io::Error>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut total_text = String::new();
-
-        // Collect entire entry text into string
-        'mainloop: loop {
-            let bytes_read;
-
-            match self.input.read_line(&mut total_text) {
-                Ok(bytes_read_in) => {
-                    bytes_read = bytes_read_in;
-                    if bytes_read == 0 {
-                        break 'mainloop; // EOF
-                    }
-                }
-                Err(err) => return Some(Err(err)),
+    /// Replaces the contents of `buffer` with the text of the next record.
+    /// Returns `false` at the end of the input.
+    ///
+    /// Reusing the same buffer for every record saves an allocation per record.
+    pub fn read_record(&mut self, buffer: &mut String) -> std::io::Result<bool> {
+        buffer.clear();
+
+        loop {
+            let line_start = buffer.len();
+            if self.input.read_line(buffer)? == 0 {
+                return Ok(!buffer.trim().is_empty()); // EOF
             }
 
-            let last_line = &total_text[(total_text.len() - bytes_read)..];
-            if does_string_only_has_a_char(last_line.trim(), '*') {
-                break;
+            if does_string_only_has_a_char(buffer[line_start..].trim(), '*') {
+                // The separator is not a field; skip empty records
+                buffer.truncate(line_start);
+                if !buffer.trim().is_empty() {
+                    return Ok(true);
+                }
+                buffer.clear();
             }
         }
+    }
+}
 
-        if total_text.len() == 0 {
-            return None;
+/// Parses the text of a single record, as given by [`RecordSplitter`].
+pub fn parse_record(text: &str) -> Record {
+    text.lines()
+        .filter_map(|line| match parse_line(line) {
+            FieldReadResult::Ok(field) => Some(Ok(field)),
+            FieldReadResult::Err(err) => Some(Err(err)),
+            FieldReadResult::Empty | FieldReadResult::End => None,
+        })
+        .collect()
+}
+
+pub struct RecordsReader<T: BufRead> {
+    splitter: RecordSplitter<T>,
+    text: String,
+}
+
+impl<T: BufRead> RecordsReader<T> {
+    pub fn new(input: T) -> Self {
+        Self {
+            splitter: RecordSplitter::new(input),
+            text: String::new(),
         }
+    }
+}
 
-        // Parse each field
-        let cursor = Cursor::new(total_text);
-        let buf_reader = BufReader::new(cursor);
-        let parser = FieldsReader::new(buf_reader);
+impl<T: BufRead> Iterator for RecordsReader<T> {
+    type Item = Result<Record, std::io::Error>;
 
-        let record: Record = parser.collect();
-        return Some(Ok(record));
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.splitter.read_record(&mut self.text) {
+            Ok(true) => Some(Ok(parse_record(&self.text))),
+            Ok(false) => None,
+            Err(err) => Some(Err(err)),
+        }
     }
 }
 
-fn does_string_only_has_a_char(text: &str, c: char) -> bool {
+pub(crate) fn does_string_only_has_a_char(text: &str, c: char) -> bool {
     for text_char in text.chars() {
         if text_char != c {
             return false;
@@ -0,0 +1,248 @@
+//! Records borrowed from a text buffer, without a `String` per field.
+//!
+//! Fields are parsed lazily on every iteration, so a [`RecordRef`] is cheap to
+//! create and to copy. Use it for tasks that only look at a few fields, and
+//! upgrade to owned values with [`RecordRef::to_record`] / [`FieldRef::to_field`]
+//! when needed.
+//!
+//! ```
+//! use rusmarc_raw::record_ref::records;
+//!
+//! // E.g. `std::str::from_utf8(&mmap)` of a memory-mapped dump
+//! let dump = "#1: id-001\n#200: ^AНазвание^FАвтор\n*****\n#1: id-002\n#200: ^AДругое\n";
+//!
+//! let ids: Vec<&str> = records(dump)
+//!     .filter_map(|record| record.get_fields(1).next()?.data.as_singular_text())
+//!     .collect();
+//! assert_eq!(ids, ["id-001", "id-002"]);
+//!
+//! let record = records(dump).next().unwrap();
+//! let title = record.get_fields(200).next().unwrap();
+//! assert_eq!(title.data.get_subfields('f'), ["Автор"]);
+//!
+//! let owned = record.to_record();
+//! assert_eq!(owned.len(), 2);
+//!
+//! // Queries work on borrowed records as well
+//! let query = rusmarc_raw::query::Query::parse("200$a ~ '^Друг'").unwrap();
+//! assert_eq!(records(dump).filter(|record| query.matches(record)).count(), 1);
+//! ```
+
+use crate::field::{get_number_and_data_parts, Error, Field, FieldData, Subfield};
+use crate::record::{does_string_only_has_a_char, Record};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubfieldRef<'a> {
+    pub marker: char,
+    pub text: &'a str,
+}
+
+impl SubfieldRef<'_> {
+    pub fn to_subfield(&self) -> Subfield {
+        Subfield {
+            marker: self.marker,
+            text: self.text.to_string(),
+        }
+    }
+}
+
+/// Borrowed [`FieldData`]. Subfields are split on access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldDataRef<'a> {
+    FullLine {
+        text: &'a str,
+    },
+    /// `text` is the raw `^Atext^Btext` data.
+    Subfields {
+        text: &'a str,
+    },
+}
+
+impl<'a> FieldDataRef<'a> {
+    /// Subfields in order. A full-line field has none.
+    pub fn subfields(&self) -> impl Iterator<Item = SubfieldRef<'a>> + 'a {
+        let text = match *self {
+            Self::FullLine { .. } => "",
+            Self::Subfields { text } => text,
+        };
+
+        text.split('^').skip(1).filter_map(|subfield| {
+            let mut chars = subfield.chars();
+            let marker = chars.next()?;
+            let text = chars.as_str();
+            (!text.is_empty()).then_some(SubfieldRef { marker, text })
+        })
+    }
+
+    /// Text of a full-line field or of the only subfield.
+    pub fn as_singular_text(&self) -> Option<&'a str> {
+        match *self {
+            Self::FullLine { text } => Some(text),
+            Self::Subfields { .. } => {
+                let mut subfields = self.subfields();
+                match (subfields.next(), subfields.next()) {
+                    (Some(subfield), None) => Some(subfield.text),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    pub fn get_main_subfields(&self) -> Vec<&'a str> {
+        self.get_subfields('a')
+    }
+
+    /// Same as [`FieldData::get_subfields`].
+    pub fn get_subfields(&self, marker: char) -> Vec<&'a str> {
+        let marker = marker.to_lowercase().next().unwrap();
+
+        match *self {
+            Self::FullLine { text } => {
+                if marker == 'a' {
+                    vec![text]
+                } else {
+                    vec![]
+                }
+            }
+            Self::Subfields { .. } => self
+                .subfields()
+                .filter(|s| {
+                    s.marker.is_ascii() && s.marker.to_lowercase().next().unwrap() == marker
+                })
+                .map(|s| s.text)
+                .collect(),
+        }
+    }
+
+    pub fn to_field_data(&self) -> FieldData {
+        match *self {
+            Self::FullLine { text } => FieldData::FullLine {
+                text: text.to_string(),
+            },
+            Self::Subfields { .. } => FieldData::Subfields {
+                subfields: self.subfields().map(|s| s.to_subfield()).collect(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldRef<'a> {
+    pub number: u128,
+    pub data: FieldDataRef<'a>,
+}
+
+impl<'a> FieldRef<'a> {
+    /// Parses a single line of a record. Empty lines and fields give `None`.
+    pub fn parse(line: &'a str) -> Option<Result<Self, Error>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        // Line starts are: `#<integer>:`
+        // Due to format being undocumented, parser will allow omitting `#` and `:`
+        let (number_part, data_part) = get_number_and_data_parts(line);
+
+        let number = match number_part.parse() {
+            Ok(number) => number,
+            Err(_) => return Some(Err(Error::NoNumberPresent)),
+        };
+        if data_part.is_empty() {
+            return None;
+        }
+
+        let data = if data_part.starts_with('^') {
+            FieldDataRef::Subfields { text: data_part }
+        } else {
+            FieldDataRef::FullLine { text: data_part }
+        };
+
+        Some(Ok(Self { number, data }))
+    }
+
+    pub fn to_field(&self) -> Field {
+        Field {
+            number: self.number,
+            data: self.data.to_field_data(),
+        }
+    }
+}
+
+/// Text of a single record, fields are parsed on access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordRef<'a> {
+    text: &'a str,
+}
+
+impl<'a> RecordRef<'a> {
+    /// `text` should not contain record separators, see [`records`].
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = Result<FieldRef<'a>, Error>> + 'a {
+        self.text.lines().filter_map(FieldRef::parse)
+    }
+
+    /// Fields with the `number`. Fields with errors are skipped.
+    pub fn get_fields(&self, number: u128) -> impl Iterator<Item = FieldRef<'a>> + 'a {
+        self.fields()
+            .filter_map(Result::ok)
+            .filter(move |field| field.number == number)
+    }
+
+    pub fn to_record(&self) -> Record {
+        self.fields()
+            .map(|field| field.map(|field| field.to_field()))
+            .collect()
+    }
+}
+
+/// Records of a whole dump, see [`records`].
+#[derive(Debug, Clone)]
+pub struct RecordRefs<'a> {
+    rest: &'a str,
+}
+
+/// Splits a dump into records, without copying.
+pub fn records(text: &str) -> RecordRefs<'_> {
+    RecordRefs { rest: text }
+}
+
+/// Byte range of the first separator line.
+fn find_separator(text: &str) -> Option<(usize, usize)> {
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        let end = start + line.len();
+        if does_string_only_has_a_char(line.trim(), '*') {
+            return Some((start, end));
+        }
+        start = end;
+    }
+    None
+}
+
+impl<'a> Iterator for RecordRefs<'a> {
+    type Item = RecordRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.rest.is_empty() {
+            let (text, rest) = match find_separator(self.rest) {
+                Some((start, end)) => (&self.rest[..start], &self.rest[end..]),
+                None => (self.rest, ""),
+            };
+            self.rest = rest;
+
+            // Skip empty records
+            if !text.trim().is_empty() {
+                return Some(RecordRef::new(text));
+            }
+        }
+        None
+    }
+}
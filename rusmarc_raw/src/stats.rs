@@ -0,0 +1,85 @@
+//! Tag and subfield frequency of a dump.
+//!
+//! ```
+//! # use std::io::Cursor;
+//! use rusmarc_raw::record::RecordsReader;
+//! use rusmarc_raw::stats::Stats;
+//!
+//! let data = "#1: id-001\n#200: ^AНазвание^FАвтор\n#5: 2021-05-01\n*****\n#1: id-002\n#200: ^AДругое\n";
+//!
+//! let mut stats = Stats::default();
+//! for record in RecordsReader::new(Cursor::new(data)) {
+//!     stats.add_record(&record.unwrap());
+//! }
+//!
+//! assert_eq!(stats.records, 2);
+//! assert_eq!(stats.fields, 5);
+//! assert_eq!(stats.typed_field_errors, 1); // 005 is `yyyymmddHHMMSS.T`
+//! assert_eq!(stats.tags[&200].occurrences, 2);
+//! assert_eq!(stats.tags[&200].subfields[&'a'], 2);
+//! assert_eq!(stats.tags[&5].records, 1);
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::record::Record;
+use crate::typed_record::{is_unimplemented_field_error, parse_typed_field};
+
+#[derive(Debug, Default, Serialize)]
+pub struct Stats {
+    pub records: usize,
+    pub fields: usize,
+    /// Lines that could not be read as fields.
+    pub field_errors: usize,
+    /// Fields with a typed representation that failed to parse into it.
+    pub typed_field_errors: usize,
+    pub tags: BTreeMap<u128, TagStats>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TagStats {
+    /// How many times the field occurs, repetitions included.
+    pub occurrences: usize,
+    /// How many records have the field.
+    pub records: usize,
+    /// Occurrences of every subfield, by lowercase marker.
+    pub subfields: BTreeMap<char, usize>,
+}
+
+impl Stats {
+    pub fn add_record(&mut self, record: &Record) {
+        self.records += 1;
+
+        let mut seen = Vec::new();
+        for field in record {
+            let field = match field {
+                Ok(field) => field,
+                Err(_) => {
+                    self.field_errors += 1;
+                    continue;
+                }
+            };
+            self.fields += 1;
+
+            let tag = self.tags.entry(field.number).or_default();
+            tag.occurrences += 1;
+            if !seen.contains(&field.number) {
+                seen.push(field.number);
+                tag.records += 1;
+            }
+            if let crate::field::FieldData::Subfields { subfields } = &field.data {
+                for subfield in subfields {
+                    let marker = subfield.marker.to_lowercase().next().unwrap();
+                    *tag.subfields.entry(marker).or_default() += 1;
+                }
+            }
+
+            match parse_typed_field(field.clone()) {
+                Err(err) if !is_unimplemented_field_error(&err) => self.typed_field_errors += 1,
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use super::{AnyTypedField, TypedRecord};
+
+/// Builds a [`TypedRecord`], keeping fields in the order of their numbers.
+///
+/// Fields with the same number stay in the order they were added.
+#[derive(Debug, Default)]
+pub struct TypedRecordBuilder {
+    record: TypedRecord,
+}
+
+impl TypedRecordBuilder {
+    pub fn field(mut self, field: impl AnyTypedField + 'static) -> Self {
+        self.record.insert_sorted(field);
+        self
+    }
+
+    /// Adds fields of any types, e.g. those of [`super::parse_typed_field`].
+    pub fn fields(mut self, fields: impl IntoIterator<Item = Box<dyn AnyTypedField>>) -> Self {
+        for field in fields {
+            self.record.insert_boxed_sorted(field);
+        }
+        self
+    }
+
+    pub fn build(self) -> TypedRecord {
+        self.record
+    }
+}
@@ -1,14 +1,14 @@
-use std::num::ParseIntError;
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+use rusmarc_raw_macros::{IntoFieldData, TypedField};
 
-use rusmarc_raw_macros::TypedField;
-
-use crate::field::FieldData;
-use crate::typed_record::{ParseTypedFieldError, TypedField};
+use crate::field::{FieldData, Subfield};
+use crate::identifiers::{Ean13, IdentifierError, Isbn, Ismn, Issn};
+use crate::typed_record::{IntoFieldData, ParseTypedFieldError, TypedField};
 
 use super::util::{concat_subfields, expect_max_one_subfield};
 
 /// 001 ИДЕНТИФИКАТОР ЗАПИСИ
-#[derive(Debug, TypedField)]
+#[derive(Debug, PartialEq, TypedField)]
 pub struct Field001RecordId {
     pub id: String,
 }
@@ -28,9 +28,16 @@ impl TryFrom<FieldData> for Field001RecordId {
             .into())
     }
 }
+impl IntoFieldData for &Field001RecordId {
+    fn into_field_data(self) -> FieldData {
+        FieldData::FullLine {
+            text: self.id.clone(),
+        }
+    }
+}
 
 /// 003 ПОСТОЯННЫЙ ИДЕНТИФИКАТОР ЗАПИСИ
-#[derive(Debug, TypedField)]
+#[derive(Debug, PartialEq, TypedField)]
 pub struct Field003PersistentRecordId {
     pub id: String,
 }
@@ -50,35 +57,69 @@ impl TryFrom<FieldData> for Field003PersistentRecordId {
             .into())
     }
 }
+impl IntoFieldData for &Field003PersistentRecordId {
+    fn into_field_data(self) -> FieldData {
+        FieldData::FullLine {
+            text: self.id.clone(),
+        }
+    }
+}
 
 /// 005 ИДЕНТИФИКАТОР ВЕРСИИ
 ///
-/// `ГГГГММДДЧЧММСС.Т` (eng: `yyyymmddHHMMSS.T`)
-#[derive(Debug, TypedField)]
+/// `ГГГГММДДЧЧММСС.Т` (eng: `yyyymmddHHMMSS.T`), where `Т` is tenths of a second.
+///
+/// Versions are ordered by time, so the newer of two versions of the same
+/// record is simply the greater one:
+/// ```
+/// use rusmarc_raw::typed_record::Field005Version;
+///
+/// let old = Field005Version::try_from("20190314091500.0").unwrap();
+/// let new = Field005Version::try_from("20240101120000.5").unwrap();
+/// assert!(new > old);
+/// assert_eq!(std::cmp::max(&old, &new).to_string(), "20240101120000.5");
+///
+/// assert!(Field005Version::try_from("20241301000000.0").is_err()); // Month 13
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, TypedField)]
 pub struct Field005Version {
-    pub year: u16,
-    pub month: u16,
-    pub day: u16,
-    pub hour: u16,
-    pub minute: u16,
-    pub second: u16,
-    pub t: u32, // idk wtf is that T, it is not clear
+    pub timestamp: NaiveDateTime,
 }
 
-impl Default for Field005Version {
-    fn default() -> Self {
+impl Field005Version {
+    /// Tenths of a second (`Т`), `0..=9`.
+    pub fn tenths(&self) -> u32 {
+        self.timestamp.nanosecond() / 100_000_000
+    }
+}
+
+impl From<NaiveDateTime> for Field005Version {
+    /// Precision beyond tenths of a second is dropped.
+    fn from(value: NaiveDateTime) -> Self {
+        let tenths = value.nanosecond() / 100_000_000 % 10;
         Self {
-            year: Default::default(),
-            month: Default::default(),
-            day: Default::default(),
-            hour: Default::default(),
-            minute: Default::default(),
-            second: Default::default(),
-            t: Default::default(),
+            timestamp: value.with_nanosecond(tenths * 100_000_000).unwrap(),
         }
     }
 }
 
+impl From<Field005Version> for NaiveDateTime {
+    fn from(value: Field005Version) -> Self {
+        value.timestamp
+    }
+}
+
+impl std::fmt::Display for Field005Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}",
+            self.timestamp.format("%Y%m%d%H%M%S"),
+            self.tenths()
+        )
+    }
+}
+
 impl TryFrom<FieldData> for Field005Version {
     type Error = ParseTypedFieldError;
 
@@ -91,67 +132,87 @@ impl TryFrom<FieldData> for Field005Version {
     }
 }
 
+impl IntoFieldData for &Field005Version {
+    fn into_field_data(self) -> FieldData {
+        FieldData::FullLine {
+            text: self.to_string(),
+        }
+    }
+}
+
 impl TryFrom<&str> for Field005Version {
     type Error = ParseTypedFieldError;
 
+    /// Parses `yyyymmddHHMMSS.T`.
+    ///
+    /// The value may be cut after any date or time part (e.g. `20240101` or
+    /// `2024010112`): missing parts are taken as the start of the period.
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let value = value.trim();
 
-        if value.chars().any(|c| !c.is_digit(10) && c != '.') || !value.is_ascii() {
+        if value.chars().any(|c| !c.is_ascii_digit() && c != '.') {
             return Err("Contains characters besides digits and points".to_string());
         }
 
-        let split: Vec<&str> = value.split('.').collect();
-        if split.len() > 2 {
+        let (base, t_text) = match value.split_once('.') {
+            Some((base, t_text)) => (base, t_text),
+            None => (value, ""),
+        };
+        if t_text.contains('.') {
             return Err("Value has multiple points".to_string());
         }
-        let base = split[0];
-        let t_text = split[1];
-
-        // Start parsing
-
-        let mut result = Field005Version::default();
-
-        // Parse t
-        if t_text.len() > 0 {
-            result.t = t_text
-                .parse()
-                .map_err(|e: ParseIntError| -> String { e.to_string() })?;
-        }
 
-        // Parse year
-        // [name, digits, reference]
-        let parts: [(&str, usize, &mut u16); 6] = [
-            ("year", 4, &mut result.year),
-            ("month", 2, &mut result.month),
-            ("day", 2, &mut result.day),
-            ("hour", 2, &mut result.hour),
-            ("minute", 2, &mut result.minute),
-            ("second", 2, &mut result.second),
+        // [name, digits, default]
+        let parts: [(&str, usize, u32); 6] = [
+            ("year", 4, 0),
+            ("month", 2, 1),
+            ("day", 2, 1),
+            ("hour", 2, 0),
+            ("minute", 2, 0),
+            ("second", 2, 0),
         ];
 
-        let mut base = base;
-        for (name, digits_count, store_to) in parts {
-            if base.len() == 0 {
-                break;
+        let mut values = [0u32; 6];
+        let mut rest = base;
+        for (i, (name, digits_count, default)) in parts.into_iter().enumerate() {
+            if rest.is_empty() {
+                if i == 0 {
+                    return Err("No year specified".to_string());
+                }
+                values[i] = default;
+                continue;
             }
-            if value.len() < digits_count {
-                return Err(format!("No {} specified", name));
+            if rest.len() < digits_count {
+                return Err(format!("Incomplete {} in '{}'", name, rest));
             }
-            let part;
-            (part, base) = base.split_at(digits_count);
 
-            *store_to = part
-                .parse::<u16>()
-                .map_err(|err| format!("Failed to parse {} from '{}': {:?}", name, part, err))?;
+            let part;
+            (part, rest) = rest.split_at(digits_count);
+            values[i] = part.parse().unwrap(); // Only ASCII digits at this point
         }
+        if !rest.is_empty() {
+            return Err(format!("Unexpected trailing digits '{}'", rest));
+        }
+
+        // Only the first digit is tenths, the rest is more precision than RUSMARC defines
+        let tenths = t_text.chars().next().map_or(0, |c| c.to_digit(10).unwrap());
+
+        let [year, month, day, hour, minute, second] = values;
+        let date = NaiveDate::from_ymd_opt(year as i32, month, day)
+            .ok_or(format!("Invalid date {:04}-{:02}-{:02}", year, month, day))?;
+        let timestamp = date
+            .and_hms_milli_opt(hour, minute, second, tenths * 100)
+            .ok_or(format!(
+                "Invalid time {:02}:{:02}:{:02}",
+                hour, minute, second
+            ))?;
 
-        return Ok(result);
+        Ok(Self { timestamp })
     }
 }
 
 /// 010 МЕЖДУНАРОДНЫЙ СТАНДАРТНЫЙ НОМЕР КНИГИ (ISBN)
-#[derive(Debug, TypedField)]
+#[derive(Debug, Default, PartialEq, TypedField, IntoFieldData)]
 pub struct Field010Isbn {
     /// $a   Номер (ISBN)
     pub isbn: String,
@@ -201,8 +262,15 @@ impl TryFrom<FieldData> for Field010Isbn {
     }
 }
 
+impl Field010Isbn {
+    /// `$a` as a normalized and checksum-verified ISBN.
+    pub fn parsed_isbn(&self) -> Result<Isbn, IdentifierError> {
+        Isbn::parse(&self.isbn)
+    }
+}
+
 /// 011 МЕЖДУНАРОДНЫЙ СТАНДАРТНЫЙ НОМЕР СЕРИАЛЬНОГО ИЗДАНИЯ (ISSN)
-#[derive(Debug, TypedField)]
+#[derive(Debug, PartialEq, TypedField, IntoFieldData)]
 pub struct Field011Issn {
     /// $a   Номер (ISSN)
     pub issn: Option<String>,
@@ -222,8 +290,43 @@ pub struct Field011Issn {
     pub circulation: Option<String>,
 }
 
+impl TryFrom<FieldData> for Field011Issn {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let issn = expect_max_one_subfield(data.get_subfields('a'))?;
+        let clarifications = expect_max_one_subfield(data.get_subfields('b'))?;
+        let issn_l = expect_max_one_subfield(data.get_subfields('f'))?;
+
+        // Result
+        Ok(Self {
+            issn:             issn.map(|x| x.to_owned()),
+            clarifications:   clarifications.map(|x| x.to_owned()),
+            price:            concat_subfields(data.get_subfields('d')),
+            issn_l:           issn_l.map(|x| x.to_owned()),
+            cancelled_issn_l: concat_subfields(data.get_subfields('g')),
+            canelled_issn:    concat_subfields(data.get_subfields('y')),
+            errorneous_issn:  concat_subfields(data.get_subfields('z')),
+            circulation:      concat_subfields(data.get_subfields('9')),
+        })
+    }
+}
+
+impl Field011Issn {
+    /// `$a` as a normalized and checksum-verified ISSN.
+    pub fn parsed_issn(&self) -> Option<Result<Issn, IdentifierError>> {
+        self.issn.as_deref().map(Issn::parse)
+    }
+
+    /// `$f` as a normalized and checksum-verified ISSN-L.
+    pub fn parsed_issn_l(&self) -> Option<Result<Issn, IdentifierError>> {
+        self.issn_l.as_deref().map(Issn::parse)
+    }
+}
+
 /// 012 ИДЕНТИФИКАТОР ФИНГЕРПРИНТ     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field012Fingerprint {
     /// $a   Фингерпринт
     pub fingerprint: Option<String>,
@@ -236,7 +339,7 @@ pub struct Field012Fingerprint {
 }
 
 /// 013 МЕЖДУНАРОДНЫЙ СТАНДАРТНЫЙ НОМЕР ИЗДАНИЯ МУЗЫКАЛЬНОГО ПРОИЗВЕДЕНИЯ (ISMN)     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, PartialEq, TypedField, IntoFieldData)]
 pub struct Field013Ismn {
     /// $a   Номер (ISMN)
     pub ismn: Option<String>,
@@ -250,8 +353,35 @@ pub struct Field013Ismn {
     pub circulation: Option<String>,
 }
 
+impl TryFrom<FieldData> for Field013Ismn {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let ismn = expect_max_one_subfield(data.get_subfields('a'))?;
+        let clarifications = expect_max_one_subfield(data.get_subfields('b'))?;
+        let price = expect_max_one_subfield(data.get_subfields('d'))?;
+
+        // Result
+        Ok(Self {
+            ismn:            ismn.map(|x| x.to_owned()),
+            clarifications:  clarifications.map(|x| x.to_owned()),
+            price:           price.map(|x| x.to_owned()),
+            errorneous_ismn: concat_subfields(data.get_subfields('z')),
+            circulation:     concat_subfields(data.get_subfields('9')),
+        })
+    }
+}
+
+impl Field013Ismn {
+    /// `$a` as a normalized and checksum-verified ISMN.
+    pub fn parsed_ismn(&self) -> Option<Result<Ismn, IdentifierError>> {
+        self.ismn.as_deref().map(Ismn::parse)
+    }
+}
+
 /// 014 ИДЕНТИФИКАТОР СТАТЬИ     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field014ArticleId {
     /// $a   Идентификатор статьи
     pub id: Option<String>,
@@ -262,7 +392,7 @@ pub struct Field014ArticleId {
 }
 
 /// 015 МЕЖДУНАРОДНЫЙ СТАНДАРТНЫЙ НОМЕР ТЕХНИЧЕСКОГО ОТЧЕТА (ISRN)     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field015Isrn {
     /// $a   Номер (ISRN)
     pub isrn: Option<String>,
@@ -277,7 +407,7 @@ pub struct Field015Isrn {
 }
 
 /// 016 МЕЖДУНАРОДНЫЙ СТАНДАРТНЫЙ НОМЕР АУДИО/ВИДЕО ЗАПИСИ (ISRC)     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field016Isrc {
     /// $a   Номер (ISRC)
     pub isrc: Option<String>,
@@ -292,7 +422,7 @@ pub struct Field016Isrc {
 }
 
 /// 017 ДРУГОЙ СТАНДАРТНЫЙ ИДЕНТИФИКАТОР     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field017OtherStandardId {
     /// $a   Стандартный номер
     pub standard_number: Option<String>,
@@ -309,7 +439,7 @@ pub struct Field017OtherStandardId {
 }
 
 /// 020 НОМЕР ДОКУМЕНТА В НАЦИОНАЛЬНОЙ БИБЛИОГРАФИИ     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field020NationalBibliographyNumber {
     /// $a   Код страны
     pub country_code: Option<String>,
@@ -322,7 +452,7 @@ pub struct Field020NationalBibliographyNumber {
 }
 
 /// 021 НОМЕР ГОСУДАРСТВЕННОЙ РЕГИСТРАЦИИ     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field021StateRegistrationNumber {
     /// $a   Код страны
     pub country_code: Option<String>,
@@ -335,7 +465,7 @@ pub struct Field021StateRegistrationNumber {
 }
 
 /// 022 НОМЕР ПУБЛИКАЦИИ ОРГАНА ГОСУДАРСТВЕННОЙ ВЛАСТИ     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field022GovernmentPublicationNumber {
     /// $a   Код страны
     pub country_code: Option<String>,
@@ -346,7 +476,7 @@ pub struct Field022GovernmentPublicationNumber {
 }
 
 /// 029 НОМЕР ДОКУМЕНТА (НОРМАТИВНЫЕ И ТЕХНИЧЕСКИЕ ДОКУМЕНТЫ. НЕОПУБЛИКОВАННЫЕ ДОКУМЕНТЫ)     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field029DocumentNumber {
     /// $a   Страна или международная организация, присвоившая номер
     pub country_or_org: Option<String>,
@@ -361,7 +491,7 @@ pub struct Field029DocumentNumber {
 }
 
 /// 033 ПОСТОЯННЫЙ ИДЕНТИФИКАТОР ЗАПИСИ ДРУГОЙ СИСТЕМЫ     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field033PersistentId {
     /// $a   Идентификатор записи
     pub record_id: Option<String>,
@@ -370,7 +500,7 @@ pub struct Field033PersistentId {
 }
 
 /// 035 ДРУГИЕ СИСТЕМНЫЕ НОМЕРА     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field035OtherSystemNumbers {
     /// $a   Идентификатор записи
     pub record_id: Option<String>,
@@ -379,7 +509,7 @@ pub struct Field035OtherSystemNumbers {
 }
 
 /// 036 МУЗЫКАЛЬНЫЙ ИНЦИПИТ     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field036MusicalIncipit {
     /// $a   Номер произведения
     pub work_number: Option<String>,
@@ -418,7 +548,7 @@ pub struct Field036MusicalIncipit {
 }
 
 /// 039 НОМЕР ЗАЯВКИ (ПАТЕНТНЫЕ ДОКУМЕНТЫ)     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field039PatentApplicationNumber {
     /// $a   Страна
     pub country: Option<String>,
@@ -429,7 +559,7 @@ pub struct Field039PatentApplicationNumber {
 }
 
 /// 071 ИЗДАТЕЛЬСКИЙ НОМЕР     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field071PublisherNumber {
     /// $a   Номер, присвоенный агентством
     pub assigned_number: Option<String>,
@@ -444,7 +574,7 @@ pub struct Field071PublisherNumber {
 }
 
 /// 073 МЕЖДУНАРОДНЫЙ НОМЕР ТОВАРА (EAN)     (П)
-#[derive(Debug, TypedField)]
+#[derive(Debug, PartialEq, TypedField, IntoFieldData)]
 pub struct Field073Ean {
     /// $a   Стандартный номер (EAN)
     pub standard_number: Option<String>,
@@ -460,9 +590,38 @@ pub struct Field073Ean {
     pub circulation: Option<String>,
 }
 
+impl TryFrom<FieldData> for Field073Ean {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let standard_number = expect_max_one_subfield(data.get_subfields('a'))?;
+        let clarifications = expect_max_one_subfield(data.get_subfields('b'))?;
+        let additional_codes = expect_max_one_subfield(data.get_subfields('c'))?;
+        let availability_or_price = expect_max_one_subfield(data.get_subfields('d'))?;
+
+        // Result
+        Ok(Self {
+            standard_number:       standard_number.map(|x| x.to_owned()),
+            clarifications:        clarifications.map(|x| x.to_owned()),
+            additional_codes:      additional_codes.map(|x| x.to_owned()),
+            availability_or_price: availability_or_price.map(|x| x.to_owned()),
+            erroneous_number:      concat_subfields(data.get_subfields('z')),
+            circulation:           concat_subfields(data.get_subfields('9')),
+        })
+    }
+}
+
+impl Field073Ean {
+    /// `$a` as a normalized and checksum-verified EAN-13.
+    pub fn parsed_ean(&self) -> Option<Result<Ean13, IdentifierError>> {
+        self.standard_number.as_deref().map(Ean13::parse)
+    }
+}
+
 /// 079 ИЗДАТЕЛЬСКИЕ НОМЕРА (КРОМЕ ЗВУКОЗАПИСЕЙ И НОТНЫХ ИЗДАНИЙ) (устаревшее)     (П)
 #[deprecated]
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field079PublisherNumbers {
     /// $a   Издательский номер, присвоенный агентством
     pub assigned_number: Option<String>,
@@ -1,6 +1,12 @@
-use rusmarc_raw_macros::TypedField;
+use rusmarc_raw_macros::{IntoFieldData, TypedField};
 
-use crate::typed_record::TypedField;
+use crate::codes::{CountryCode, LanguageCode};
+use crate::field::{FieldData, Subfield};
+use crate::typed_record::{IntoFieldData, TypedField};
+
+use super::util::expect_max_one_subfield;
+use super::validation::{Finding, Validate};
+use super::ParseTypedFieldError;
 
 /// Тип даты
 #[derive(Debug)]
@@ -55,6 +61,28 @@ impl From<String> for DateType {
     }
 }
 
+impl std::fmt::Display for DateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::CurrentOngoingResource => "a",
+            Self::OngoingResourceWithEndedPublication => "b",
+            Self::OngoingResourceWithUnknownStatus => "c",
+            Self::MonographPublishedInOneYear => "d",
+            Self::ReproducedDocument => "e",
+            Self::MonographWithUnknownPublicationDate => "f",
+            Self::MonographPublishedForMoreThanOneYear => "g",
+            Self::MonographWithActualPublicationDateAndCopyrightDate => "h",
+            Self::MonographWithProductionAndRealizationDate => "i",
+            Self::DocumentWithExactPublicationDate => "j",
+            Self::MonographWithDifferentEditionAndManufacturingDates => "k",
+            Self::ExtremeDatesCollection => "l",
+            Self::UnknownPublicationDate => "u",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Код целевого назначения
 #[derive(Debug)]
 pub enum TargetAudienceCode {
@@ -93,6 +121,23 @@ impl From<String> for TargetAudienceCode {
     }
 }
 
+impl std::fmt::Display for TargetAudienceCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::YouthGeneral => "a",
+            Self::PreschoolChildren => "b",
+            Self::YoungerChildren => "c",
+            Self::MiddleAgeChildren => "d",
+            Self::Youth14to20 => "e",
+            Self::AdultsScientific => "k",
+            Self::AdultsGeneral => "m",
+            Self::Unknown => "u",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Код публикации органа государственной власти
 #[derive(Debug)]
 pub enum GovernmentPublicationCode {
@@ -140,6 +185,26 @@ impl From<String> for GovernmentPublicationCode {
     }
 }
 
+impl std::fmt::Display for GovernmentPublicationCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::FederalNational => "a",
+            Self::RepublicState => "b",
+            Self::RegionArea => "c",
+            Self::LocalMunicipal => "d",
+            Self::Interterritorial => "e",
+            Self::Intergovernmental => "f",
+            Self::ExiledGovernment => "g",
+            Self::UndefinedLevel => "h",
+            Self::Unknown => "u",
+            Self::NonGovernmentPublication => "y",
+            Self::OtherLevel => "z",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Код транслитерации
 #[derive(Debug)]
 pub enum TransliterationCode {
@@ -166,6 +231,19 @@ impl From<String> for TransliterationCode {
     }
 }
 
+impl std::fmt::Display for TransliterationCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::IsoRules => "a",
+            Self::OtherRules => "b",
+            Self::MultipleSystems => "c",
+            Self::NoTransliteration => "y",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Дополнительные наборы символов
 #[derive(Debug)]
 pub enum CharacterSets {
@@ -225,6 +303,30 @@ impl From<String> for CharacterSets {
     }
 }
 
+impl std::fmt::Display for CharacterSets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::Iso646 => "01",
+            Self::Iso37 => "02",
+            Self::Iso5426 => "03",
+            Self::Iso5427 => "04",
+            Self::Iso5428 => "05",
+            Self::Iso6438 => "06",
+            Self::Iso10586 => "07",
+            Self::Iso8957Table1 => "08",
+            Self::Iso8957Table2 => "09",
+            Self::Reserved => "10",
+            Self::Iso54262 => "11",
+            Self::Iso10646Unicode => "50",
+            Self::CodePage866 => "79",
+            Self::Win1251 => "89",
+            Self::Koi8 => "99",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Графика заглавия
 #[derive(Debug)]
 pub enum TitleGraphics {
@@ -286,6 +388,30 @@ impl From<String> for TitleGraphics {
     }
 }
 
+impl std::fmt::Display for TitleGraphics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::Latin => "ba",
+            Self::Cyrillic => "ca",
+            Self::JapaneseUndetermined => "da",
+            Self::JapaneseKanji => "db",
+            Self::JapaneseKana => "dc",
+            Self::Chinese => "ea",
+            Self::Arabic => "fa",
+            Self::Greek => "ga",
+            Self::Hebrew => "ha",
+            Self::Thai => "ia",
+            Self::Devanagari => "ja",
+            Self::Korean => "ka",
+            Self::Tamil => "la",
+            Self::Georgian => "ma",
+            Self::Armenian => "mb",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// 100 ДАННЫЕ ОБЩЕЙ ОБРАБОТКИ
 #[derive(Debug, TypedField)]
 pub struct Field100GeneralProcessingData {
@@ -315,44 +441,175 @@ pub struct Field100GeneralProcessingData {
     pub title_graphics: Option<TitleGraphics>,
 }
 
+/// Code of `width` characters at fixed positions of `$a`, blank if unknown.
+fn fixed_position(code: Option<impl std::fmt::Display>, width: usize) -> String {
+    match code {
+        Some(code) => format!("{:<width$.width$}", code.to_string()),
+        None => " ".repeat(width),
+    }
+}
+
+impl IntoFieldData for &Field100GeneralProcessingData {
+    fn into_field_data(self) -> FieldData {
+        let text = [
+            fixed_position(Some(&self.date_input), 8),
+            fixed_position(Some(&self.date_type), 1),
+            fixed_position(Some(&self.date_1), 4),
+            fixed_position(Some(&self.date_2), 4),
+            fixed_position(self.target_audience_code.as_ref(), 3),
+            fixed_position(self.publication_code.as_ref(), 1),
+            fixed_position(self.modified_record_code, 1),
+            fixed_position(self.cataloging_language.as_ref(), 3),
+            fixed_position(self.transliteration_code.as_ref(), 1),
+            fixed_position(Some(&self.character_sets), 4),
+            fixed_position(self.extra_character_sets.as_ref(), 4),
+            fixed_position(self.title_graphics.as_ref(), 2),
+        ]
+        .concat();
+
+        FieldData::Subfields {
+            subfields: vec![Subfield { marker: 'A', text }],
+        }
+    }
+}
+
 /// 101 ЯЗЫК ДОКУМЕНТА
-#[derive(Debug, TypedField)]
+#[derive(Debug, Default, PartialEq, TypedField, IntoFieldData)]
 pub struct Field101Language {
-    /// $a   Язык текста, звукозаписи и т.д.
-    pub language_text: Option<String>,
-    /// $b   Язык промежуточного перевода
-    pub language_intermediate_translation: Option<String>,
-    /// $c   Язык оригинала
-    pub language_original: Option<String>,
-    /// $d   Язык резюме
-    pub language_summary: Option<String>,
-    /// $e   Язык оглавления
-    pub language_contents: Option<String>,
-    /// $f   Язык титульного листа
-    pub language_title_page: Option<String>,
+    /// $a   Язык текста, звукозаписи и т.д.     (П)
+    pub language_text: Vec<LanguageCode>,
+    /// $b   Язык промежуточного перевода     (П)
+    pub language_intermediate_translation: Vec<LanguageCode>,
+    /// $c   Язык оригинала     (П)
+    pub language_original: Vec<LanguageCode>,
+    /// $d   Язык резюме     (П)
+    pub language_summary: Vec<LanguageCode>,
+    /// $e   Язык оглавления     (П)
+    pub language_contents: Vec<LanguageCode>,
+    /// $f   Язык титульного листа     (П)
+    pub language_title_page: Vec<LanguageCode>,
     /// $g   Язык основного заглавия
-    pub language_main_title: Option<String>,
-    /// $h   Язык либретто
-    pub language_libretto: Option<String>,
-    /// $i   Язык сопроводительного материала
-    pub language_supporting_material: Option<String>,
-    /// $j   Язык субтитров
-    pub language_subtitles: Option<String>,
+    pub language_main_title: Vec<LanguageCode>,
+    /// $h   Язык либретто     (П)
+    pub language_libretto: Vec<LanguageCode>,
+    /// $i   Язык сопроводительного материала     (П)
+    pub language_supporting_material: Vec<LanguageCode>,
+    /// $j   Язык субтитров     (П)
+    pub language_subtitles: Vec<LanguageCode>,
+}
+
+impl Field101Language {
+    /// Every subfield with its codes, in subfield order.
+    pub fn subfields(&self) -> [(char, &[LanguageCode]); 10] {
+        [
+            ('a', &self.language_text),
+            ('b', &self.language_intermediate_translation),
+            ('c', &self.language_original),
+            ('d', &self.language_summary),
+            ('e', &self.language_contents),
+            ('f', &self.language_title_page),
+            ('g', &self.language_main_title),
+            ('h', &self.language_libretto),
+            ('i', &self.language_supporting_material),
+            ('j', &self.language_subtitles),
+        ]
+    }
+}
+
+impl TryFrom<FieldData> for Field101Language {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let codes = |marker| -> Vec<LanguageCode> {
+            data.get_subfields(marker)
+                .into_iter()
+                .map(|x| LanguageCode::from(x.to_string()))
+                .collect()
+        };
+
+        // Result
+        Ok(Self {
+            language_text:                     codes('a'),
+            language_intermediate_translation: codes('b'),
+            language_original:                 codes('c'),
+            language_summary:                  codes('d'),
+            language_contents:                 codes('e'),
+            language_title_page:               codes('f'),
+            language_main_title:               codes('g'),
+            language_libretto:                 codes('h'),
+            language_supporting_material:      codes('i'),
+            language_subtitles:                codes('j'),
+        })
+    }
+}
+
+impl Validate for Field101Language {
+    fn validate(&self) -> Vec<Finding> {
+        self.subfields()
+            .into_iter()
+            .flat_map(|(marker, codes)| codes.iter().map(move |code| (marker, code)))
+            .filter(|(_, code)| !code.is_known())
+            .map(|(marker, code)| {
+                Finding::new(
+                    101,
+                    Some(marker),
+                    format!("Unknown ISO 639-2 language code `{code}`"),
+                )
+            })
+            .collect()
+    }
 }
 
 /// 102 СТРАНА ПУБЛИКАЦИИ ИЛИ ПРОИЗВОДСТВА
-#[derive(Debug, TypedField)]
+#[derive(Debug, PartialEq, TypedField, IntoFieldData)]
 pub struct Field102CountryOfPublication {
-    /// $a   Страна публикации
-    pub country_of_publication: Option<String>,
-    /// $b   Место издания (не ISO)
-    pub place_of_publication_non_iso: Option<String>,
-    /// $c   Место издания (ISO)
-    pub place_of_publication_iso: Option<String>,
+    /// $a   Страна публикации     (П)
+    pub country_of_publication: Vec<CountryCode>,
+    /// $b   Место издания (не ISO)     (П)
+    pub place_of_publication_non_iso: Vec<String>,
+    /// $c   Место издания (ISO)     (П)
+    pub place_of_publication_iso: Vec<String>,
     /// $2   Код системы (источник кода, отличный от ISO)
     pub code_system: Option<String>,
 }
 
+impl TryFrom<FieldData> for Field102CountryOfPublication {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let strings = |marker| -> Vec<String> {
+            data.get_subfields(marker).into_iter().map(|x| x.to_string()).collect()
+        };
+        let code_system = expect_max_one_subfield(data.get_subfields('2'))?;
+
+        // Result
+        Ok(Self {
+            country_of_publication:       strings('a').into_iter().map(CountryCode::from).collect(),
+            place_of_publication_non_iso: strings('b'),
+            place_of_publication_iso:     strings('c'),
+            code_system:                  code_system.map(|x| x.to_owned()),
+        })
+    }
+}
+
+impl Validate for Field102CountryOfPublication {
+    fn validate(&self) -> Vec<Finding> {
+        self.country_of_publication
+            .iter()
+            .filter(|code| !code.is_known())
+            .map(|code| {
+                Finding::new(
+                    102,
+                    Some('a'),
+                    format!("Unknown ISO 3166-1 country code `{code}`"),
+                )
+            })
+            .collect()
+    }
+}
+
 /// Коды иллюстраций
 #[derive(Debug)]
 pub enum IllustrationCode {
@@ -415,6 +672,31 @@ impl From<String> for IllustrationCode {
     }
 }
 
+impl std::fmt::Display for IllustrationCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::Illustrations => "a",
+            Self::Maps => "b",
+            Self::Portraits => "c",
+            Self::NauticalMaps => "d",
+            Self::Plans => "e",
+            Self::Inserts => "f",
+            Self::MusicalWorks => "g",
+            Self::Facsimiles => "h",
+            Self::CoatsOfArms => "i",
+            Self::GenealogicalTables => "j",
+            Self::Forms => "k",
+            Self::Samples => "l",
+            Self::SoundRecordings => "m",
+            Self::Transparencies => "n",
+            Self::DecorationsAndOrnaments => "o",
+            Self::NoIllustrations => "y",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Коды формы содержания
 #[derive(Debug)]
 pub enum ContentFormCode {
@@ -500,6 +782,38 @@ impl From<String> for ContentFormCode {
     }
 }
 
+impl std::fmt::Display for ContentFormCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::PreDoctoralThesis => "7",
+            Self::BibliographicPublication => "a",
+            Self::Catalog => "b",
+            Self::Index => "c",
+            Self::Abstract => "d",
+            Self::Dictionary => "e",
+            Self::Encyclopedia => "f",
+            Self::GeneralReferenceBook => "g",
+            Self::ProjectDescription => "h",
+            Self::StatisticalData => "i",
+            Self::Textbook => "j",
+            Self::PatentDocument => "k",
+            Self::Standard => "l",
+            Self::DissertationOriginal => "m",
+            Self::LawsAndLegislations => "n",
+            Self::DigitalTables => "o",
+            Self::TechnicalReport => "p",
+            Self::ExaminationSheet => "q",
+            Self::LiteraryReview => "r",
+            Self::Contracts => "s",
+            Self::CartoonsOrComics => "t",
+            Self::RevisedDissertation => "v",
+            Self::ReligiousTexts => "w",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Код конференции
 #[derive(Debug)]
 pub enum ConferenceCode {
@@ -520,6 +834,17 @@ impl From<String> for ConferenceCode {
     }
 }
 
+impl std::fmt::Display for ConferenceCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::NotConferencePublication => "0",
+            Self::ConferencePublication => "1",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Индикатор юбилейного издания
 #[derive(Debug)]
 pub enum AnniversaryEditionIndicator {
@@ -540,6 +865,17 @@ impl From<String> for AnniversaryEditionIndicator {
     }
 }
 
+impl std::fmt::Display for AnniversaryEditionIndicator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::NotAnniversaryEdition => "0",
+            Self::AnniversaryEdition => "1",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Индикатор указателя
 #[derive(Debug)]
 pub enum IndexIndicator {
@@ -560,6 +896,17 @@ impl From<String> for IndexIndicator {
     }
 }
 
+impl std::fmt::Display for IndexIndicator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::NoIndex => "0",
+            Self::IndexPresent => "1",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Код литературного жанра
 #[derive(Debug)]
 pub enum LiteraryGenreCode {
@@ -604,6 +951,25 @@ impl From<String> for LiteraryGenreCode {
     }
 }
 
+impl std::fmt::Display for LiteraryGenreCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::Fiction => "a",
+            Self::Drama => "b",
+            Self::Essays => "c",
+            Self::HumorAndSatire => "d",
+            Self::Letters => "e",
+            Self::ShortStories => "f",
+            Self::Poetry => "g",
+            Self::SpeechesAndRhetoric => "h",
+            Self::NonLiteraryText => "y",
+            Self::MixedAndOtherForms => "z",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Код биографии
 #[derive(Debug)]
 pub enum BiographyCode {
@@ -633,6 +999,20 @@ impl From<String> for BiographyCode {
     }
 }
 
+impl std::fmt::Display for BiographyCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::Autobiography => "a",
+            Self::BiographyOfAnIndividual => "b",
+            Self::CollectiveBiography => "c",
+            Self::BiographicalCollection => "d",
+            Self::NotBiography => "y",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// Код ступени высшего профессионального образования
 #[derive(Debug)]
 pub enum HigherEducationDegreeCode {
@@ -670,6 +1050,22 @@ impl From<String> for HigherEducationDegreeCode {
     }
 }
 
+impl std::fmt::Display for HigherEducationDegreeCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::IncompleteHigherEducation => "aa",
+            Self::Bachelor => "ab",
+            Self::Specialist => "ac",
+            Self::Master => "ad",
+            Self::UnknownHigherEducation => "au",
+            Self::PostgraduateCandidate => "ba",
+            Self::PostdoctoralDoctor => "ca",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// 105 ПОЛЕ КОДИРОВАННЫХ ДАННЫХ: ТЕКСТОВЫЕ МАТЕРИАЛЫ, МОНОГРАФИЧЕСКИЕ
 #[derive(Debug, TypedField)]
 pub struct Field105TextMaterials {
@@ -690,6 +1086,41 @@ pub struct Field105TextMaterials {
     /// $9   Код ступени высшего профессионального образования
     pub higher_education_degree_code: Option<HigherEducationDegreeCode>,
 }
+
+impl IntoFieldData for &Field105TextMaterials {
+    fn into_field_data(self) -> FieldData {
+        let illustrations: String = self
+            .illustration_codes
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let content_forms: String = self
+            .content_form_codes
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let text = [
+            fixed_position(Some(illustrations), 4),
+            fixed_position(Some(content_forms), 4),
+            fixed_position(self.conference_code.as_ref(), 1),
+            fixed_position(self.anniversary_edition_indicator.as_ref(), 1),
+            fixed_position(self.index_indicator.as_ref(), 1),
+            fixed_position(self.literary_genre_code.as_ref(), 1),
+            fixed_position(self.biography_code.as_ref(), 1),
+        ]
+        .concat();
+
+        let mut subfields = vec![Subfield { marker: 'A', text }];
+        if let Some(code) = &self.higher_education_degree_code {
+            subfields.push(Subfield {
+                marker: '9',
+                text: code.to_string(),
+            });
+        }
+        FieldData::Subfields { subfields }
+    }
+}
+
 /// Форма документа: кодированные данные: обозначение носителя
 #[derive(Debug)]
 pub enum DocumentForm {
@@ -738,8 +1169,28 @@ impl From<String> for DocumentForm {
     }
 }
 
+impl std::fmt::Display for DocumentForm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            Self::LargePrint => "d",
+            Self::NewspaperFormat => "e",
+            Self::BrailleAndMoonFonts => "f",
+            Self::Microprint => "g",
+            Self::Handwritten => "h",
+            Self::MultiMedia => "i",
+            Self::MiniPrint => "j",
+            Self::RegularPrint => "r",
+            Self::ElectronicResource => "s",
+            Self::Microform => "t",
+            Self::OtherFontForms => "z",
+            Self::Other(code) => code,
+        };
+        f.write_str(code)
+    }
+}
+
 /// 106 ПОЛЕ КОДИРОВАННЫХ ДАННЫХ: ФОРМА ДОКУМЕНТА
-#[derive(Debug, TypedField)]
+#[derive(Debug, TypedField, IntoFieldData)]
 pub struct Field106DocumentForm {
     /// $a   Форма документа: кодированные данные: обозначение носителя
     pub document_form: DocumentForm,
@@ -1,4 +1,3 @@
-// TODO: 205 Сведения об издании
 // TODO: 206 Область специфических сведений: картографические материалы - математические данные
 // TODO: 207 Область специфических сведений: нумерация продолжающихся ресурсов
 // TODO: 208 Область специфических сведений: нотные издания
@@ -11,18 +10,17 @@
 // TODO: 283 Тип носителя
 
 // TODO: 203 Вид содержания и тип средства
-// TODO: 210 Публикация, распространение и др.
-// TODO: 215 Физическая характеристика
 
-use crate::typed_record::TypedField;
-use rusmarc_raw_macros::TypedField;
+use crate::typed_record::{IntoFieldData, TypedField};
+use rusmarc_raw_macros::{IntoFieldData, TypedField};
 
-use crate::field::FieldData;
+use crate::field::{FieldData, Subfield};
 
-use super::{util::concat_subfields, ParseTypedFieldError};
+use super::util::{concat_subfields, expect_max_one_subfield};
+use super::ParseTypedFieldError;
 
 /// 200 ЗАГЛАВИЕ И СВЕДЕНИЯ ОБ ОТВЕТСТВЕННОСТИ
-#[derive(Debug, TypedField)]
+#[derive(Debug, Default, PartialEq, TypedField, IntoFieldData)]
 pub struct Field200Header {
     /// $a   Основное заглавие     (П)
     pub main_title: Option<String>,
@@ -78,3 +76,118 @@ impl TryFrom<FieldData> for Field200Header {
         })
     }
 }
+
+/// 205 СВЕДЕНИЯ ОБ ИЗДАНИИ
+#[derive(Debug, Default, PartialEq, TypedField, IntoFieldData)]
+pub struct Field205Edition {
+    /// $a   Сведения об издании
+    pub edition: Option<String>,
+    /// $b   Дополнительные сведения об издании     (П)
+    pub additional_info: Vec<String>,
+    /// $d   Параллельные сведения об издании     (П)
+    pub parallel_edition: Vec<String>,
+    /// $f   Первые сведения об ответственности, относящиеся к изданию     (П)
+    pub main_responsibility: Vec<String>,
+    /// $g   Последующие сведения об ответственности     (П)
+    pub other_responsibility: Vec<String>,
+}
+
+impl TryFrom<FieldData> for Field205Edition {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let strings = |marker| -> Vec<String> {
+            data.get_subfields(marker).into_iter().map(|x| x.to_string()).collect()
+        };
+        let edition = expect_max_one_subfield(data.get_subfields('a'))?;
+
+        // Result
+        Ok(Self {
+            edition:              edition.map(|x| x.to_owned()),
+            additional_info:      strings('b'),
+            parallel_edition:     strings('d'),
+            main_responsibility:  strings('f'),
+            other_responsibility: strings('g'),
+        })
+    }
+}
+
+/// 210 ПУБЛИКАЦИЯ, РАСПРОСТРАНЕНИЕ И ДР.
+#[derive(Debug, Default, PartialEq, TypedField, IntoFieldData)]
+pub struct Field210PublicationDistribution {
+    /// $a   Место издания, распространения и т. д.     (П)
+    pub place: Vec<String>,
+    /// $b   Адрес издателя, распространителя и т. д.     (П)
+    pub address: Vec<String>,
+    /// $c   Имя издателя, распространителя и т. д.     (П)
+    pub publisher: Vec<String>,
+    /// $d   Дата издания, распространения и т. д.
+    pub date: Option<String>,
+    /// $e   Место изготовления     (П)
+    pub manufacture_place: Vec<String>,
+    /// $f   Адрес изготовителя     (П)
+    pub manufacturer_address: Vec<String>,
+    /// $g   Имя изготовителя     (П)
+    pub manufacturer: Vec<String>,
+    /// $h   Дата изготовления
+    pub manufacture_date: Option<String>,
+}
+
+impl TryFrom<FieldData> for Field210PublicationDistribution {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let strings = |marker| -> Vec<String> {
+            data.get_subfields(marker).into_iter().map(|x| x.to_string()).collect()
+        };
+        let date = expect_max_one_subfield(data.get_subfields('d'))?;
+        let manufacture_date = expect_max_one_subfield(data.get_subfields('h'))?;
+
+        // Result
+        Ok(Self {
+            place:                strings('a'),
+            address:              strings('b'),
+            publisher:            strings('c'),
+            date:                 date.map(|x| x.to_owned()),
+            manufacture_place:    strings('e'),
+            manufacturer_address: strings('f'),
+            manufacturer:         strings('g'),
+            manufacture_date:     manufacture_date.map(|x| x.to_owned()),
+        })
+    }
+}
+
+/// 215 ФИЗИЧЕСКАЯ ХАРАКТЕРИСТИКА
+#[derive(Debug, Default, PartialEq, TypedField, IntoFieldData)]
+pub struct Field215PhysicalDescription {
+    /// $a   Специфическое обозначение материала и объем     (П)
+    pub extent: Vec<String>,
+    /// $c   Другие уточнения физических характеристик
+    pub other_details: Option<String>,
+    /// $d   Размеры     (П)
+    pub dimensions: Vec<String>,
+    /// $e   Сопроводительный материал     (П)
+    pub accompanying_material: Vec<String>,
+}
+
+impl TryFrom<FieldData> for Field215PhysicalDescription {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let strings = |marker| -> Vec<String> {
+            data.get_subfields(marker).into_iter().map(|x| x.to_string()).collect()
+        };
+        let other_details = expect_max_one_subfield(data.get_subfields('c'))?;
+
+        // Result
+        Ok(Self {
+            extent:                strings('a'),
+            other_details:         other_details.map(|x| x.to_owned()),
+            dimensions:            strings('d'),
+            accompanying_material: strings('e'),
+        })
+    }
+}
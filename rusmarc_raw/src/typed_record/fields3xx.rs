@@ -0,0 +1,31 @@
+// TODO: 300 Общие примечания
+// TODO: 320 Примечания о наличии библиографии / указателя
+// TODO: 327 Примечания о содержании
+
+use rusmarc_raw_macros::{IntoFieldData, TypedField};
+
+use crate::field::{FieldData, Subfield};
+use crate::typed_record::{IntoFieldData, TypedField};
+
+use super::util::expect_max_one_subfield;
+use super::ParseTypedFieldError;
+
+/// 330 РЕЗЮМЕ ИЛИ РЕФЕРАТ
+#[derive(Debug, PartialEq, TypedField, IntoFieldData)]
+pub struct Field330Summary {
+    /// $a   Текст примечания
+    pub text: String,
+}
+
+impl TryFrom<FieldData> for Field330Summary {
+    type Error = ParseTypedFieldError;
+
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let text = expect_max_one_subfield(data.get_subfields('a'))?
+            .ok_or("No summary text specified".to_string())?;
+
+        Ok(Self {
+            text: text.to_owned(),
+        })
+    }
+}
@@ -0,0 +1,178 @@
+// TODO: 720 Имя / наименование – родовое имя
+
+use rusmarc_raw_macros::{IntoFieldData, TypedField};
+
+use crate::field::{FieldData, Subfield};
+use crate::typed_record::{IntoFieldData, TypedField};
+
+use super::util::expect_max_one_subfield;
+use super::ParseTypedFieldError;
+
+/// Имя лица в полях 700, 701 и 702
+#[derive(Debug, Default, Clone, PartialEq, Eq, IntoFieldData)]
+pub struct PersonalName {
+    /// $a   Начальный элемент ввода
+    pub entry_element: String,
+    /// $b   Часть имени, кроме начального элемента ввода
+    pub rest_of_name: Option<String>,
+    /// $c   Дополнения к именам, кроме дат     (П)
+    pub additions: Vec<String>,
+    /// $d   Римские цифры
+    pub roman_numerals: Option<String>,
+    /// $f   Даты
+    pub dates: Option<String>,
+    /// $g   Расширение инициалов личного имени
+    pub expanded_initials: Option<String>,
+    /// $p   Наименование / адрес организации
+    pub affiliation: Option<String>,
+    /// $3   Номер авторитетной / нормативной записи
+    pub authority_record_number: Option<String>,
+    /// $4   Код отношения     (П)
+    pub relator_codes: Vec<String>,
+}
+
+impl PersonalName {
+    /// `Иванов, И. И.`, as names are sorted in catalogues.
+    pub fn inverted(&self) -> String {
+        match &self.rest_of_name {
+            Some(rest) => format!("{}, {rest}", self.entry_element),
+            None => self.entry_element.clone(),
+        }
+    }
+}
+
+impl TryFrom<FieldData> for PersonalName {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let strings = |marker| -> Vec<String> {
+            data.get_subfields(marker).into_iter().map(|x| x.to_string()).collect()
+        };
+        let one = |marker| -> Result<Option<String>, ParseTypedFieldError> {
+            Ok(expect_max_one_subfield(data.get_subfields(marker))?.map(|x| x.to_owned()))
+        };
+
+        // Result
+        Ok(Self {
+            entry_element:           one('a')?.ok_or("No entry element specified".to_string())?,
+            rest_of_name:            one('b')?,
+            additions:               strings('c'),
+            roman_numerals:          one('d')?,
+            dates:                   one('f')?,
+            expanded_initials:       one('g')?,
+            affiliation:             one('p')?,
+            authority_record_number: one('3')?,
+            relator_codes:           strings('4'),
+        })
+    }
+}
+
+/// Наименование организации в полях 710, 711 и 712
+#[derive(Debug, Default, Clone, PartialEq, Eq, IntoFieldData)]
+pub struct CorporateName {
+    /// $a   Начальный элемент ввода
+    pub entry_element: String,
+    /// $b   Структурное подразделение     (П)
+    pub subdivisions: Vec<String>,
+    /// $c   Идентифицирующий признак     (П)
+    pub additions: Vec<String>,
+    /// $d   Порядковый номер мероприятия и / или его части     (П)
+    pub meeting_number: Vec<String>,
+    /// $e   Место проведения мероприятия
+    pub meeting_place: Option<String>,
+    /// $f   Дата проведения мероприятия
+    pub meeting_date: Option<String>,
+    /// $3   Номер авторитетной / нормативной записи
+    pub authority_record_number: Option<String>,
+    /// $4   Код отношения     (П)
+    pub relator_codes: Vec<String>,
+}
+
+impl CorporateName {
+    /// Name with its subdivisions, `Организация. Подразделение`.
+    pub fn full_name(&self) -> String {
+        std::iter::once(self.entry_element.as_str())
+            .chain(self.subdivisions.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(". ")
+    }
+}
+
+impl TryFrom<FieldData> for CorporateName {
+    type Error = ParseTypedFieldError;
+
+    #[rustfmt::skip]
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        let strings = |marker| -> Vec<String> {
+            data.get_subfields(marker).into_iter().map(|x| x.to_string()).collect()
+        };
+        let one = |marker| -> Result<Option<String>, ParseTypedFieldError> {
+            Ok(expect_max_one_subfield(data.get_subfields(marker))?.map(|x| x.to_owned()))
+        };
+
+        // Result
+        Ok(Self {
+            entry_element:           one('a')?.ok_or("No entry element specified".to_string())?,
+            subdivisions:            strings('b'),
+            additions:               strings('c'),
+            meeting_number:          strings('d'),
+            meeting_place:           one('e')?,
+            meeting_date:            one('f')?,
+            authority_record_number: one('3')?,
+            relator_codes:           strings('4'),
+        })
+    }
+}
+
+/// Fields 7xx only differ in the kind of responsibility.
+macro_rules! name_field {
+    ($(#[$doc:meta])* $field:ident($name:ident)) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq, TypedField)]
+        pub struct $field {
+            pub name: $name,
+        }
+
+        impl TryFrom<FieldData> for $field {
+            type Error = ParseTypedFieldError;
+
+            fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+                Ok(Self {
+                    name: $name::try_from(data)?,
+                })
+            }
+        }
+
+        impl IntoFieldData for &$field {
+            fn into_field_data(self) -> FieldData {
+                self.name.into_field_data()
+            }
+        }
+    };
+}
+
+name_field! {
+    /// 700 ИМЯ ЛИЦА – ПЕРВИЧНАЯ ОТВЕТСТВЕННОСТЬ
+    Field700PersonPrimary(PersonalName)
+}
+name_field! {
+    /// 701 ИМЯ ЛИЦА – АЛЬТЕРНАТИВНАЯ ОТВЕТСТВЕННОСТЬ
+    Field701PersonAlternative(PersonalName)
+}
+name_field! {
+    /// 702 ИМЯ ЛИЦА – ВТОРИЧНАЯ ОТВЕТСТВЕННОСТЬ
+    Field702PersonSecondary(PersonalName)
+}
+name_field! {
+    /// 710 НАИМЕНОВАНИЕ ОРГАНИЗАЦИИ – ПЕРВИЧНАЯ ОТВЕТСТВЕННОСТЬ
+    Field710OrganizationPrimary(CorporateName)
+}
+name_field! {
+    /// 711 НАИМЕНОВАНИЕ ОРГАНИЗАЦИИ – АЛЬТЕРНАТИВНАЯ ОТВЕТСТВЕННОСТЬ
+    Field711OrganizationAlternative(CorporateName)
+}
+name_field! {
+    /// 712 НАИМЕНОВАНИЕ ОРГАНИЗАЦИИ – ВТОРИЧНАЯ ОТВЕТСТВЕННОСТЬ
+    Field712OrganizationSecondary(CorporateName)
+}
@@ -0,0 +1,50 @@
+use crate::field::{Field, FieldData};
+use crate::record::ErrorlessRecord;
+
+use super::*;
+
+/// Subfields of a typed field as `(marker, text)` with lowercase markers.
+/// Full-line fields use `a`.
+pub(crate) fn typed_field_subfields(field: &dyn AnyTypedField) -> Vec<(char, String)> {
+    match field.to_field_data() {
+        FieldData::FullLine { text } => vec![('a', text)],
+        FieldData::Subfields { subfields } => subfields
+            .into_iter()
+            .map(|subfield| (subfield.marker.to_ascii_lowercase(), subfield.text))
+            .collect(),
+    }
+}
+
+impl TypedRecord {
+    /// Untyped fields back from the typed ones, in the same order, to be
+    /// written with any [`crate::formats::RecordWriter`].
+    ///
+    /// ```
+    /// use rusmarc_raw::formats::{record_writer, Format};
+    /// use rusmarc_raw::typed_record::{Field001RecordId, Field200Header, TypedRecord};
+    ///
+    /// let record = TypedRecord::builder()
+    ///     .field(Field001RecordId::from("local-1".to_string()))
+    ///     .field(Field200Header {
+    ///         main_title: Some("Основы права".to_string()),
+    ///         ..Default::default()
+    ///     })
+    ///     .build();
+    ///
+    /// let mut output = Vec::new();
+    /// let mut writer = record_writer(&mut output, Format::Text);
+    /// writer.write_record(&record.to_record()).unwrap();
+    /// writer.finish().unwrap();
+    /// drop(writer);
+    /// assert!(String::from_utf8(output).unwrap().starts_with("#1: local-1\n#200: ^AОсновы права\n"));
+    /// ```
+    pub fn to_record(&self) -> ErrorlessRecord {
+        self.fields
+            .iter()
+            .map(|field| Field {
+                number: field.field_number(),
+                data: field.to_field_data(),
+            })
+            .collect()
+    }
+}
@@ -3,17 +3,27 @@
 //!
 //! <http://www.rusmarc.info/2017/rusmarc/fields.htm>
 
+mod builder;
 mod fields0xx;
 mod fields1xx;
 mod fields2xx;
+mod fields3xx;
+mod fields7xx;
+mod lowering;
 mod util;
+mod validation;
 
 use std::{any::Any, marker::PhantomData};
 
 use crate::field::FieldData;
+pub use builder::TypedRecordBuilder;
 pub use fields0xx::*;
 pub use fields1xx::*;
 pub use fields2xx::*;
+pub use fields3xx::*;
+pub use fields7xx::*;
+pub(crate) use lowering::typed_field_subfields;
+pub use validation::*;
 
 pub trait TypedField: std::fmt::Debug {
     fn field_number(&self) -> u128;
@@ -33,9 +43,30 @@ where
     }
 }
 
-pub trait AnyTypedField: std::fmt::Debug {
+/// Untyped data of a typed field, the reverse of `TryFrom<FieldData>`.
+///
+/// Implemented for references, so that fields can be lowered without being
+/// consumed. Fields below 010 are full lines, the rest are subfields with
+/// uppercase markers, as vendors write them.
+///
+/// ```
+/// use rusmarc_raw::typed_record::{Field200Header, IntoFieldData};
+///
+/// let header = Field200Header {
+///     main_title: Some("Основы права".to_string()),
+///     title_related_info: Some("Учебник".to_string()),
+///     ..Default::default()
+/// };
+/// assert_eq!(header.into_field_data().to_string(), "^AОсновы права^EУчебник");
+/// ```
+pub trait IntoFieldData {
+    fn into_field_data(self) -> FieldData;
+}
+
+pub trait AnyTypedField: std::fmt::Debug + Send + Sync {
     fn any_ref(&self) -> &dyn Any;
     fn any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
 
     fn typed_field_ref(&self) -> &dyn TypedField;
     fn typed_field_mut(&mut self) -> &mut dyn TypedField;
@@ -43,15 +74,23 @@ pub trait AnyTypedField: std::fmt::Debug {
     fn field_number(&self) -> u128 {
         self.typed_field_ref().field_number()
     }
+
+    fn to_field_data(&self) -> FieldData;
 }
 
-impl<T: Any + TypedField + std::fmt::Debug> AnyTypedField for T {
+impl<T: Any + TypedField + std::fmt::Debug + Send + Sync> AnyTypedField for T
+where
+    for<'a> &'a T: IntoFieldData,
+{
     fn any_ref(&self) -> &dyn Any {
         self as &dyn Any
     }
     fn any_mut(&mut self) -> &mut dyn Any {
         self as &mut dyn Any
     }
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 
     fn typed_field_ref(&self) -> &dyn TypedField {
         self as &dyn TypedField
@@ -59,6 +98,10 @@ impl<T: Any + TypedField + std::fmt::Debug> AnyTypedField for T {
     fn typed_field_mut(&mut self) -> &mut dyn TypedField {
         self as &mut dyn TypedField
     }
+
+    fn to_field_data(&self) -> FieldData {
+        self.into_field_data()
+    }
 }
 
 /// Typed collection of fields, in accordance to RUSMARC documentation.
@@ -90,7 +133,7 @@ impl<T: Any + TypedField + std::fmt::Debug> AnyTypedField for T {
 /// let id2 = record2.get_fields::<Field001RecordId>().next().unwrap();
 /// assert_eq!(id2.id, "id-002");
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct TypedRecord {
     pub fields: Vec<Box<dyn AnyTypedField>>,
 }
@@ -152,20 +195,188 @@ impl TypedRecord {
         let iter: TypedFieldsView<'a, T> = TypedFieldsView::new(&self.fields);
         iter
     }
+
+    /// Empty record to add fields to in the order of their numbers.
+    ///
+    /// ```
+    /// use rusmarc_raw::typed_record::{Field001RecordId, Field101Language, Field200Header, TypedRecord};
+    ///
+    /// let record = TypedRecord::builder()
+    ///     .field(Field200Header {
+    ///         main_title: Some("Основы права".to_string()),
+    ///         ..Default::default()
+    ///     })
+    ///     .field(Field001RecordId::from("local-1".to_string()))
+    ///     .build();
+    ///
+    /// let numbers: Vec<_> = record.fields.iter().map(|field| field.field_number()).collect();
+    /// assert_eq!(numbers, [1, 200]);
+    /// ```
+    pub fn builder() -> TypedRecordBuilder {
+        TypedRecordBuilder::default()
+    }
+
+    pub fn get_fields_mut<'a, T: 'static + TypedField>(
+        &'a mut self,
+    ) -> impl Iterator<Item = &'a mut T> + 'a {
+        self.fields
+            .iter_mut()
+            .filter_map(|field| field.any_mut().downcast_mut::<T>())
+    }
+
+    /// Removes every field of type `T` and returns them in order.
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+    /// use rusmarc_raw::typed_record::{Field200Header, Field700PersonPrimary, Field701PersonAlternative, TypedRecord};
+    ///
+    /// let data = "#200: ^AОсновы\n#700: ^AИванов\n#701: ^AПетров\n#701: ^AСидоров\n";
+    /// let record = RecordsReader::new(Cursor::new(data)).next().unwrap().unwrap();
+    /// let (mut record, _errors) = TypedRecord::parse(record_remove_errors(record).into_iter());
+    ///
+    /// // Co-authors that the vendor put in the wrong field
+    /// for mut author in record.remove_fields::<Field701PersonAlternative>() {
+    ///     author.name.relator_codes = vec!["070".to_string()];
+    ///     record.insert_sorted(Field700PersonPrimary { name: author.name });
+    /// }
+    /// record.get_fields_mut::<Field200Header>().next().unwrap().title_related_info = Some("Учебник".to_string());
+    ///
+    /// let fields: Vec<_> = record.fields.iter().map(|field| field.field_number()).collect();
+    /// assert_eq!(fields, [200, 700, 700, 700]);
+    /// let names: Vec<_> = record.get_fields::<Field700PersonPrimary>().map(|field| &field.name.entry_element).collect();
+    /// assert_eq!(names, ["Иванов", "Петров", "Сидоров"]);
+    /// ```
+    pub fn remove_fields<T: 'static + TypedField>(&mut self) -> Vec<T> {
+        let (removed, kept) = std::mem::take(&mut self.fields)
+            .into_iter()
+            .partition(|field| field.any_ref().is::<T>());
+        self.fields = kept;
+
+        removed
+            .into_iter()
+            .map(|field: Box<dyn AnyTypedField>| *field.into_any().downcast::<T>().unwrap())
+            .collect()
+    }
+
+    /// Puts `field` in place of the first field of its type and returns the
+    /// replaced field. Without one, inserts `field` as [`TypedRecord::insert_sorted`].
+    ///
+    /// ```
+    /// use rusmarc_raw::typed_record::{Field001RecordId, Field200Header, TypedRecord};
+    ///
+    /// let mut record = TypedRecord::builder().field(Field200Header::default()).build();
+    /// assert!(record.replace_field(Field001RecordId::from("lan-1".to_string())).is_none());
+    ///
+    /// let old = record.replace_field(Field001RecordId::from("local-1".to_string()));
+    /// assert_eq!(old.unwrap().id, "lan-1");
+    /// assert_eq!(record.fields.len(), 2);
+    /// assert_eq!(record.fields[0].field_number(), 1);
+    /// ```
+    pub fn replace_field<T: 'static + AnyTypedField>(&mut self, field: T) -> Option<T> {
+        let Some(position) = self
+            .fields
+            .iter()
+            .position(|other| other.any_ref().is::<T>())
+        else {
+            self.insert_sorted(field);
+            return None;
+        };
+
+        let replaced = std::mem::replace(&mut self.fields[position], Box::new(field));
+        Some(*replaced.into_any().downcast::<T>().unwrap())
+    }
+
+    /// Inserts `field` after the fields with the same or lower number, so a
+    /// record sorted by field numbers stays sorted.
+    pub fn insert_sorted(&mut self, field: impl AnyTypedField + 'static) {
+        self.insert_boxed_sorted(Box::new(field));
+    }
+
+    fn insert_boxed_sorted(&mut self, field: Box<dyn AnyTypedField>) {
+        let number = field.field_number();
+        let position = self
+            .fields
+            .partition_point(|other| other.field_number() <= number);
+        self.fields.insert(position, field);
+    }
+
+    /// Findings of [`Validate`] for every field of the record.
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// use rusmarc_raw::record::{record_remove_errors, RecordsReader};
+    /// use rusmarc_raw::typed_record::TypedRecord;
+    ///
+    /// let data = "#101: ^Arus^Axyz\n#102: ^ARU";
+    /// let record = RecordsReader::new(Cursor::new(data)).next().unwrap().unwrap();
+    /// let (record, _errors) = TypedRecord::parse(record_remove_errors(record).into_iter());
+    ///
+    /// let findings = record.validate();
+    /// assert_eq!(findings.len(), 1);
+    /// assert_eq!(findings[0].to_string(), "#101$a: Unknown ISO 639-2 language code `xyz`");
+    /// ```
+    pub fn validate(&self) -> Vec<Finding> {
+        self.fields
+            .iter()
+            .flat_map(|field| validate_typed_field(field.as_ref()))
+            .collect()
+    }
+
+    /// Version identifier (field 005), if the record has one.
+    pub fn version(&self) -> Option<&Field005Version> {
+        self.get_fields::<Field005Version>().max()
+    }
+
+    /// Picks the newer of two versions of the same record.
+    ///
+    /// A record without field 005 is considered older than any versioned one.
+    /// On a tie, `self` is returned.
+    pub fn newer<'a>(&'a self, other: &'a TypedRecord) -> &'a TypedRecord {
+        if other.version() > self.version() {
+            other
+        } else {
+            self
+        }
+    }
 }
 
+// Tags are written with three digits, as in the records
 #[rustfmt::skip]
+#[allow(clippy::zero_prefixed_literal)]
 pub fn parse_typed_field(field: crate::field::Field) -> Result<Box<dyn AnyTypedField>, String> {
     match field.number {
         001 => field.data.parse::<Field001RecordId>(),
         003 => field.data.parse::<Field003PersistentRecordId>(),
         005 => field.data.parse::<Field005Version>(),
         010 => field.data.parse::<Field010Isbn>(),
+        011 => field.data.parse::<Field011Issn>(),
+        013 => field.data.parse::<Field013Ismn>(),
+        073 => field.data.parse::<Field073Ean>(),
+        101 => field.data.parse::<Field101Language>(),
+        102 => field.data.parse::<Field102CountryOfPublication>(),
         200 => field.data.parse::<Field200Header>(),
-        other => Err(format!("Unimplemented field: `{other}`")),
+        205 => field.data.parse::<Field205Edition>(),
+        210 => field.data.parse::<Field210PublicationDistribution>(),
+        215 => field.data.parse::<Field215PhysicalDescription>(),
+        330 => field.data.parse::<Field330Summary>(),
+        700 => field.data.parse::<Field700PersonPrimary>(),
+        701 => field.data.parse::<Field701PersonAlternative>(),
+        702 => field.data.parse::<Field702PersonSecondary>(),
+        710 => field.data.parse::<Field710OrganizationPrimary>(),
+        711 => field.data.parse::<Field711OrganizationAlternative>(),
+        712 => field.data.parse::<Field712OrganizationSecondary>(),
+        other => Err(format!("{UNIMPLEMENTED_FIELD}: `{other}`")),
     }
 }
 
+const UNIMPLEMENTED_FIELD: &str = "Unimplemented field";
+
+/// Whether the error of [`parse_typed_field`] only says that the field has no typed representation yet.
+pub fn is_unimplemented_field_error(error: &ParseTypedFieldError) -> bool {
+    error.starts_with(UNIMPLEMENTED_FIELD)
+}
+
 /*
 
 All fields used by Znanium, Ssga, and Lan:
@@ -0,0 +1,56 @@
+//! Checks of field contents that go beyond parsing.
+//!
+//! A typed field may be parsed successfully and still contain values that do
+//! not make sense, e.g. a language code that is not in ISO 639-2. Such problems
+//! are reported as [`Finding`]s instead of parse errors, so that the data is
+//! not lost.
+
+use serde::Serialize;
+
+use super::{AnyTypedField, Field101Language, Field102CountryOfPublication, TypedField};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub field: u128,
+    pub subfield: Option<char>,
+    pub message: String,
+}
+
+impl Finding {
+    pub fn new(field: u128, subfield: Option<char>, message: String) -> Self {
+        Self {
+            field,
+            subfield,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.subfield {
+            Some(marker) => write!(f, "#{}${}: {}", self.field, marker, self.message),
+            None => write!(f, "#{}: {}", self.field, self.message),
+        }
+    }
+}
+
+pub trait Validate {
+    fn validate(&self) -> Vec<Finding>;
+}
+
+fn validate_as<T: 'static + TypedField + Validate>(field: &dyn AnyTypedField) -> Vec<Finding> {
+    match field.any_ref().downcast_ref::<T>() {
+        Some(field) => field.validate(),
+        None => vec![],
+    }
+}
+
+/// Runs [`Validate`] for fields that implement it. Other fields produce no findings.
+pub fn validate_typed_field(field: &dyn AnyTypedField) -> Vec<Finding> {
+    match field.field_number() {
+        101 => validate_as::<Field101Language>(field),
+        102 => validate_as::<Field102CountryOfPublication>(field),
+        _ => vec![],
+    }
+}
@@ -1,2 +1,2 @@
 #!/bin/bash
-cargo run --release > ./log.txt
+cargo run --release -- stats "$@" > ./log.txt
@@ -0,0 +1,306 @@
+//! Typed fields survive lowering to `FieldData` and parsing back.
+
+use std::fmt::Debug;
+
+use chrono::{NaiveDate, NaiveTime};
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use rusmarc_raw::codes::{CountryCode, LanguageCode};
+use rusmarc_raw::field::FieldData;
+use rusmarc_raw::typed_record::*;
+
+fn round_trip<T>(field: T) -> Result<(), TestCaseError>
+where
+    T: Debug + PartialEq + TryFrom<FieldData, Error = ParseTypedFieldError>,
+    for<'a> &'a T: IntoFieldData,
+{
+    let data = (&field).into_field_data();
+    let parsed = T::try_from(data.clone())
+        .map_err(|error| TestCaseError::fail(format!("{error} in `{data}`")))?;
+    prop_assert_eq!(parsed, field);
+    Ok(())
+}
+
+fn text() -> impl Strategy<Value = String> {
+    "\\PC{0,12}"
+}
+
+fn optional() -> impl Strategy<Value = Option<String>> {
+    proptest::option::of(text())
+}
+
+fn repeated() -> impl Strategy<Value = Vec<String>> {
+    proptest::collection::vec(text(), 0..3)
+}
+
+/// Codes that are kept as they are, unlike lowercase or ISO 639-2/T ones.
+fn languages() -> impl Strategy<Value = Vec<LanguageCode>> {
+    proptest::collection::vec(
+        select(&["rus", "eng", "ger", "fre", "chi", "und"][..])
+            .prop_map(|code| LanguageCode::from(code.to_string())),
+        0..3,
+    )
+}
+
+fn countries() -> impl Strategy<Value = Vec<CountryCode>> {
+    proptest::collection::vec("[A-Z]{2}".prop_map(CountryCode::from), 0..3)
+}
+
+prop_compose! {
+    fn version()(
+        date in (1900..2100i32, 1..=12u32, 1..=28u32),
+        time in (0..24u32, 0..60u32, 0..60u32),
+        tenths in 0..10u32,
+    ) -> Field005Version {
+        let date = NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+        let time = NaiveTime::from_hms_milli_opt(time.0, time.1, time.2, tenths * 100).unwrap();
+        Field005Version::from(date.and_time(time))
+    }
+}
+
+prop_compose! {
+    fn isbn()(
+        isbn in text(),
+        clarifications in optional(),
+        price in optional(),
+        errorneous_isbn in optional(),
+        circulation in optional(),
+    ) -> Field010Isbn {
+        Field010Isbn { isbn, clarifications, price, errorneous_isbn, circulation }
+    }
+}
+
+prop_compose! {
+    fn issn()(
+        (issn, clarifications, price, issn_l) in (optional(), optional(), optional(), optional()),
+        (cancelled_issn_l, canelled_issn, errorneous_issn, circulation) in (optional(), optional(), optional(), optional()),
+    ) -> Field011Issn {
+        Field011Issn {
+            issn, clarifications, price, issn_l,
+            cancelled_issn_l, canelled_issn, errorneous_issn, circulation,
+        }
+    }
+}
+
+prop_compose! {
+    fn ismn()(
+        ismn in optional(),
+        clarifications in optional(),
+        price in optional(),
+        errorneous_ismn in optional(),
+        circulation in optional(),
+    ) -> Field013Ismn {
+        Field013Ismn { ismn, clarifications, price, errorneous_ismn, circulation }
+    }
+}
+
+prop_compose! {
+    fn ean()(
+        (standard_number, clarifications, additional_codes) in (optional(), optional(), optional()),
+        (availability_or_price, erroneous_number, circulation) in (optional(), optional(), optional()),
+    ) -> Field073Ean {
+        Field073Ean {
+            standard_number, clarifications, additional_codes,
+            availability_or_price, erroneous_number, circulation,
+        }
+    }
+}
+
+prop_compose! {
+    fn language()(
+        (language_text, language_intermediate_translation, language_original, language_summary, language_contents)
+            in (languages(), languages(), languages(), languages(), languages()),
+        (language_title_page, language_main_title, language_libretto, language_supporting_material, language_subtitles)
+            in (languages(), languages(), languages(), languages(), languages()),
+    ) -> Field101Language {
+        Field101Language {
+            language_text, language_intermediate_translation, language_original, language_summary,
+            language_contents, language_title_page, language_main_title, language_libretto,
+            language_supporting_material, language_subtitles,
+        }
+    }
+}
+
+prop_compose! {
+    fn country()(
+        country_of_publication in countries(),
+        place_of_publication_non_iso in repeated(),
+        place_of_publication_iso in repeated(),
+        code_system in optional(),
+    ) -> Field102CountryOfPublication {
+        Field102CountryOfPublication {
+            country_of_publication, place_of_publication_non_iso, place_of_publication_iso, code_system,
+        }
+    }
+}
+
+prop_compose! {
+    fn header()(
+        (main_title, general_material_notion, parallel_title, title_related_info, main_responsibility)
+            in (optional(), optional(), optional(), optional(), optional()),
+        (other_responsibility, part_number, part_name, end_dates, main_documents_dates)
+            in (optional(), optional(), optional(), optional(), optional()),
+        (title_page_info, volume_notion, parallel_title_lang, org_and_instance)
+            in (optional(), optional(), optional(), optional()),
+    ) -> Field200Header {
+        Field200Header {
+            main_title, general_material_notion, parallel_title, title_related_info,
+            main_responsibility, other_responsibility, part_number, part_name, end_dates,
+            main_documents_dates, title_page_info, volume_notion, parallel_title_lang,
+            org_and_instance,
+        }
+    }
+}
+
+prop_compose! {
+    fn edition()(
+        edition in optional(),
+        additional_info in repeated(),
+        parallel_edition in repeated(),
+        main_responsibility in repeated(),
+        other_responsibility in repeated(),
+    ) -> Field205Edition {
+        Field205Edition {
+            edition, additional_info, parallel_edition, main_responsibility, other_responsibility,
+        }
+    }
+}
+
+prop_compose! {
+    fn publication()(
+        (place, address, publisher, date) in (repeated(), repeated(), repeated(), optional()),
+        (manufacture_place, manufacturer_address, manufacturer, manufacture_date)
+            in (repeated(), repeated(), repeated(), optional()),
+    ) -> Field210PublicationDistribution {
+        Field210PublicationDistribution {
+            place, address, publisher, date,
+            manufacture_place, manufacturer_address, manufacturer, manufacture_date,
+        }
+    }
+}
+
+prop_compose! {
+    fn physical_description()(
+        extent in repeated(),
+        other_details in optional(),
+        dimensions in repeated(),
+        accompanying_material in repeated(),
+    ) -> Field215PhysicalDescription {
+        Field215PhysicalDescription { extent, other_details, dimensions, accompanying_material }
+    }
+}
+
+prop_compose! {
+    fn personal_name()(
+        (entry_element, rest_of_name, additions, roman_numerals, dates)
+            in (text(), optional(), repeated(), optional(), optional()),
+        (expanded_initials, affiliation, authority_record_number, relator_codes)
+            in (optional(), optional(), optional(), repeated()),
+    ) -> PersonalName {
+        PersonalName {
+            entry_element, rest_of_name, additions, roman_numerals, dates,
+            expanded_initials, affiliation, authority_record_number, relator_codes,
+        }
+    }
+}
+
+prop_compose! {
+    fn corporate_name()(
+        (entry_element, subdivisions, additions, meeting_number)
+            in (text(), repeated(), repeated(), repeated()),
+        (meeting_place, meeting_date, authority_record_number, relator_codes)
+            in (optional(), optional(), optional(), repeated()),
+    ) -> CorporateName {
+        CorporateName {
+            entry_element, subdivisions, additions, meeting_number,
+            meeting_place, meeting_date, authority_record_number, relator_codes,
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn field_001(id in text()) {
+        round_trip(Field001RecordId { id })?;
+    }
+
+    #[test]
+    fn field_003(id in text()) {
+        round_trip(Field003PersistentRecordId { id })?;
+    }
+
+    #[test]
+    fn field_005(field in version()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_010(field in isbn()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_011(field in issn()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_013(field in ismn()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_073(field in ean()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_101(field in language()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_102(field in country()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_200(field in header()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_205(field in edition()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_210(field in publication()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_215(field in physical_description()) {
+        round_trip(field)?;
+    }
+
+    #[test]
+    fn field_330(text in text()) {
+        round_trip(Field330Summary { text })?;
+    }
+
+    #[test]
+    fn fields_70x(name in personal_name()) {
+        round_trip(Field700PersonPrimary { name: name.clone() })?;
+        round_trip(Field701PersonAlternative { name: name.clone() })?;
+        round_trip(Field702PersonSecondary { name })?;
+    }
+
+    #[test]
+    fn fields_71x(name in corporate_name()) {
+        round_trip(Field710OrganizationPrimary { name: name.clone() })?;
+        round_trip(Field711OrganizationAlternative { name: name.clone() })?;
+        round_trip(Field712OrganizationSecondary { name })?;
+    }
+}
@@ -0,0 +1,108 @@
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, Fields, Lit, Meta, Type};
+
+pub fn derive_into_field_data(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match derive_into_field_data_inner(input) {
+        Ok(ts) => ts,
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Every struct field becomes subfields, with the marker from its doc comment
+/// (`/// $a   Основное заглавие`): `Option` gives zero or one, `Vec` any
+/// number, anything else exactly one. Values are written with `to_string`.
+fn derive_into_field_data_inner(input: DeriveInput) -> Result<proc_macro::TokenStream, syn::Error> {
+    let name = input.ident;
+
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "Struct must have named fields",
+                ))
+            }
+        },
+        _ => return Err(syn::Error::new(name.span(), "Only structs can be derived")),
+    };
+
+    let mut pushes = Vec::new();
+    for field in fields {
+        let ident = field.ident.unwrap();
+        let marker = get_subfield_marker(&field.attrs).ok_or_else(|| {
+            syn::Error::new(
+                ident.span(),
+                "Field must be documented with its subfield marker (e.g. `/// $a   Заглавие`)",
+            )
+        })?;
+        let marker = marker.to_ascii_uppercase();
+
+        let push = match get_container(&field.ty).as_deref() {
+            Some("Option") => quote! {
+                if let Some(value) = &self.#ident {
+                    subfields.push(Subfield { marker: #marker, text: value.to_string() });
+                }
+            },
+            Some("Vec") => quote! {
+                for value in &self.#ident {
+                    subfields.push(Subfield { marker: #marker, text: value.to_string() });
+                }
+            },
+            _ => quote! {
+                subfields.push(Subfield { marker: #marker, text: self.#ident.to_string() });
+            },
+        };
+        pushes.push(push);
+    }
+
+    let expanded = quote! {
+        impl IntoFieldData for &#name {
+            fn into_field_data(self) -> FieldData {
+                let mut subfields = Vec::new();
+                #(#pushes)*
+                FieldData::Subfields { subfields }
+            }
+        }
+    };
+
+    Ok(proc_macro::TokenStream::from(expanded))
+}
+
+/// `a` from a `/// $a   ...` doc comment.
+fn get_subfield_marker(attrs: &[Attribute]) -> Option<char> {
+    attrs.iter().find_map(|attr| {
+        let Meta::NameValue(meta) = &attr.meta else {
+            return None;
+        };
+        if !meta.path.is_ident("doc") {
+            return None;
+        }
+        let Expr::Lit(expr) = &meta.value else {
+            return None;
+        };
+        let Lit::Str(text) = &expr.lit else {
+            return None;
+        };
+
+        let text = text.value();
+        let mut chars = text.trim_start().chars();
+        match (chars.next(), chars.next()) {
+            (Some('$'), Some(marker)) if marker.is_ascii_alphanumeric() => Some(marker),
+            _ => None,
+        }
+    })
+}
+
+/// `Option` of `Option<String>`, `Vec` of `Vec<String>`.
+fn get_container(ty: &Type) -> Option<String> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(_) => Some(segment.ident.to_string()),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
+mod derive_into_field_data;
 mod derive_parse_typed;
 mod derive_typed_field;
 mod proc_rusmarc_docs;
@@ -7,6 +8,10 @@ mod util;
 pub fn derive_typed_field(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_typed_field::derive_typed_field(input)
 }
+#[proc_macro_derive(IntoFieldData)]
+pub fn derive_into_field_data(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_into_field_data::derive_into_field_data(input)
+}
 #[proc_macro_derive(ParseTypedField)]
 pub fn derive_parse_typed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_parse_typed::derive_parse_typed(input)